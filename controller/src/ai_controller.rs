@@ -1,4 +1,4 @@
-use super::player_controller::PlayerController;
+use super::player_controller::{PlayerController, DIRECTIONS};
 
 use model::obstacle::Obstacle;
 use model::obstacle_table::ObstacleTable;
@@ -29,6 +29,14 @@ impl AIController {
             rng: RandomNumberGenerator::new(),
         }
     }
+
+    // picks its goals the same way every time for the same seed
+    pub fn seeded(start_x: i32, start_y: i32, seed: u64) -> Self {
+        AIController {
+            rng: RandomNumberGenerator::seeded(seed),
+            ..AIController::new(start_x, start_y)
+        }
+    }
 }
 
 impl AIController {
@@ -158,8 +166,8 @@ impl AIController {
         // iterate through all possible inputs to the player controller
         // and push the new player that is closest to the next
         // step in this controller's path (computed in choose_goal)
-        for key in player_control.get_keys() {
-            let mov = player_control.move_player(&obs_table, &player, key);
+        for direction in DIRECTIONS {
+            let mov = player_control.move_player(obs_table, player, direction);
 
             match mov.recent_event {
                 PlayerEvent::GameOver(_) | PlayerEvent::Respawn => {}
@@ -202,8 +210,8 @@ impl AIController {
 
         // iterate through the possible inputs and push any moves that end on a platform
         // along with the time it took to make the move
-        for key in player_control.get_keys() {
-            let mov = player_control.move_player(&obs_table, &player, key);
+        for direction in DIRECTIONS {
+            let mov = player_control.move_player(obs_table, player, direction);
             match mov.recent_event {
                 PlayerEvent::GameOver(_) | PlayerEvent::Respawn => {}
                 PlayerEvent::FallOver => {
@@ -0,0 +1,448 @@
+use rltk::VirtualKeyCode;
+
+use model::trick::Trick;
+
+// Everything a key can do while playing. Keys are looked up here and
+// nowhere else, so the help screen and the rebinding screen always show
+// what the game actually does
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    // push in a direction, (0, 0) waits
+    Move(i32, i32),
+    // switch between full and gentle pushes
+    PushStrength,
+    Trick(Trick),
+
+    // repeat what to do next
+    Hint,
+    // talk to a skater next to the player
+    Chat,
+    History,
+
+    // move the view without moving the player
+    Pan(i32, i32),
+    Minimap,
+    Trajectory,
+
+    NewGame,
+    Menu,
+}
+
+impl Action {
+    // in the order the help and rebinding screens list them
+    pub const ALL: [Action; 24] = [
+        Action::Move(0, -1),
+        Action::Move(-1, 0),
+        Action::Move(0, 1),
+        Action::Move(1, 0),
+        Action::Move(1, -1),
+        Action::Move(-1, -1),
+        Action::Move(-1, 1),
+        Action::Move(1, 1),
+        Action::Move(0, 0),
+        Action::PushStrength,
+        Action::Trick(Trick::Ollie),
+        Action::Trick(Trick::Manual),
+        Action::Trick(Trick::Bail),
+        Action::Hint,
+        Action::Chat,
+        Action::History,
+        Action::Pan(-1, 0),
+        Action::Pan(1, 0),
+        Action::Pan(0, -1),
+        Action::Pan(0, 1),
+        Action::Minimap,
+        Action::Trajectory,
+        Action::NewGame,
+        Action::Menu,
+    ];
+
+    // what the action is called in bindings.txt
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Move(0, -1) => "up",
+            Action::Move(-1, 0) => "left",
+            Action::Move(0, 1) => "down",
+            Action::Move(1, 0) => "right",
+            Action::Move(1, -1) => "up_right",
+            Action::Move(-1, -1) => "up_left",
+            Action::Move(-1, 1) => "down_left",
+            Action::Move(1, 1) => "down_right",
+            Action::Move(..) => "wait",
+            Action::PushStrength => "push_strength",
+            Action::Trick(Trick::Ollie) => "ollie",
+            Action::Trick(Trick::Manual) => "manual",
+            Action::Trick(Trick::Bail) => "bail",
+            Action::Hint => "hint",
+            Action::Chat => "chat",
+            Action::History => "history",
+            Action::Pan(-1, 0) => "look_left",
+            Action::Pan(1, 0) => "look_right",
+            Action::Pan(0, -1) => "look_up",
+            Action::Pan(..) => "look_down",
+            Action::Minimap => "minimap",
+            Action::Trajectory => "trajectory",
+            Action::NewGame => "new_game",
+            Action::Menu => "menu",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().find(|a| a.name() == name).copied()
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Move(0, -1) => "Up",
+            Action::Move(-1, 0) => "Left",
+            Action::Move(0, 1) => "Down",
+            Action::Move(1, 0) => "Right",
+            Action::Move(1, -1) => "NorthEast",
+            Action::Move(-1, -1) => "NorthWest",
+            Action::Move(-1, 1) => "SouthWest",
+            Action::Move(1, 1) => "SouthEast",
+            Action::Move(..) => "Wait (don't increase speed or turn)",
+            Action::PushStrength => "Full or Gentle Pushes",
+            Action::Trick(Trick::Ollie) => "Ollie (hop a pit or rail, costs speed)",
+            Action::Trick(Trick::Manual) => "Manual (keep speed, lose balance)",
+            Action::Trick(Trick::Bail) => "Bail (stop without falling)",
+            Action::Hint => "Help Message",
+            Action::Chat => "Get Information or Give Package",
+            Action::History => "Message History (scroll and filter)",
+            Action::Pan(-1, 0) => "Look Left",
+            Action::Pan(1, 0) => "Look Right",
+            Action::Pan(0, -1) => "Look Up",
+            Action::Pan(..) => "Look Down",
+            Action::Minimap => "Minimap",
+            Action::Trajectory => "Trajectory and Landing Spots",
+            Action::NewGame => "New Game",
+            Action::Menu => "Menu",
+        }
+    }
+
+    // the heading the action is listed under
+    pub fn section(&self) -> &'static str {
+        match self {
+            Action::Move(..) | Action::PushStrength => "Movement",
+            Action::Trick(_) => "Tricks",
+            Action::Hint | Action::Chat | Action::History => "Messages",
+            Action::Pan(..) | Action::Minimap | Action::Trajectory => "View",
+            Action::NewGame | Action::Menu => "Game Functions",
+        }
+    }
+
+    // moves or acts for the courier whose turn it is, the rest act on
+    // the screen the couriers share
+    pub fn is_courier_action(&self) -> bool {
+        matches!(
+            self,
+            Action::Move(..) | Action::PushStrength | Action::Trick(_) | Action::Chat
+        )
+    }
+}
+
+// Which keys trigger which action. Every action has a (possibly empty)
+// list of keys, a key should only be bound to one action
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bindings {
+    keys: Vec<(Action, Vec<VirtualKeyCode>)>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Bindings {
+            keys: Action::ALL.iter().map(|a| (*a, Vec::new())).collect(),
+        }
+    }
+}
+
+impl Default for Bindings {
+    // the same keys as raws/bindings.txt, for when it can't be read
+    fn default() -> Self {
+        use VirtualKeyCode::*;
+
+        let mut b = Bindings::new();
+        let defaults: [(Action, &[VirtualKeyCode]); 24] = [
+            (Action::Move(0, -1), &[W, K]),
+            (Action::Move(-1, 0), &[A, H]),
+            (Action::Move(0, 1), &[S, J]),
+            (Action::Move(1, 0), &[D, L]),
+            (Action::Move(1, -1), &[E, U]),
+            (Action::Move(-1, -1), &[Q, Y]),
+            (Action::Move(-1, 1), &[Z, B]),
+            (Action::Move(1, 1), &[C, N]),
+            (Action::Move(0, 0), &[Key1, Period]),
+            (Action::PushStrength, &[F]),
+            (Action::Trick(Trick::Ollie), &[Space]),
+            (Action::Trick(Trick::Manual), &[M]),
+            (Action::Trick(Trick::Bail), &[X]),
+            (Action::Hint, &[Semicolon]),
+            (Action::Chat, &[G]),
+            (Action::History, &[P]),
+            (Action::Pan(-1, 0), &[Left]),
+            (Action::Pan(1, 0), &[Right]),
+            (Action::Pan(0, -1), &[Up]),
+            (Action::Pan(0, 1), &[Down]),
+            (Action::Minimap, &[Tab]),
+            (Action::Trajectory, &[T]),
+            (Action::NewGame, &[Key5]),
+            (Action::Menu, &[Escape]),
+        ];
+        for (action, keys) in defaults.iter() {
+            b.set_keys(*action, keys.to_vec());
+        }
+        b
+    }
+}
+
+impl Bindings {
+    // the action key triggers. If a key ended up bound twice
+    // the action listed first wins
+    pub fn action(&self, key: VirtualKeyCode) -> Option<Action> {
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[VirtualKeyCode] {
+        match self.keys.iter().find(|(a, _)| *a == action) {
+            Some((_, keys)) => keys,
+            None => &[],
+        }
+    }
+
+    fn set_keys(&mut self, action: Action, keys: Vec<VirtualKeyCode>) {
+        if let Some((_, bound)) = self.keys.iter_mut().find(|(a, _)| *a == action) {
+            *bound = keys;
+        }
+    }
+
+    // adds key to action, unless another action already has it
+    pub fn bind(&mut self, action: Action, key: VirtualKeyCode) -> Result<(), Action> {
+        match self.action(key) {
+            Some(other) if other != action => Err(other),
+            Some(_) => Ok(()),
+            None => {
+                let mut keys = self.keys(action).to_vec();
+                keys.push(key);
+                self.set_keys(action, keys);
+                Ok(())
+            }
+        }
+    }
+
+    // makes key the only key of action, unless another action already has it
+    pub fn rebind(&mut self, action: Action, key: VirtualKeyCode) -> Result<(), Action> {
+        match self.action(key) {
+            Some(other) if other != action => Err(other),
+            _ => {
+                self.set_keys(action, vec![key]);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.set_keys(action, Vec::new());
+    }
+
+    // every key bound to more than one action, with the actions
+    pub fn conflicts(&self) -> Vec<(VirtualKeyCode, Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (i, (first, keys)) in self.keys.iter().enumerate() {
+            for key in keys.iter() {
+                for (second, other_keys) in self.keys[i + 1..].iter() {
+                    if other_keys.contains(key) {
+                        conflicts.push((*key, *first, *second));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    // a key for every direction and for waiting
+    pub fn can_skate(&self) -> bool {
+        Action::ALL
+            .iter()
+            .filter(|a| matches!(a, Action::Move(..)))
+            .all(|a| !self.keys(*a).is_empty())
+    }
+
+    fn unbind_key(&mut self, key: VirtualKeyCode) {
+        for (_, keys) in self.keys.iter_mut() {
+            keys.retain(|k| *k != key);
+        }
+    }
+
+    // the keys of action as they are shown to the player, like "W or K"
+    pub fn describe(&self, action: Action) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "(unbound)".to_string();
+        }
+        keys.iter()
+            .map(|k| key_label(*k))
+            .collect::<Vec<String>>()
+            .join(" or ")
+    }
+
+    pub fn to_config(&self) -> String {
+        let mut out = String::from("# <action> <key> [<key> ...]\n");
+        for (action, keys) in self.keys.iter() {
+            out.push_str(action.name());
+            for key in keys.iter() {
+                out.push(' ');
+                out.push_str(&key_name(*key));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // Each line is an action followed by its keys. Actions that are not
+    // listed have no keys, unknown actions and keys are skipped
+    pub fn from_config(raw_string: &str) -> Bindings {
+        let mut b = Bindings::new();
+
+        for line in raw_string.lines() {
+            let words: Vec<&str> = line.split_ascii_whitespace().collect();
+            if words.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(action) = Action::from_name(words[0]) {
+                let keys = words[1..].iter().filter_map(|w| key_from_name(w)).collect();
+                b.set_keys(action, keys);
+            }
+        }
+
+        b
+    }
+}
+
+pub fn load_bindings() -> Bindings {
+    match rltk::embedding::EMBED
+        .lock()
+        .get_resource("raws/bindings.txt".to_string())
+    {
+        None => Bindings::default(),
+        Some(raw_data) => {
+            let raw_string = std::str::from_utf8(raw_data)
+                .expect("Unable to convert to a valid UTF-8 string.");
+            Bindings::from_config(raw_string)
+        }
+    }
+}
+
+// what the second and third courier skate with when couriers share the
+// keyboard, in the order of the courier actions in Action::ALL
+const COURIER_KEYS: [[VirtualKeyCode; 14]; 2] = {
+    use VirtualKeyCode::*;
+    [
+        [
+            Up, Left, Down, Right, PageUp, Home, End, PageDown, Delete, Insert, Return, Slash,
+            Apostrophe, Backslash,
+        ],
+        [
+            Numpad8, Numpad4, Numpad2, Numpad6, Numpad9, Numpad7, Numpad1, Numpad3, Numpad5,
+            Numpad0, Back, Equals, Minus, Key0,
+        ],
+    ]
+};
+
+// the keys courier index starts with. The first courier has raws/bindings.txt,
+// the next two skate with the arrows and the numpad and share the first
+// courier's keys for everything else. The keyboard runs out of keys to skate
+// with after that, so a fourth courier binds their own
+pub fn courier_bindings(index: usize) -> Bindings {
+    let mut b = load_bindings();
+    if index == 0 {
+        return b;
+    }
+
+    let own = Action::ALL.iter().filter(|a| a.is_courier_action());
+    match COURIER_KEYS.get(index - 1) {
+        Some(keys) => {
+            for (action, key) in own.zip(keys.iter()) {
+                b.unbind_key(*key);
+                b.set_keys(*action, vec![*key]);
+            }
+        }
+        None => {
+            for action in own {
+                b.clear(*action);
+            }
+        }
+    }
+    b
+}
+
+// keys more than one courier skates or chats with, with the two couriers
+pub fn shared_keys(couriers: &[Bindings]) -> Vec<(VirtualKeyCode, usize, usize)> {
+    let own_keys = |b: &Bindings| -> Vec<VirtualKeyCode> {
+        Action::ALL
+            .iter()
+            .filter(|a| a.is_courier_action())
+            .flat_map(|a| b.keys(*a).to_vec())
+            .collect()
+    };
+
+    let mut shared = Vec::new();
+    for (first, bindings) in couriers.iter().enumerate() {
+        let others: Vec<Vec<VirtualKeyCode>> = couriers[first + 1..].iter().map(own_keys).collect();
+        for key in own_keys(bindings) {
+            for (offset, other_keys) in others.iter().enumerate() {
+                if other_keys.contains(&key) {
+                    shared.push((key, first, first + 1 + offset));
+                }
+            }
+        }
+    }
+    shared
+}
+
+// keys that can be bound, named in bindings.txt as rltk names them
+const BINDABLE_KEYS: [VirtualKeyCode; 72] = {
+    use VirtualKeyCode::*;
+    [
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Key0, Key1,
+        Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Left, Right, Up, Down, Space, Tab,
+        Return, Escape, Back, Period, Comma, Semicolon, Slash, Backslash, Minus, Equals,
+        Apostrophe, Grave, LBracket, RBracket, Home, End, PageUp, PageDown, Insert, Delete,
+        Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    ]
+};
+
+pub fn key_name(key: VirtualKeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn key_from_name(name: &str) -> Option<VirtualKeyCode> {
+    BINDABLE_KEYS.iter().find(|k| key_name(**k) == name).copied()
+}
+
+pub fn is_bindable(key: VirtualKeyCode) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
+
+// how a key is written on screen
+pub fn key_label(key: VirtualKeyCode) -> String {
+    match key {
+        VirtualKeyCode::Left => "←".to_string(),
+        VirtualKeyCode::Right => "→".to_string(),
+        VirtualKeyCode::Up => "↑".to_string(),
+        VirtualKeyCode::Down => "↓".to_string(),
+        VirtualKeyCode::Escape => "Esc".to_string(),
+        VirtualKeyCode::Return => "Enter".to_string(),
+        VirtualKeyCode::Back => "Backspace".to_string(),
+        _ => {
+            let name = key_name(key);
+            match name.strip_prefix("Key") {
+                Some(digit) => digit.to_string(),
+                None => name,
+            }
+        }
+    }
+}
@@ -1,18 +1,26 @@
 use std::collections::HashSet;
 
-use model::{obstacle::Obstacle, obstacle_table::ObstacleTable, player::Player};
+use model::{
+    obstacle::Obstacle, obstacle_table::ObstacleTable, player::Player, player_event::PlayerEvent,
+};
 use util::vec_ops;
 
 use crate::ai_controller::AIController;
+use crate::physics;
+use crate::player_controller::PlayerController;
 
+// every skater on the map takes up their tile, except opponents
+// waiting to respawn
 pub fn update_blocked(
     table: &mut ObstacleTable,
-    human: &Player,
+    humans: &[Player],
     ai: &Vec<AIController>,
     dead: &HashSet<u32>,
 ) {
     table.blocked.clear();
-    table.blocked.insert(human.position, Player::clone(human));
+    for human in humans.iter() {
+        table.blocked.insert(human.position, Player::clone(human));
+    }
     for p in ai.iter().enumerate() {
         if !dead.contains(&(p.0 as u32)) {
             table
@@ -22,6 +30,77 @@ pub fn update_blocked(
     }
 }
 
+// Resolves mover running into struck (mover's recent_event is Collide).
+// The skaters trade speeds like two equal masses, struck is shoved aside,
+// and both are pushed off balance by the difference in their speeds.
+// The slower skater takes the full push and the faster one half of it,
+// so whoever was already leaning or going slow is the one that goes over.
+// Returns the updated (mover, struck)
+pub fn resolve(
+    table: &ObstacleTable,
+    mover: &Player,
+    struck: &Player,
+    controller: &PlayerController,
+) -> (Player, Player) {
+    let impact = (
+        mover.speed_x() - struck.speed_x(),
+        mover.speed_y() - struck.speed_y(),
+    );
+    // by the mover's values, it's their push
+    let knock = vec_ops::magnitude(impact) * controller.physics_for(mover).knock_factor;
+    let norm_impact = vec_ops::normalize(impact);
+
+    let mut new_mover = Player::clone(mover);
+    new_mover.speed = struck.speed;
+
+    let mut new_struck = collide(table, struck, mover);
+    new_struck.speed = mover.speed;
+
+    if !f32::is_nan(norm_impact.0) {
+        let (mover_knock, struck_knock) =
+            if vec_ops::magnitude(mover.speed) > vec_ops::magnitude(struck.speed) {
+                (knock * 0.5, knock)
+            } else {
+                (knock, knock * 0.5)
+            };
+
+        // the mover is thrown back, the struck skater forward
+        new_mover.balance.0 -= norm_impact.0 * mover_knock;
+        new_mover.balance.1 -= norm_impact.1 * mover_knock;
+        new_struck.balance.0 += norm_impact.0 * struck_knock;
+        new_struck.balance.1 += norm_impact.1 * struck_knock;
+    }
+
+    (
+        knock_over(
+            table,
+            &new_mover,
+            controller.physics_for(mover).fallover_threshold,
+        ),
+        knock_over(
+            table,
+            &new_struck,
+            controller.physics_for(struck).fallover_threshold,
+        ),
+    )
+}
+
+fn knock_over(table: &ObstacleTable, player: &Player, fallover_threshold: f32) -> Player {
+    if vec_ops::magnitude(player.balance) >= fallover_threshold {
+        let mut fallen = physics::fallover(table, player);
+        if fallen.recent_event == PlayerEvent::FallOver {
+            fallen.recent_event = PlayerEvent::KnockedOver;
+        }
+        return fallen;
+    }
+
+    let mut clone = Player::clone(player);
+    clone.recent_event = PlayerEvent::Bumped;
+    clone
+}
+
+// slides slider one tile in the direction of the combined speed of
+// slider and collided, or the free neighbor closest to it
 pub fn collide(table: &ObstacleTable, slider: &Player, collided: &Player) -> Player {
     let mut slide = Player::clone(&slider);
     let new_speed = (
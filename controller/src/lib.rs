@@ -1,4 +1,10 @@
 pub mod ai_controller;
+pub mod bindings;
 pub mod collision;
 pub mod look_mode;
+pub mod physics;
 pub mod player_controller;
+pub mod profile;
+pub mod sandbox;
+pub mod shop;
+pub mod turn_scheduler;
@@ -0,0 +1,793 @@
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use model::surface::Surface;
+use model::traversability::Traversability;
+
+use util::vec_ops;
+
+// how the surfaces of platforms change the way the player moves
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceFactors {
+    // how far gravel pushes the player's balance sideways at max speed
+    pub gravel_drift: f32,
+
+    // pushes on a wet surface are multiplied by this
+    pub wet_grip: f32,
+
+    // turning on a wet surface affects balance this many times more
+    pub wet_turn_factor: f32,
+}
+
+// Every number the skating model runs on, see raws/model.txt for what
+// each one does and design/doc.txt for the equations
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsModel {
+    pub speed_damp: f32,
+    pub balance_damp: f32,
+    pub turn_factor: f32,
+    pub up_speed_factor: f32,
+    pub down_speed_factor: f32,
+    pub max_speed: f32,
+    pub fallover_threshold: f32,
+    pub inst_length: f32,
+    pub rail_boost: f32,
+    pub onrail_balance_factor: f32,
+    pub offrail_balance_factor: f32,
+    pub surface_factors: SurfaceFactors,
+
+    // skaters running into each other
+    pub knock_factor: f32,
+
+    // tricks, see PlayerController::trick
+    pub ollie_min_speed: f32,
+    pub ollie_speed_cost: f32,
+    pub manual_speed_damp: f32,
+    pub manual_balance_cost: f32,
+
+    // how many pieces the continuous integrator cuts a turn into
+    pub substeps: u32,
+}
+
+impl PhysicsModel {
+    // speed_damp such that pushing the same way every turn takes the player
+    // to about max_speed. After k pushes speed is
+    //     inst_length * (1 + damp + damp^2 + ... + damp^k)
+    // which grows with damp, so binary search for the damp that makes the
+    // sum over 64 turns max_speed / inst_length
+    pub fn speed_damp_for(max_speed: f32, inst_length: f32) -> f32 {
+        let k = 64;
+        let search_fn = |damp: f32| (damp.powf(k as f32 + 1.0) - 1.0) / (damp - 1.0);
+
+        let radius = 0.05;
+        let mut l = 0.0;
+        let mut r = 1.0;
+        let mut mid = (l + r) / 2.0;
+        let mut check = search_fn(mid);
+        while (check - (max_speed / inst_length)).abs() > radius && l < r {
+            if check - (max_speed / inst_length) > 0.0 {
+                r = mid;
+            } else {
+                l = mid;
+            }
+
+            mid = (l + r) / 2.0;
+            check = search_fn(mid);
+        }
+
+        mid
+    }
+
+    // how hard inst_v pushes, from 0 to 1. Pushes shorter than a unit
+    // vector are weaker, longer ones (like diagonals) are full pushes
+    pub fn push_strength(inst_v: (f32, f32)) -> f32 {
+        vec_ops::magnitude(inst_v).min(1.0)
+    }
+}
+
+// A value from raws/model.txt that can be changed after it is read, by
+// the sandbox or a skater's profile
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tunable {
+    BalanceDamp,
+    TurnFactor,
+    UpSpeedFactor,
+    DownSpeedFactor,
+    MaxSpeed,
+    FalloverThreshold,
+    InstLength,
+    RailBoost,
+    OnrailBalanceFactor,
+    OffrailBalanceFactor,
+    GravelDrift,
+    WetGrip,
+    WetTurnFactor,
+    KnockFactor,
+    OllieMinSpeed,
+    OllieSpeedCost,
+    ManualSpeedDamp,
+    ManualBalanceCost,
+    Substeps,
+}
+
+impl Tunable {
+    pub const ALL: [Tunable; 19] = [
+        Tunable::BalanceDamp,
+        Tunable::TurnFactor,
+        Tunable::UpSpeedFactor,
+        Tunable::DownSpeedFactor,
+        Tunable::MaxSpeed,
+        Tunable::FalloverThreshold,
+        Tunable::InstLength,
+        Tunable::RailBoost,
+        Tunable::OnrailBalanceFactor,
+        Tunable::OffrailBalanceFactor,
+        Tunable::GravelDrift,
+        Tunable::WetGrip,
+        Tunable::WetTurnFactor,
+        Tunable::KnockFactor,
+        Tunable::OllieMinSpeed,
+        Tunable::OllieSpeedCost,
+        Tunable::ManualSpeedDamp,
+        Tunable::ManualBalanceCost,
+        Tunable::Substeps,
+    ];
+
+    // what it is called in raws/model.txt
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tunable::BalanceDamp => "balance_damp",
+            Tunable::TurnFactor => "turn_factor",
+            Tunable::UpSpeedFactor => "up_speed_factor",
+            Tunable::DownSpeedFactor => "down_speed_factor",
+            Tunable::MaxSpeed => "max_speed",
+            Tunable::FalloverThreshold => "fallover_threshold",
+            Tunable::InstLength => "inst_length",
+            Tunable::RailBoost => "rail_boost",
+            Tunable::OnrailBalanceFactor => "onrail_balance_factor",
+            Tunable::OffrailBalanceFactor => "offrail_balance_factor",
+            Tunable::GravelDrift => "gravel_drift",
+            Tunable::WetGrip => "wet_grip",
+            Tunable::WetTurnFactor => "wet_turn_factor",
+            Tunable::KnockFactor => "knock_factor",
+            Tunable::OllieMinSpeed => "ollie_min_speed",
+            Tunable::OllieSpeedCost => "ollie_speed_cost",
+            Tunable::ManualSpeedDamp => "manual_speed_damp",
+            Tunable::ManualBalanceCost => "manual_balance_cost",
+            Tunable::Substeps => "substeps",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Tunable> {
+        Tunable::ALL.iter().find(|t| t.name() == name).copied()
+    }
+
+    // how much one key press changes it
+    pub fn step(&self) -> f32 {
+        match self {
+            Tunable::Substeps => 1.0,
+            Tunable::BalanceDamp | Tunable::WetGrip | Tunable::ManualSpeedDamp => 0.01,
+            _ => 0.05,
+        }
+    }
+
+    // the lowest and highest values that still make sense
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            Tunable::BalanceDamp
+            | Tunable::WetGrip
+            | Tunable::OllieSpeedCost
+            | Tunable::ManualSpeedDamp => (0.0, 0.99),
+            Tunable::MaxSpeed | Tunable::FalloverThreshold | Tunable::InstLength => (0.05, 10.0),
+            Tunable::Substeps => (1.0, 32.0),
+            _ => (0.0, 10.0),
+        }
+    }
+
+    pub fn get(&self, model: &PhysicsModel) -> f32 {
+        match self {
+            Tunable::BalanceDamp => model.balance_damp,
+            Tunable::TurnFactor => model.turn_factor,
+            Tunable::UpSpeedFactor => model.up_speed_factor,
+            Tunable::DownSpeedFactor => model.down_speed_factor,
+            Tunable::MaxSpeed => model.max_speed,
+            Tunable::FalloverThreshold => model.fallover_threshold,
+            Tunable::InstLength => model.inst_length,
+            Tunable::RailBoost => model.rail_boost,
+            Tunable::OnrailBalanceFactor => model.onrail_balance_factor,
+            Tunable::OffrailBalanceFactor => model.offrail_balance_factor,
+            Tunable::GravelDrift => model.surface_factors.gravel_drift,
+            Tunable::WetGrip => model.surface_factors.wet_grip,
+            Tunable::WetTurnFactor => model.surface_factors.wet_turn_factor,
+            Tunable::KnockFactor => model.knock_factor,
+            Tunable::OllieMinSpeed => model.ollie_min_speed,
+            Tunable::OllieSpeedCost => model.ollie_speed_cost,
+            Tunable::ManualSpeedDamp => model.manual_speed_damp,
+            Tunable::ManualBalanceCost => model.manual_balance_cost,
+            Tunable::Substeps => model.substeps as f32,
+        }
+    }
+
+    // sets the value, kept inside range(). speed_damp is worked
+    // out again when the values it comes from change
+    pub fn set(&self, model: &mut PhysicsModel, value: f32) {
+        let (min, max) = self.range();
+        let value = value.clamp(min, max);
+        match self {
+            Tunable::BalanceDamp => model.balance_damp = value,
+            Tunable::TurnFactor => model.turn_factor = value,
+            Tunable::UpSpeedFactor => model.up_speed_factor = value,
+            Tunable::DownSpeedFactor => model.down_speed_factor = value,
+            Tunable::MaxSpeed => model.max_speed = value,
+            Tunable::FalloverThreshold => model.fallover_threshold = value,
+            Tunable::InstLength => model.inst_length = value,
+            Tunable::RailBoost => model.rail_boost = value,
+            Tunable::OnrailBalanceFactor => model.onrail_balance_factor = value,
+            Tunable::OffrailBalanceFactor => model.offrail_balance_factor = value,
+            Tunable::GravelDrift => model.surface_factors.gravel_drift = value,
+            Tunable::WetGrip => model.surface_factors.wet_grip = value,
+            Tunable::WetTurnFactor => model.surface_factors.wet_turn_factor = value,
+            Tunable::KnockFactor => model.knock_factor = value,
+            Tunable::OllieMinSpeed => model.ollie_min_speed = value,
+            Tunable::OllieSpeedCost => model.ollie_speed_cost = value,
+            Tunable::ManualSpeedDamp => model.manual_speed_damp = value,
+            Tunable::ManualBalanceCost => model.manual_balance_cost = value,
+            Tunable::Substeps => model.substeps = value.round() as u32,
+        }
+
+        if let Tunable::MaxSpeed | Tunable::InstLength = self {
+            model.speed_damp = PhysicsModel::speed_damp_for(model.max_speed, model.inst_length);
+        }
+    }
+}
+
+// One turn of movement: how a push changes a skater's speed, balance and
+// position. Every skater, human or AI, moves with the same one
+pub trait Integrator {
+    // what it is called in raws/game.txt
+    fn name(&self) -> &'static str;
+
+    fn step(
+        &self,
+        model: &PhysicsModel,
+        table: &ObstacleTable,
+        player: &Player,
+        inst_v: (f32, f32),
+    ) -> Player;
+}
+
+// The original model: speed and balance are damped once per turn and
+// the push is added all at once
+pub struct Discrete;
+
+impl Integrator for Discrete {
+    fn name(&self) -> &'static str {
+        "discrete"
+    }
+
+    fn step(
+        &self,
+        model: &PhysicsModel,
+        table: &ObstacleTable,
+        player: &Player,
+        inst_v: (f32, f32),
+    ) -> Player {
+        let mut clone = Player::clone(player);
+        clone.speed = stop_if_slow(cap_speed(
+            push_speed(model, table, player, inst_v, 1.0),
+            model.max_speed,
+        ));
+        clone.balance = steady(lean(model, table, player, clone.speed, inst_v, 1.0));
+
+        let (clone, next_pos) = compute_next_position(table, &clone, inst_v);
+        finish_move(model, table, &clone, next_pos)
+    }
+}
+
+// Cuts the turn into model.substeps pieces, each damping by a fraction of
+// a turn and adding a fraction of the push. Turning into the push leans
+// less than the discrete model, since speed comes round during the turn
+pub struct Continuous;
+
+impl Integrator for Continuous {
+    fn name(&self) -> &'static str {
+        "continuous"
+    }
+
+    fn step(
+        &self,
+        model: &PhysicsModel,
+        table: &ObstacleTable,
+        player: &Player,
+        inst_v: (f32, f32),
+    ) -> Player {
+        let substeps = model.substeps.max(1);
+        let dt = 1.0 / substeps as f32;
+
+        let mut clone = Player::clone(player);
+        for _ in 0..substeps {
+            let speed = cap_speed(
+                push_speed(model, table, &clone, inst_v, dt),
+                model.max_speed,
+            );
+            clone.balance = lean(model, table, &clone, speed, inst_v, dt);
+            clone.speed = speed;
+        }
+        clone.speed = stop_if_slow(clone.speed);
+        clone.balance = steady(clone.balance);
+
+        let (clone, next_pos) = compute_next_position(table, &clone, inst_v);
+        finish_move(model, table, &clone, next_pos)
+    }
+}
+
+// Direct position control: every push moves the player one tile that way
+// and there is no momentum to keep balanced, only obstacles to avoid.
+// Gentle pushes are slower
+pub struct Arcade;
+
+impl Integrator for Arcade {
+    fn name(&self) -> &'static str {
+        "arcade"
+    }
+
+    fn step(
+        &self,
+        model: &PhysicsModel,
+        table: &ObstacleTable,
+        player: &Player,
+        inst_v: (f32, f32),
+    ) -> Player {
+        let mut clone = Player::clone(player);
+        clone.speed = get_scaled(inst_v, model.inst_length);
+        clone.balance = (0.0, 0.0);
+        clone.recent_event = PlayerEvent::Move;
+
+        let (dx, dy) = vec_ops::discrete_jmp(clone.speed);
+        let next_pos = (player.x() + dx, player.y() + dy);
+        if next_pos == player.xy() {
+            clone.recent_event = PlayerEvent::Wait;
+        } else if next_pos.0 < 0
+            || next_pos.1 < 0
+            || next_pos.0 >= table.width() as i32
+            || next_pos.1 >= table.height() as i32
+        {
+            clone = fallover(table, player);
+        }
+
+        finish_move(model, table, &clone, next_pos)
+    }
+}
+
+pub const INTEGRATORS: [&str; 3] = ["discrete", "continuous", "arcade"];
+
+// the integrator called name in raws/game.txt
+pub fn integrator(name: &str) -> Option<Box<dyn Integrator>> {
+    match name {
+        "discrete" => Some(Box::new(Discrete)),
+        "continuous" => Some(Box::new(Continuous)),
+        "arcade" => Some(Box::new(Arcade)),
+        _ => None,
+    }
+}
+
+// a push of inst_length in the direction of inst_v, times its strength
+fn get_scaled((inst_x, inst_y): (f32, f32), inst_length: f32) -> (f32, f32) {
+    let norm_inst = vec_ops::normalize((inst_x, inst_y));
+    if !f32::is_nan(norm_inst.0) {
+        let length = inst_length * PhysicsModel::push_strength((inst_x, inst_y));
+        return (norm_inst.0 * length, norm_inst.1 * length);
+    }
+    (0.0, 0.0)
+}
+
+// scales speed down to max_speed, keeping its direction
+fn cap_speed(speed: (f32, f32), max_speed: f32) -> (f32, f32) {
+    let magnitude = vec_ops::magnitude(speed);
+    if magnitude >= max_speed {
+        return (speed.0 * max_speed / magnitude, speed.1 * max_speed / magnitude);
+    }
+    speed
+}
+
+// too slow to keep rolling
+fn stop_if_slow(speed: (f32, f32)) -> (f32, f32) {
+    if vec_ops::magnitude(speed) < 0.5 {
+        return (0.0, 0.0);
+    }
+    speed
+}
+
+// leaning this little doesn't count
+fn steady(balance: (f32, f32)) -> (f32, f32) {
+    if vec_ops::magnitude(balance) <= 0.33 {
+        return (0.0, 0.0);
+    }
+    balance
+}
+
+// the player's speed after dt of a turn of pushing with inst_v
+fn push_speed(
+    model: &PhysicsModel,
+    table: &ObstacleTable,
+    player: &Player,
+    inst_v: (f32, f32),
+    dt: f32,
+) -> (f32, f32) {
+    let last_obstacle = table.get_obstacle(player.position.0, player.position.1);
+    let last_surface = table.get_surface(player.position.0, player.position.1);
+    let speed_damp = model.speed_damp.powf(dt);
+
+    // a wet surface gives less grip for pushing
+    let mut inst_length = model.inst_length * dt;
+    if last_obstacle == Obstacle::Platform && last_surface == Surface::Wet {
+        inst_length *= model.surface_factors.wet_grip;
+    }
+
+    let mut speed = player.speed;
+    match last_obstacle {
+        Obstacle::Platform => {
+            speed.0 *= speed_damp;
+            speed.1 *= speed_damp;
+
+            let add = get_scaled(inst_v, inst_length);
+            speed.0 += add.0;
+            speed.1 += add.1;
+        }
+        Obstacle::Rail(x_dir, y_dir) => {
+            // this will not be NaN, if it is it's a bug
+            // found two on the borders
+            let norm_dir = vec_ops::normalize((x_dir as f32, y_dir as f32));
+
+            speed.0 *= speed_damp;
+            speed.1 *= speed_damp;
+
+            if !f32::is_nan(norm_dir.0) {
+                speed.0 += norm_dir.0 * model.rail_boost * dt;
+                speed.1 += norm_dir.1 * model.rail_boost * dt;
+            }
+
+            let inst_add = get_scaled(inst_v, inst_length);
+            speed.0 += inst_add.0;
+            speed.1 += inst_add.1;
+
+            speed.0 = speed.0.clamp(-model.max_speed, model.max_speed);
+            speed.1 = speed.1.clamp(-model.max_speed, model.max_speed);
+        }
+        _ => {}
+    }
+
+    speed
+}
+
+// the player's balance after dt of a turn of pushing with inst_v,
+// which took them from player.speed to speed
+fn lean(
+    model: &PhysicsModel,
+    table: &ObstacleTable,
+    player: &Player,
+    speed: (f32, f32),
+    (inst_x, inst_y): (f32, f32),
+    dt: f32,
+) -> (f32, f32) {
+    let norm_inst = vec_ops::normalize((inst_x, inst_y));
+    let last_speed = player.speed;
+    let last_obstacle = table.get_obstacle(player.position.0, player.position.1);
+    let last_surface = table.get_surface(player.position.0, player.position.1);
+
+    // a wet surface makes turns harder
+    let mut inst_length = model.inst_length;
+    let mut turn_fact = model.turn_factor * dt;
+    if last_obstacle == Obstacle::Platform && last_surface == Surface::Wet {
+        inst_length *= model.surface_factors.wet_grip;
+        turn_fact *= model.surface_factors.wet_turn_factor;
+    }
+
+    let balance_damp = model.balance_damp.powf(dt);
+    let mut balance = (player.balance.0 * balance_damp, player.balance.1 * balance_damp);
+
+    let norm_last_speed = vec_ops::normalize(last_speed);
+    if !f32::is_nan(norm_inst.0) && !f32::is_nan(norm_last_speed.0) {
+        let inst_v = get_scaled((inst_x, inst_y), inst_length);
+
+        let diff = (last_speed.0 - inst_v.0, last_speed.1 - inst_v.1);
+
+        let dotp = vec_ops::dot(inst_v, last_speed);
+
+        let turn = (2.0 * vec_ops::magnitude(inst_v) * vec_ops::magnitude(last_speed) - dotp)
+            / (2.0 * vec_ops::magnitude(inst_v) * vec_ops::magnitude(last_speed));
+
+        // a gentle push against the skater's momentum is easier
+        // to stay upright through than a hard one
+        let turn = turn * PhysicsModel::push_strength((inst_x, inst_y));
+
+        balance.0 += diff.1.signum() * turn * turn_fact;
+
+        balance.1 += diff.0.signum() * turn * turn_fact;
+    }
+
+    // gravel pushes the player sideways, alternating sides from tile to tile
+    if last_obstacle == Obstacle::Platform && last_surface == Surface::Gravel {
+        let norm_speed = vec_ops::normalize(speed);
+        if !f32::is_nan(norm_speed.0) {
+            let side = if (player.x() + player.y()) % 2 == 0 {
+                1.0
+            } else {
+                -1.0
+            };
+            let drift = side * model.surface_factors.gravel_drift * vec_ops::magnitude(speed)
+                / model.max_speed
+                * dt;
+            balance.0 += -norm_speed.1 * drift;
+            balance.1 += norm_speed.0 * drift;
+        }
+    }
+
+    balance
+}
+
+// the rest of a turn once the integrator has worked out where the player
+// is headed: time passes, then they fall, drop into a pit or move
+fn finish_move(
+    model: &PhysicsModel,
+    table: &ObstacleTable,
+    player: &Player,
+    next_pos: (i32, i32),
+) -> Player {
+    let mut player = Player::clone(player);
+    player.time += 1.0 / (1.0 + vec_ops::magnitude(player.speed));
+
+    if player.recent_event == PlayerEvent::FallOver {
+        return player;
+    }
+
+    // fallover if the player is off balance
+    if vec_ops::magnitude(player.balance) >= model.fallover_threshold {
+        return fallover(table, &player);
+    }
+
+    // fall into a pit. Game Over
+    if let Obstacle::Pit = table.get_obstacle(next_pos.0, next_pos.1) {
+        // reset the player
+        player.recent_event = PlayerEvent::Respawn;
+    }
+
+    if let Obstacle::Wall = table.get_obstacle(next_pos.0, next_pos.1) {
+        return fallover(table, &player);
+    }
+
+    // try to move player to next_pos
+    // updating speed values depending on the change
+    // in height after the move
+    // and return the updated Player
+    try_traverse(model, table, &player, next_pos)
+}
+
+pub fn fallover(table: &ObstacleTable, player: &Player) -> Player {
+    let mut clone = Player::clone(player);
+    clone.recent_event = PlayerEvent::FallOver;
+    clone.speed = (0.0, 0.0);
+    clone.balance = (0.0, 0.0);
+    clone.n_falls += 1;
+
+    // off a rail onto the first platform next to it
+    if let Obstacle::Rail(_, _) = table.get_obstacle(player.x(), player.y()) {
+        let neighbors = vec_ops::neighbors(
+            player.xy(),
+            (0, 0),
+            (table.width() as i32 - 1, table.height() as i32 - 1),
+        );
+        let platform = neighbors
+            .into_iter()
+            .find(|n| table.get_obstacle(n.0, n.1) == Obstacle::Platform);
+        match platform {
+            Some(neighbor) => clone.position = (neighbor.0, neighbor.1),
+            None => clone.recent_event = PlayerEvent::Respawn,
+        }
+    }
+
+    clone
+}
+
+/*
+// updated a player's balance so it must return a new player as well
+fn compute_onrail(table: &ObstacleTable, player: &Player, (inst_x, inst_y): (f32, f32), (x_dir, y_dir): (f32, f32), onrail_balance_fact: f32, rail_boost: f32) -> (Player, (i32, i32, i32)) {
+    let (unit_x, unit_y) = vec_ops::discrete_jmp((inst_x, inst_y));
+    let mut next_pos = player.position;
+    next_pos.0 = (player.position.0 + unit_x).clamp(0, table.width() as i32 - 1);
+    next_pos.1 = (player.position.1 + unit_y).clamp(0, table.height() as i32 - 1);
+    next_pos.2 = table.get_height(next_pos.0, next_pos.1);
+
+    let mut clone = Player::clone(player);
+
+    let norm_speed = vec_ops::normalize(clone.speed);
+    let norm_dir = vec_ops::normalize((x_dir, y_dir));
+
+    if !f32::is_nan(norm_speed.0) && !f32::is_nan(norm_dir.0) {
+        let scaled_dir = (x_dir * rail_boost, y_dir * rail_boost);
+        let turn = vec_ops::magnitude(scaled_dir)*vec_ops::magnitude(clone.speed) - vec_ops::dot(clone.speed, scaled_dir);
+        clone.balance.0 += norm_dir.1.signum() *
+                           turn *
+                           onrail_balance_fact;
+
+        clone.balance.1 += norm_dir.0.signum() *
+                           turn *
+                           onrail_balance_fact;
+    }
+
+    (clone, next_pos)
+}
+*/
+
+fn compute_continue(table: &ObstacleTable, player: &Player) -> (i32, i32) {
+    let mut next_pos = player.position;
+
+    next_pos.0 = ((next_pos.0 as f32 + player.speed.0).round() as i32)
+        .clamp(next_pos.0 - 1, next_pos.0 + 1);
+    next_pos.0 = next_pos.0.clamp(0, table.width() as i32 - 1);
+
+    next_pos.1 = ((next_pos.1 as f32 + player.speed.1).round() as i32)
+        .clamp(next_pos.1 - 1, next_pos.1 + 1);
+    next_pos.1 = next_pos.1.clamp(0, table.height() as i32 - 1);
+
+    next_pos
+}
+
+// Note: the integrator must update speed and balance first, otherwise
+// this will compute the next position without taking into account user
+// input
+fn compute_next_position(
+    table: &ObstacleTable,
+    player: &Player,
+    (inst_x, inst_y): (f32, f32),
+) -> (Player, (i32, i32)) {
+    let mut next_pos = player.position;
+    let last_obstacle = table.get_obstacle(player.x(), player.y());
+    let units = vec_ops::discrete_jmp((inst_x, inst_y));
+    let unit_x = units.0;
+    let unit_y = units.1;
+
+    let mut clone = Player::clone(player);
+    clone.recent_event = PlayerEvent::Move;
+
+    // bump into border
+    if player.x() + unit_x >= table.width() as i32
+        || player.x() + unit_x < 0
+        || player.y() + unit_y >= table.height() as i32
+        || player.y() + unit_y < 0
+    {
+        clone = fallover(table, player);
+        return (clone, clone.position);
+    }
+
+    // compute position
+    let obs_at_next = table.get_obstacle(player.x() + unit_x, player.y() + unit_y);
+    match last_obstacle {
+        Obstacle::Rail(last_height, _) => match obs_at_next {
+            Obstacle::Rail(height, _) => {
+                if (height - last_height).abs() > 1
+                    || vec_ops::magnitude(player.speed).abs() < 0.1
+                {
+                    // fallover may have moved the player off the rail
+                    clone = fallover(table, player);
+                    return (clone, clone.position);
+                } else {
+                    next_pos = compute_continue(table, player);
+                    clone.recent_event = PlayerEvent::OffRail;
+                }
+            }
+            _ => {
+                next_pos = compute_continue(table, player);
+                clone.recent_event = PlayerEvent::OnRail;
+            }
+        },
+        Obstacle::Platform => {
+            match obs_at_next {
+                Obstacle::Rail(_x_dir, _y_dir) => {
+                    /*
+                    let result = compute_onrail(
+                        table,
+                        player,
+                        (inst_x, inst_y),
+                        (x_dir, y_dir),
+                        onrail_balance_fact,
+                        rail_boost,
+                    );
+                    clone = result.0;
+                    next_pos = result.1
+                    clone.recent_event = PlayerEvent::OnRail;
+                    */
+                    next_pos = compute_continue(table, player);
+                }
+                _ => {
+                    next_pos = compute_continue(table, player);
+                }
+            }
+        }
+
+        _ => {}
+    }
+
+    if next_pos.0 == player.x() && next_pos.1 == player.y() {
+        clone.recent_event = PlayerEvent::Wait;
+        return (clone, next_pos);
+    }
+
+    if next_pos.0 >= table.width() as i32
+        || next_pos.0 < 0
+        || next_pos.1 >= table.height() as i32
+        || next_pos.1 < 0
+    {
+        clone = fallover(table, &clone);
+        return (clone, clone.position);
+    }
+
+    (clone, next_pos)
+}
+
+fn try_traverse(
+    model: &PhysicsModel,
+    table: &ObstacleTable,
+    player: &Player,
+    next_pos: (i32, i32),
+) -> Player {
+    // check if next_pos is adjacent to current position
+    let mut clone = Player::clone(player);
+    let last_obstacle = table.get_obstacle(clone.x(), clone.y());
+
+    if table.can_traverse(player.xy(), (next_pos.0, next_pos.1)) {
+        if clone.x() != next_pos.0 || clone.y() != next_pos.1 {
+            // move player to next position
+            clone.position.0 = next_pos.0.clamp(0, table.width() as i32 - 1);
+            clone.position.1 = next_pos.1.clamp(0, table.height() as i32 - 1);
+
+            // ramps slow the player going up and speed them up going down,
+            // once as they get on the ramp however many tiles long it is
+            let on_ramp = table.get_surface(player.x(), player.y())
+                == table.get_surface(clone.x(), clone.y());
+            let slope_fact = match table.traversability(player.xy(), clone.xy()) {
+                Traversability::Up if !on_ramp => model.up_speed_factor,
+                Traversability::Down if !on_ramp => model.down_speed_factor,
+                _ => 1.0,
+            };
+            clone.speed.0 *= slope_fact;
+            clone.speed.1 *= slope_fact;
+            clone.speed = cap_speed(clone.speed, model.max_speed);
+
+            match table.get_obstacle(clone.x(), clone.y()) {
+                Obstacle::Platform => match last_obstacle {
+                    Obstacle::Platform => {
+                        clone.recent_event = PlayerEvent::Move;
+                    }
+                    Obstacle::Pit => {}
+                    Obstacle::Rail(_, _) => {
+                        clone.recent_event = PlayerEvent::OffRail;
+                    }
+                    _ => {}
+                },
+                Obstacle::Pit => {
+                    clone.recent_event = PlayerEvent::Respawn;
+                }
+                Obstacle::Rail(_, _) => {
+                    clone.recent_event = PlayerEvent::OnRail;
+                }
+                _ => {}
+            }
+        }
+
+    // ran into another skater, stay put and let the turn loop
+    // work out what happens to both of us (see collision::resolve)
+    } else if let Some(collided) = table.blocked.get(&next_pos) {
+        if collided.x() != clone.x() || collided.y() != clone.y() {
+            clone.recent_event = PlayerEvent::Collide(next_pos.0, next_pos.1);
+        }
+
+    // fallover if we cannot traverse to next_pos
+    // and do not update the player's position
+    } else {
+        clone = fallover(table, player);
+    }
+
+    clone
+}
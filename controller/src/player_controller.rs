@@ -1,35 +1,40 @@
-use rltk::VirtualKeyCode;
-
-use std::collections::HashMap;
 //use std::f32::consts::PI;
 
 use model::obstacle::Obstacle;
 use model::obstacle_table::ObstacleTable;
 use model::player::Player;
 use model::player_event::PlayerEvent;
+use model::trick::Trick;
 
 use util::vec_ops;
 
-use crate::collision;
+use crate::physics::{self, Integrator, PhysicsModel, SurfaceFactors};
+use crate::profile::{self, Board, Profile};
+
+// every push the player can make, including waiting
+pub const DIRECTIONS: [(i32, i32); 9] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (-1, -1),
+    (-1, 1),
+    (1, 1),
+    (0, 0),
+];
 
 pub struct PlayerController {
-    key_map: HashMap<VirtualKeyCode, (f32, f32)>,
-    pub speed_damp: f32,
-    pub balance_damp: f32,
-    pub turn_factor: f32,
-    pub onrail_balance_factor: f32,
-    pub offrail_balance_factor: f32,
-    pub up_speed_factor: f32,
-    pub down_speed_factor: f32,
-    pub max_speed: f32,
-    pub fallover_threshold: f32,
-    pub inst_length: f32,
-    pub rail_boost: f32,
+    pub physics: PhysicsModel,
+    integrator: Box<dyn Integrator>,
+    // kinds of skater, each changing some of physics
+    pub profiles: Vec<Profile>,
+    // boards from the shop, added on top of a profile. 0 changes nothing
+    pub boards: Vec<Board>,
 }
 
 impl PlayerController {
     pub fn new() -> Self {
-        let speed_damp: f32;
         let mut inst_length: f32 = 0.66;
         let mut rail_boost: f32 = 2.0;
         let mut balance_damp: f32 = 0.5;
@@ -40,6 +45,17 @@ impl PlayerController {
         let mut down_speed_factor: f32 = 1.5;
         let mut max_speed: f32 = 3.0;
         let mut fallover_threshold: f32 = 1.0;
+        let mut knock_factor: f32 = 0.5;
+        let mut ollie_min_speed: f32 = 1.0;
+        let mut ollie_speed_cost: f32 = 0.4;
+        let mut manual_speed_damp: f32 = 0.95;
+        let mut manual_balance_cost: f32 = 0.45;
+        let mut substeps: u32 = 4;
+        let mut surface_factors = SurfaceFactors {
+            gravel_drift: 0.3,
+            wet_grip: 0.6,
+            wet_turn_factor: 1.75,
+        };
 
         // read conf file
         let raw_data = rltk::embedding::EMBED
@@ -98,122 +114,115 @@ impl PlayerController {
                     if let Ok(num) = words[1].parse::<f32>() {
                         fallover_threshold = num;
                     }
+                } else if words[0] == "knock_factor" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        knock_factor = num;
+                    }
+                } else if words[0] == "ollie_min_speed" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        ollie_min_speed = num;
+                    }
+                } else if words[0] == "ollie_speed_cost" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        ollie_speed_cost = num;
+                    }
+                } else if words[0] == "manual_speed_damp" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        manual_speed_damp = num;
+                    }
+                } else if words[0] == "manual_balance_cost" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        manual_balance_cost = num;
+                    }
+                } else if words[0] == "substeps" {
+                    if let Ok(num) = words[1].parse::<u32>() {
+                        substeps = num;
+                    }
+                } else if words[0] == "gravel_drift" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        surface_factors.gravel_drift = num;
+                    }
+                } else if words[0] == "wet_grip" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        surface_factors.wet_grip = num;
+                    }
+                } else if words[0] == "wet_turn_factor" {
+                    if let Ok(num) = words[1].parse::<f32>() {
+                        surface_factors.wet_turn_factor = num;
+                    }
                 }
             }
         }
 
-        let k = 64;
-        let search_fn = |damp: f32| {
-            (damp.powf(k as f32 + 1.0) - 1.0) / 
-            (damp  - 1.0)
-        };
-
-        let radius = 0.05;
-        let mut l = 0.0;
-        let mut r = 1.0;
-        let mut mid = (l + r) / 2.0;
-        let mut check = search_fn(mid);
-        while (check - (max_speed / inst_length)).abs() > radius && l < r {
-            if check - (max_speed / inst_length) > 0.0 {
-                r = mid;
-            }
-            else {
-                l = mid;
-            }
-
-            mid = (l + r) / 2.0;
-            check = search_fn(mid);
-        }
-
-        speed_damp = mid;
+        let speed_damp = PhysicsModel::speed_damp_for(max_speed, inst_length);
         rltk::console::log(format!("speed damp: {}", speed_damp));
 
 
-        let mut pc = PlayerController {
-            key_map: HashMap::new(),
-            speed_damp,
-            balance_damp,
-            turn_factor,
-            onrail_balance_factor,
-            offrail_balance_factor,
-            up_speed_factor,
-            down_speed_factor,
-            max_speed,
-            fallover_threshold,
-            inst_length,
-            rail_boost,
-        };
-
-        // left
-        pc.key_map.insert(VirtualKeyCode::H, (-1.0, 0.0));
-        pc.key_map.insert(VirtualKeyCode::A, (-1.0, 0.0));
-
-        // right
-        pc.key_map.insert(VirtualKeyCode::L, (1.0, 0.0));
-        pc.key_map.insert(VirtualKeyCode::D, (1.0, 0.0));
-
-        // up
-        pc.key_map.insert(VirtualKeyCode::K, (0.0, -1.0));
-        pc.key_map.insert(VirtualKeyCode::W, (0.0, -1.0));
-
-        // down
-        pc.key_map.insert(VirtualKeyCode::J, (0.0, 1.0));
-        pc.key_map.insert(VirtualKeyCode::S, (0.0, 1.0));
-
-        // up right
-        pc.key_map.insert(VirtualKeyCode::U, (1.0, -1.0));
-        pc.key_map.insert(VirtualKeyCode::E, (1.0, -1.0));
-
-        // up left
-        pc.key_map.insert(VirtualKeyCode::Y, (-1.0, -1.0));
-        pc.key_map.insert(VirtualKeyCode::Q, (-1.0, -1.0));
-
-        // down left
-        pc.key_map.insert(VirtualKeyCode::B, (-1.0, 1.0));
-        pc.key_map.insert(VirtualKeyCode::Z, (-1.0, 1.0));
+        PlayerController {
+            physics: PhysicsModel {
+                speed_damp,
+                balance_damp,
+                turn_factor,
+                up_speed_factor,
+                down_speed_factor,
+                max_speed,
+                fallover_threshold,
+                inst_length,
+                rail_boost,
+                onrail_balance_factor,
+                offrail_balance_factor,
+                surface_factors,
+                knock_factor,
+                ollie_min_speed,
+                ollie_speed_cost,
+                manual_speed_damp,
+                manual_balance_cost,
+                substeps,
+            },
+            integrator: Box::new(physics::Discrete),
+            profiles: profile::load_profiles(),
+            boards: vec![Board::default()],
+        }
+    }
+}
 
-        // down right
-        pc.key_map.insert(VirtualKeyCode::N, (1.0, 1.0));
-        pc.key_map.insert(VirtualKeyCode::C, (1.0, 1.0));
+impl PlayerController {
+    pub fn set_physics(&mut self, physics: PhysicsModel) {
+        self.physics = physics;
+    }
 
-        // wait
-        pc.key_map.insert(VirtualKeyCode::Period, (0.0, 0.0));
-        pc.key_map.insert(VirtualKeyCode::Key1, (0.0, 0.0));
+    // every skater moves with the same integrator, see physics.rs
+    pub fn set_integrator(&mut self, integrator: Box<dyn Integrator>) {
+        self.integrator = integrator;
+    }
 
-        pc
+    pub fn integrator(&self) -> &dyn Integrator {
+        self.integrator.as_ref()
     }
-}
 
-impl PlayerController {
-    pub fn set_model(
-        &mut self,
-        speed_damp: f32,
-        balance_damp: f32,
-        turn_factor: f32,
-        onrail_balance_factor: f32,
-        offrail_balance_factor: f32,
-        up_speed_factor: f32,
-        down_speed_factor: f32,
-        max_speed: f32,
-        fallover_threshold: f32,
-    ) {
-        self.speed_damp = speed_damp;
-        self.balance_damp = balance_damp;
-        self.turn_factor = turn_factor;
-        self.onrail_balance_factor = onrail_balance_factor;
-        self.offrail_balance_factor = offrail_balance_factor;
-        self.up_speed_factor = up_speed_factor;
-        self.down_speed_factor = down_speed_factor;
-        self.max_speed = max_speed;
-        self.fallover_threshold = fallover_threshold;
+    // the values player moves with, physics changed by their profile
+    // and then by their board
+    pub fn physics_for(&self, player: &Player) -> PhysicsModel {
+        let model = match self.profiles.get(player.profile) {
+            Some(profile) => profile.apply(&self.physics),
+            None => self.physics,
+        };
+        match self.boards.get(player.board) {
+            Some(board) => board.apply(&model),
+            None => model,
+        }
     }
 
-    pub fn get_keys(&self) -> Vec<VirtualKeyCode> {
-        self.key_map.keys().map(|k| k.clone()).collect()
+    pub fn profile_name(&self, player: &Player) -> &str {
+        match self.profiles.get(player.profile) {
+            Some(profile) => &profile.name,
+            None => "",
+        }
     }
 
-    pub fn get_inst_velocity(&self, key: VirtualKeyCode) -> Option<&(f32, f32)> {
-        self.key_map.get(&key)
+    // the push for one of DIRECTIONS, before it is scaled by inst_length
+    pub fn get_inst_velocity(&self, (dx, dy): (i32, i32)) -> (f32, f32) {
+        (dx as f32, dy as f32)
     }
 
     pub fn move_player_vel(
@@ -222,495 +231,192 @@ impl PlayerController {
         player: &Player,
         inst_v: (f32, f32),
     ) -> Player {
-        return PlayerController::compute_move(
-            table,
-            player,
-            inst_v,
-            self.speed_damp,
-            self.balance_damp,
-            self.turn_factor,
-            self.up_speed_factor,
-            self.down_speed_factor,
-            self.max_speed,
-            self.fallover_threshold,
-            self.inst_length,
-            self.rail_boost,
-        );
+        self.integrator
+            .step(&self.physics_for(player), table, player, inst_v)
     }
 
     pub fn move_player(
         &self,
         table: &ObstacleTable,
         player: &Player,
-        key: VirtualKeyCode,
-    ) -> Player {
-        if let Some(inst_v) = self.get_inst_velocity(key) {
-            return PlayerController::compute_move(
-                table,
-                player,
-                *inst_v,
-                self.speed_damp,
-                self.balance_damp,
-                self.turn_factor,
-                self.up_speed_factor,
-                self.down_speed_factor,
-                self.max_speed,
-                self.fallover_threshold,
-                self.inst_length,
-                self.rail_boost,
-            );
-        }
-        return PlayerController::compute_move(
-            table,
-            player,
-            (0.0, 0.0),
-            self.speed_damp,
-            self.balance_damp,
-            self.turn_factor,
-            self.up_speed_factor,
-            self.down_speed_factor,
-            self.max_speed,
-            self.fallover_threshold,
-            self.inst_length,
-            self.rail_boost,
-        );
-    }
-
-    pub fn reset_player_gameover(
-        _table: &ObstacleTable,
-        player: &Player,
-        x: i32,
-        y: i32,
-    ) -> Player {
-        let mut clone = Player::clone(player);
-        clone.position = (x, y);
-        clone.speed = (0.0, 0.0);
-        clone.balance = (0.0, 0.0);
-        clone.recent_event = PlayerEvent::GameOver(clone.time.round() as i32);
-        clone.time = 0.0;
-        clone.n_falls = 0;
-        clone.n_delivered = 0;
-        clone
-    }
-
-    pub fn reset_player_continue(
-        _table: &ObstacleTable,
-        player: &Player,
-        x: i32,
-        y: i32,
+        direction: (i32, i32),
     ) -> Player {
-        let mut clone = Player::clone(player);
-        let last_pos = clone.position;
-        clone.position = (x, y);
-        clone.speed = (0.0, 0.0);
-        clone.balance = (0.0, 0.0);
-        clone.n_falls = 0;
-        clone.time += vec_ops::magnitude((
-            clone.x() as f32 - last_pos.0 as f32,
-            clone.y() as f32 - last_pos.1 as f32,
-        )) / 2.0;
-        clone.recent_event = PlayerEvent::Respawn;
-        clone
-    }
-
-    pub fn reset_ai_continue(player: &Player, x: i32, y: i32) -> Player {
-        let mut clone = Player::clone(player);
-
-        clone.position = (x, y);
-        clone.speed = (0.0, 0.0);
-        clone.balance = (0.0, 0.0);
-        clone.n_falls = 0;
-        clone
+        self.move_player_vel(table, player, self.get_inst_velocity(direction))
     }
 
-    pub fn compute_move(
+    // a push in direction scaled by magnitude, from 0 (no push) to 1 (a full
+    // push like move_player), for input that isn't all or nothing
+    pub fn move_player_analog(
+        &self,
         table: &ObstacleTable,
         player: &Player,
-        (inst_x, inst_y): (f32, f32),
-        speed_damp: f32,
-        balance_damp: f32,
-        turn_fact: f32,
-        up_speed_fact: f32,
-        down_speed_fact: f32,
-        max_speed: f32,
-        fallover_threshold: f32,
-        inst_length: f32,
-        rail_boost: f32,
+        direction: (i32, i32),
+        magnitude: f32,
     ) -> Player {
-        // compute initial speed and balance values
-        let mut player = PlayerController::compute_initial_speed_balance(
-            table,
-            player,
-            (inst_x, inst_y),
-            max_speed,
-            speed_damp,
-            balance_damp,
-            turn_fact,
-            inst_length,
-            rail_boost,
-        );
-
-        // compute position and updated player fields
-        let result = PlayerController::compute_next_position(table, &player, (inst_x, inst_y));
-
-        player = result.0;
-        let next_pos = result.1;
-
-        player.time += 1.0 / (1.0 + vec_ops::magnitude(player.speed));
-
-        match player.recent_event {
-            PlayerEvent::FallOver => {
-                return player;
-            }
-            _ => {}
-        }
-
-        // fallover if the player is off balance
-        if vec_ops::magnitude(player.balance) >= fallover_threshold {
-            return PlayerController::fallover(table, &player);
-        }
-
-        // fall into a pit. Game Over
-        if let Obstacle::Pit = table.get_obstacle(next_pos.0, next_pos.1) {
-            // reset the player
-            player.recent_event = PlayerEvent::Respawn;
-        }
-
-        if let Obstacle::Wall = table.get_obstacle(next_pos.0, next_pos.1) {
-            return PlayerController::fallover(table, &player);
-        }
-
-        // try to move player to next_pos
-        // updating speed values depending on the change
-        // in height after the move
-        // and return the updated Player
-        return PlayerController::try_traverse(
-            table,
-            &player,
-            next_pos,
-            up_speed_fact,
-            down_speed_fact,
-        );
+        let magnitude = magnitude.clamp(0.0, 1.0);
+        let (x, y) = self.get_inst_velocity(direction);
+        self.move_player_vel(table, player, (x * magnitude, y * magnitude))
     }
 
-    fn fallover(table: &ObstacleTable, player: &Player) -> Player {
-        let mut clone = Player::clone(player);
-        clone.recent_event = PlayerEvent::FallOver;
-        clone.speed = (0.0, 0.0);
-        clone.balance = (0.0, 0.0);
-        clone.n_falls += 1;
-
-        match table.get_obstacle(player.x(), player.y()) {
-            Obstacle::Rail(_, _) => {
-                let neighbors = vec_ops::neighbors(
-                    player.xy(),
-                    (0, 0),
-                    (table.width() as i32 - 1, table.height() as i32 - 1),
-                );
-                let mut found = false;
-
-                for neighbor in neighbors {
-                    match table.get_obstacle(neighbor.0, neighbor.1) {
-                        Obstacle::Platform => {
-                            clone.position = (neighbor.0, neighbor.1);
-                            found = true;
-                            break;
-                        }
-                        _ => {
-                            continue;
-                        }
-                    }
-                }
-                if !found {
-                    clone.recent_event = PlayerEvent::Respawn;
+    // where the player ends up after each of the next steps turns
+    // if they keep waiting. Stops early once they stop, fall or leave the map
+    pub fn predict_coast(&self, table: &ObstacleTable, player: &Player, steps: usize) -> Vec<Player> {
+        let mut path = Vec::new();
+        let mut current = *player;
+
+        for _ in 0..steps {
+            current = self.move_player_vel(table, &current, (0.0, 0.0));
+            match current.recent_event {
+                PlayerEvent::Wait => break,
+                PlayerEvent::FallOver | PlayerEvent::Respawn => {
+                    path.push(current);
+                    break;
                 }
+                _ => path.push(current),
             }
-            _ => {}
         }
 
-        clone
+        path
     }
 
-    fn get_scaled((inst_x, inst_y): (f32, f32), inst_length: f32) -> (f32, f32) {
-        let norm_inst = vec_ops::normalize((inst_x, inst_y));
-        if !f32::is_nan(norm_inst.0) {
-            return (norm_inst.0 * inst_length, norm_inst.1 * inst_length);
+    pub fn perform_trick(&self, table: &ObstacleTable, player: &Player, trick: Trick) -> Player {
+        match trick {
+            Trick::Ollie => self.ollie(table, player),
+            Trick::Manual => self.manual(table, player),
+            Trick::Bail => PlayerController::bail(table, player),
         }
-        (0.0, 0.0)
     }
-    fn compute_initial_speed_balance(
-        table: &ObstacleTable,
-        player: &Player,
-        (inst_x, inst_y): (f32, f32),
-        max_speed: f32,
-        speed_damp: f32,
-        balance_damp: f32,
-        turn_fact: f32,
-        inst_length: f32,
-        rail_boost: f32,
-    ) -> Player {
-        let norm_inst = vec_ops::normalize((inst_x, inst_y));
-        let last_speed = player.speed;
-        let last_obstacle = table.get_obstacle(player.position.0, player.position.1);
-
-        let mut clone = Player::clone(player);
-
-        match last_obstacle {
-            Obstacle::Platform => {
-                // compute speed
-                clone.speed.0 = clone.speed.0 * speed_damp;
-                clone.speed.1 = clone.speed.1 * speed_damp;
-
-                let add = PlayerController::get_scaled((inst_x, inst_y), inst_length);
-                clone.speed.0 += add.0;
-                clone.speed.1 += add.1;
-            }
-            Obstacle::Rail(x_dir, y_dir) => {
-                // compute speed
-
-                // this will not be NaN, if it is it's a bug
-                // found two on the borders
-                let norm_dir = vec_ops::normalize((x_dir as f32, y_dir as f32));
 
-                clone.speed.0 = clone.speed.0 * speed_damp;
-                clone.speed.1 = clone.speed.1 * speed_damp;
+    // jump in the direction of travel, clearing a pit or rail in the way.
+    // Too slow to get off the ground and it's just a push with no input
+    fn ollie(&self, table: &ObstacleTable, player: &Player) -> Player {
+        let model = self.physics_for(player);
+        if vec_ops::magnitude(player.speed) < model.ollie_min_speed {
+            return self.move_player_vel(table, player, (0.0, 0.0));
+        }
 
-                if !f32::is_nan(norm_dir.0) {
-                    clone.speed.0 += norm_dir.0 * rail_boost;
-                    clone.speed.1 += norm_dir.1 * rail_boost;
+        let dir = vec_ops::discrete_jmp(player.speed);
+        let over = (player.x() + dir.0, player.y() + dir.1);
+        let mut land = over;
+        if PlayerController::in_bounds(table, over) {
+            match table.get_obstacle(over.0, over.1) {
+                Obstacle::Pit | Obstacle::Rail(_, _) => {
+                    land = (over.0 + dir.0, over.1 + dir.1);
                 }
-
-                let inst_add = PlayerController::get_scaled((inst_x, inst_y), inst_length);
-
-                clone.speed.0 += inst_add.0;
-                clone.speed.1 += inst_add.1;
-
-                clone.speed.0 = clone.speed.0.clamp(-max_speed, max_speed);
-                clone.speed.1 = clone.speed.1.clamp(-max_speed, max_speed);
+                _ => {}
             }
-            _ => {}
         }
 
-        if vec_ops::magnitude(clone.speed) >= max_speed {
-            clone.speed.0 *= max_speed / vec_ops::magnitude(clone.speed);
-            clone.speed.1 *= max_speed / vec_ops::magnitude(clone.speed);
-        } else if vec_ops::magnitude(clone.speed) < 0.5 {
-            clone.speed.0 = 0.0;
-            clone.speed.1 = 0.0;
+        let mut clone = Player::clone(player);
+        clone.speed.0 *= 1.0 - model.ollie_speed_cost;
+        clone.speed.1 *= 1.0 - model.ollie_speed_cost;
+        clone.balance.0 *= model.balance_damp;
+        clone.balance.1 *= model.balance_damp;
+        clone.time += 1.0 / (1.0 + vec_ops::magnitude(clone.speed));
+
+        if !PlayerController::in_bounds(table, land)
+            || table.blocked.contains_key(&land)
+            || table.get_obstacle(land.0, land.1) == Obstacle::Wall
+        {
+            return physics::fallover(table, &clone);
         }
 
-        let norm_last_speed = vec_ops::normalize(last_speed);
-
-        clone.balance.0 = clone.balance.0 * balance_damp;
-        clone.balance.1 = clone.balance.1 * balance_damp;
-
-        if !f32::is_nan(norm_inst.0) && !f32::is_nan(norm_last_speed.0) {
-            let inst_v = PlayerController::get_scaled((inst_x, inst_y), inst_length);
-
-            let diff = (last_speed.0 - inst_v.0, last_speed.1 - inst_v.1);
-
-            let dotp = vec_ops::dot(inst_v, last_speed);
+        clone.position = land;
+        clone.recent_event = match table.get_obstacle(land.0, land.1) {
+            Obstacle::Pit => PlayerEvent::Respawn,
+            _ => PlayerEvent::Ollie,
+        };
 
-            let turn = (2.0 * vec_ops::magnitude(inst_v) * vec_ops::magnitude(last_speed) - dotp)
-                / (2.0 * vec_ops::magnitude(inst_v) * vec_ops::magnitude(last_speed));
+        clone
+    }
 
-            clone.balance.0 += diff.1.signum() as f32 * turn * turn_fact;
+    // coast without the usual loss of speed, but lean back while doing it
+    fn manual(&self, table: &ObstacleTable, player: &Player) -> Player {
+        let model = self.physics_for(player);
+        let model = PhysicsModel {
+            speed_damp: model.manual_speed_damp,
+            ..model
+        };
+        let mut clone = self.integrator.step(&model, table, player, (0.0, 0.0));
 
-            clone.balance.1 += diff.0.signum() as f32 * turn * turn_fact;
+        match clone.recent_event {
+            PlayerEvent::Move | PlayerEvent::Wait => {}
+            _ => return clone,
+        }
 
-            //clone.balance.0 += norm_inst.0 * turn_fact;
-            //clone.balance.1 += norm_inst.1 * turn_fact;
+        let norm_speed = vec_ops::normalize(clone.speed);
+        if !f32::is_nan(norm_speed.0) {
+            clone.balance.0 -= norm_speed.0 * model.manual_balance_cost;
+            clone.balance.1 -= norm_speed.1 * model.manual_balance_cost;
         }
 
-        if vec_ops::magnitude(clone.balance) <= 0.33 {
-            clone.balance = (0.0, 0.0);
+        if vec_ops::magnitude(clone.balance) >= model.fallover_threshold {
+            return physics::fallover(table, &clone);
         }
 
+        clone.recent_event = PlayerEvent::Manual;
         clone
     }
 
-    /*
-    // updated a player's balance so it must return a new player as well
-    fn compute_onrail(table: &ObstacleTable, player: &Player, (inst_x, inst_y): (f32, f32), (x_dir, y_dir): (f32, f32), onrail_balance_fact: f32, rail_boost: f32) -> (Player, (i32, i32, i32)) {
-        let (unit_x, unit_y) = vec_ops::discrete_jmp((inst_x, inst_y));
-        let mut next_pos = player.position;
-        next_pos.0 = (player.position.0 + unit_x).clamp(0, table.width() as i32 - 1);
-        next_pos.1 = (player.position.1 + unit_y).clamp(0, table.height() as i32 - 1);
-        next_pos.2 = table.get_height(next_pos.0, next_pos.1);
-
-        let mut clone = Player::clone(player);
-
-        let norm_speed = vec_ops::normalize(clone.speed);
-        let norm_dir = vec_ops::normalize((x_dir, y_dir));
-
-        if !f32::is_nan(norm_speed.0) && !f32::is_nan(norm_dir.0) {
-            let scaled_dir = (x_dir * rail_boost, y_dir * rail_boost);
-            let turn = vec_ops::magnitude(scaled_dir)*vec_ops::magnitude(clone.speed) - vec_ops::dot(clone.speed, scaled_dir);
-            clone.balance.0 += norm_dir.1.signum() *
-                               turn *
-                               onrail_balance_fact;
-
-            clone.balance.1 += norm_dir.0.signum() *
-                               turn *
-                               onrail_balance_fact;
+    // like falling over, but on purpose, so it doesn't count as a fall
+    fn bail(table: &ObstacleTable, player: &Player) -> Player {
+        let mut clone = physics::fallover(table, player);
+        clone.n_falls = player.n_falls;
+        clone.time += 1.0;
+        if clone.recent_event == PlayerEvent::FallOver {
+            clone.recent_event = PlayerEvent::Bail;
         }
-
-        (clone, next_pos)
+        clone
     }
-    */
-
-    fn compute_continue(table: &ObstacleTable, player: &Player) -> (i32, i32) {
-        let mut next_pos = player.position;
-
-        next_pos.0 = ((next_pos.0 as f32 + player.speed.0).round() as i32)
-            .clamp(next_pos.0 - 1, next_pos.0 + 1);
-        next_pos.0 = next_pos.0.clamp(0, table.width() as i32 - 1);
 
-        next_pos.1 = ((next_pos.1 as f32 + player.speed.1).round() as i32)
-            .clamp(next_pos.1 - 1, next_pos.1 + 1);
-        next_pos.1 = next_pos.1.clamp(0, table.height() as i32 - 1);
-
-        next_pos
+    fn in_bounds(table: &ObstacleTable, (x, y): (i32, i32)) -> bool {
+        x >= 0 && x < table.width() as i32 && y >= 0 && y < table.height() as i32
     }
 
-    // Note: Must call compute_intitial_speed_balance first in order to update speed and balance
-    // values, otherwise, this will compute the next position without taking into account user
-    // input
-    fn compute_next_position(
-        table: &ObstacleTable,
+    pub fn reset_player_gameover(
+        _table: &ObstacleTable,
         player: &Player,
-        (inst_x, inst_y): (f32, f32),
-    ) -> (Player, (i32, i32)) {
-        let mut next_pos = player.position;
-        let last_obstacle = table.get_obstacle(player.x(), player.y());
-        let units = vec_ops::discrete_jmp((inst_x, inst_y));
-        let unit_x = units.0;
-        let unit_y = units.1;
-
+        x: i32,
+        y: i32,
+    ) -> Player {
         let mut clone = Player::clone(player);
-        clone.recent_event = PlayerEvent::Move;
-
-        // bump into border
-        if player.x() + unit_x >= table.width() as i32
-            || player.x() + unit_x < 0
-            || player.y() + unit_y >= table.height() as i32
-            || player.y() + unit_y < 0
-        {
-            clone = PlayerController::fallover(table, player);
-            return (clone, clone.position);
-        }
-
-        // compute position
-        let obs_at_next = table.get_obstacle(player.x() + unit_x, player.y() + unit_y);
-        match last_obstacle {
-            Obstacle::Rail(last_height, _) => match obs_at_next {
-                Obstacle::Rail(height, _) => {
-                    if (height - last_height).abs() > 1
-                        || vec_ops::magnitude(player.speed).abs() < 0.1
-                    {
-                        clone = PlayerController::fallover(table, player);
-                    } else {
-                        next_pos = PlayerController::compute_continue(table, player);
-                        clone.recent_event = PlayerEvent::OffRail;
-                    }
-                }
-                _ => {
-                    next_pos = PlayerController::compute_continue(table, player);
-                    clone.recent_event = PlayerEvent::OnRail;
-                }
-            },
-            Obstacle::Platform => {
-                match obs_at_next {
-                    Obstacle::Rail(_x_dir, _y_dir) => {
-                        /*
-                        let result = PlayerController::compute_onrail(
-                            table,
-                            player,
-                            (inst_x, inst_y),
-                            (x_dir, y_dir),
-                            onrail_balance_fact,
-                            rail_boost,
-                        );
-                        clone = result.0;
-                        next_pos = result.1
-                        clone.recent_event = PlayerEvent::OnRail;
-                        */
-                        next_pos = PlayerController::compute_continue(table, player);
-                    }
-                    _ => {
-                        next_pos = PlayerController::compute_continue(table, player);
-                    }
-                }
-            }
-
-            _ => {}
-        }
-
-        if next_pos.0 == player.x() && next_pos.1 == player.y() {
-            clone.recent_event = PlayerEvent::Wait;
-            return (clone, next_pos);
-        }
-
-        if next_pos.0 >= table.width() as i32
-            || next_pos.0 < 0
-            || next_pos.1 >= table.height() as i32
-            || next_pos.1 < 0
-        {
-            clone = PlayerController::fallover(table, &clone);
-            return (clone, clone.position);
-        }
-
-        (clone, next_pos)
+        clone.position = (x, y);
+        clone.speed = (0.0, 0.0);
+        clone.balance = (0.0, 0.0);
+        clone.recent_event = PlayerEvent::GameOver(clone.time.round() as i32);
+        clone.time = 0.0;
+        clone.n_falls = 0;
+        clone.n_delivered = 0;
+        clone
     }
 
-    fn try_traverse(
-        table: &ObstacleTable,
+    pub fn reset_player_continue(
+        _table: &ObstacleTable,
         player: &Player,
-        next_pos: (i32, i32),
-        _up_speed_fact: f32,
-        _down_speed_fact: f32,
+        x: i32,
+        y: i32,
     ) -> Player {
-        // check if next_pos is adjacent to current position
         let mut clone = Player::clone(player);
-        let last_obstacle = table.get_obstacle(clone.x(), clone.y());
-
-        if table.can_traverse(player.xy(), (next_pos.0, next_pos.1)) {
-            if clone.x() != next_pos.0 || clone.y() != next_pos.1 {
-                // move player to next position
-                clone.position.0 = next_pos.0.clamp(0, table.width() as i32 - 1);
-                clone.position.1 = next_pos.1.clamp(0, table.height() as i32 - 1);
-
-                match table.get_obstacle(clone.x(), clone.y()) {
-                    Obstacle::Platform => match last_obstacle {
-                        Obstacle::Platform => {
-                            clone.recent_event = PlayerEvent::Move;
-                        }
-                        Obstacle::Pit => {}
-                        Obstacle::Rail(_, _) => {
-                            clone.recent_event = PlayerEvent::OffRail;
-                        }
-                        _ => {}
-                    },
-                    Obstacle::Pit => {
-                        clone.recent_event = PlayerEvent::Respawn;
-                    }
-                    Obstacle::Rail(_, _) => {
-                        clone.recent_event = PlayerEvent::OnRail;
-                    }
-                    _ => {}
-                }
-            }
+        let last_pos = clone.position;
+        clone.position = (x, y);
+        clone.speed = (0.0, 0.0);
+        clone.balance = (0.0, 0.0);
+        clone.n_falls = 0;
+        clone.time += vec_ops::magnitude((
+            clone.x() as f32 - last_pos.0 as f32,
+            clone.y() as f32 - last_pos.1 as f32,
+        )) / 2.0;
+        clone.recent_event = PlayerEvent::Respawn;
+        clone
+    }
 
-        // fallover if we cannot traverse to next_pos
-        // and do not update the player's position
-        } else if let Some(collided) = table.blocked.get(&next_pos) {
-            if collided.x() != clone.x() || collided.y() != clone.y() {
-                clone = collision::collide(table, &clone, collided)
-            }
-        } else {
-            clone = PlayerController::fallover(table, player);
-        }
+    pub fn reset_ai_continue(player: &Player, x: i32, y: i32) -> Player {
+        let mut clone = Player::clone(player);
 
+        clone.position = (x, y);
+        clone.speed = (0.0, 0.0);
+        clone.balance = (0.0, 0.0);
+        clone.n_falls = 0;
         clone
     }
 }
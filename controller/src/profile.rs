@@ -0,0 +1,122 @@
+use crate::physics::{PhysicsModel, Tunable};
+
+// A kind of skater: the values from model.txt with some of them changed,
+// see raws/skaters.txt
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub about: String,
+    overrides: Vec<(Tunable, f32)>,
+}
+
+impl Profile {
+    pub fn new(name: &str, about: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            about: about.to_string(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl Default for Profile {
+    // moves exactly as model.txt says
+    fn default() -> Self {
+        Profile::new("Standard", "Moves as model.txt says")
+    }
+}
+
+impl Profile {
+    pub fn set(&mut self, tunable: Tunable, value: f32) {
+        self.overrides.retain(|(t, _)| *t != tunable);
+        self.overrides.push((tunable, value));
+    }
+
+    pub fn overrides(&self) -> &[(Tunable, f32)] {
+        &self.overrides
+    }
+
+    // base with this profile's values in place of its own
+    pub fn apply(&self, base: &PhysicsModel) -> PhysicsModel {
+        let mut model = *base;
+        for (tunable, value) in self.overrides.iter() {
+            tunable.set(&mut model, *value);
+        }
+        model
+    }
+}
+
+// What a skater's board adds to the values their profile gives them,
+// bought in the shop. Unlike a profile's values these are changes, so
+// they add up
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Board {
+    changes: Vec<(Tunable, f32)>,
+}
+
+impl Board {
+    pub fn add(&mut self, tunable: Tunable, change: f32) {
+        match self.changes.iter_mut().find(|(t, _)| *t == tunable) {
+            Some((_, total)) => *total += change,
+            None => self.changes.push((tunable, change)),
+        }
+    }
+
+    pub fn changes(&self) -> &[(Tunable, f32)] {
+        &self.changes
+    }
+
+    // base with this board's changes added, kept in each value's range
+    pub fn apply(&self, base: &PhysicsModel) -> PhysicsModel {
+        let mut model = *base;
+        for (tunable, change) in self.changes.iter() {
+            let value = tunable.get(&model) + change;
+            tunable.set(&mut model, value);
+        }
+        model
+    }
+}
+
+// reads the profiles in the format of raws/skaters.txt. The first one is
+// the human's default, if there are none there is only Profile::default()
+pub fn profiles_from_config(config: &str) -> Vec<Profile> {
+    let mut profiles: Vec<Profile> = Vec::new();
+    for line in config.lines() {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        if words.len() < 2 || words[0].starts_with('#') {
+            continue;
+        }
+
+        if words[0] == "skater" {
+            profiles.push(Profile::new(&words[1..].join(" "), ""));
+        } else if let Some(profile) = profiles.last_mut() {
+            if words[0] == "about" {
+                profile.about = words[1..].join(" ");
+            } else if let Some(tunable) = Tunable::from_name(words[0]) {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    profile.set(tunable, num);
+                }
+            }
+        }
+    }
+
+    if profiles.is_empty() {
+        profiles.push(Profile::default());
+    }
+    profiles
+}
+
+// the profiles in raws/skaters.txt
+pub fn load_profiles() -> Vec<Profile> {
+    match rltk::embedding::EMBED
+        .lock()
+        .get_resource("raws/skaters.txt".to_string())
+    {
+        None => vec![Profile::default()],
+        Some(raw_data) => {
+            let raw_string = std::str::from_utf8(raw_data)
+                .expect("Unable to convert to a valid UTF-8 string.");
+            profiles_from_config(raw_string)
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+
+use crate::physics::{PhysicsModel, Tunable};
+
+// size of the empty map
+pub const ARENA_SIZE: (u32, u32) = (48, 32);
+
+// A skater on their own on an empty map (or a copy of the run's map) for
+// trying out the values in model.txt. Keeps the last few moves so their
+// speed and balance can be plotted
+pub struct Sandbox {
+    pub table: ObstacleTable,
+    pub start: (i32, i32),
+    pub player: Player,
+    // true when the table is a copy of the run's map
+    pub run_map: bool,
+
+    // every move since the last reset, oldest first, at most trace_length
+    trace: VecDeque<Player>,
+    trace_length: usize,
+    selected: usize,
+}
+
+impl Sandbox {
+    pub fn new(trace_length: usize) -> Self {
+        let (width, height) = ARENA_SIZE;
+        let mut sandbox = Sandbox {
+            table: ObstacleTable::new(width, height),
+            start: (0, 0),
+            player: Player::new(0, 0),
+            run_map: false,
+            trace: VecDeque::new(),
+            trace_length,
+            selected: 0,
+        };
+        sandbox.empty_map();
+        sandbox
+    }
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox::new(40)
+    }
+}
+
+impl Sandbox {
+    // flat concrete with the skater in the middle
+    pub fn empty_map(&mut self) {
+        let (width, height) = ARENA_SIZE;
+        self.table = ObstacleTable::new(width, height);
+        self.table.update_platforms();
+        self.table.compute_unions();
+        self.start = (width as i32 / 2, height as i32 / 2);
+        self.run_map = false;
+        self.reset();
+    }
+
+    // the obstacles and surfaces of table without any other skaters,
+    // starting from start
+    pub fn copy_map(&mut self, table: &ObstacleTable, start: (i32, i32)) {
+        self.table = table.copy_layout();
+        self.start = start;
+        self.run_map = true;
+        self.reset();
+    }
+
+    // puts the skater back at the start, standing still
+    pub fn reset(&mut self) {
+        self.player = Player::new(self.start.0, self.start.1);
+        self.trace.clear();
+    }
+
+    // the skater after their latest move. Dropping into a pit (or falling
+    // off a rail with nowhere to land) puts them back at the start
+    pub fn record(&mut self, moved: Player) {
+        self.trace.push_back(moved);
+        while self.trace.len() > self.trace_length {
+            self.trace.pop_front();
+        }
+
+        self.player = moved;
+        if moved.recent_event == PlayerEvent::Respawn {
+            self.player.position = self.start;
+            self.player.speed = (0.0, 0.0);
+            self.player.balance = (0.0, 0.0);
+        }
+    }
+
+    pub fn trace(&self) -> &VecDeque<Player> {
+        &self.trace
+    }
+
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    pub fn selected(&self) -> Tunable {
+        Tunable::ALL[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % Tunable::ALL.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + Tunable::ALL.len() - 1) % Tunable::ALL.len();
+    }
+
+    // changes the selected value by steps key presses, negative to lower it
+    pub fn adjust(&self, model: &mut PhysicsModel, steps: i32) {
+        let tunable = self.selected();
+        let value = tunable.get(model) + tunable.step() * steps as f32;
+        tunable.set(model, value);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::physics::Tunable;
+use crate::profile::Board;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemKind {
+    // stays on the board, with at most this many levels
+    Upgrade(u32),
+    // lasts for the next run
+    Consumable,
+}
+
+// Something to buy between runs, see raws/shop.txt
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item {
+    // what gets saved, name is what gets shown
+    pub id: String,
+    pub name: String,
+    pub about: String,
+    pub price: i32,
+    pub kind: ItemKind,
+    // what one level does to the board
+    changes: Vec<(Tunable, f32)>,
+}
+
+impl Item {
+    pub fn new(id: &str, kind: ItemKind) -> Self {
+        Item {
+            id: id.to_string(),
+            name: id.to_string(),
+            about: String::new(),
+            price: 0,
+            kind,
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl Item {
+    pub fn add_change(&mut self, tunable: Tunable, change: f32) {
+        self.changes.push((tunable, change));
+    }
+
+    pub fn changes(&self) -> &[(Tunable, f32)] {
+        &self.changes
+    }
+}
+
+// why the shop won't sell something
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Refusal {
+    TooExpensive,
+    MaxedOut,
+    AlreadyStocked,
+}
+
+impl Refusal {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Refusal::TooExpensive => "You can't afford that yet",
+            Refusal::MaxedOut => "That's as good as it gets",
+            Refusal::AlreadyStocked => "You already have one for the next run",
+        }
+    }
+}
+
+// The player's money, skater and what they bought, kept between games.
+// Saved as text:
+// "money <amount>", "skater <name>", "upgrade <id> <level>", "stock <id>"
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub money: i32,
+    // name of the profile they skate with
+    pub skater: String,
+    levels: Vec<(String, u32)>,
+    // consumables bought for the next run
+    stocked: Vec<String>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Progress {
+            money: 0,
+            skater: String::new(),
+            levels: Vec::new(),
+            stocked: Vec::new(),
+        }
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress::new()
+    }
+}
+
+impl Progress {
+    // levels bought of an upgrade
+    pub fn level(&self, id: &str) -> u32 {
+        match self.levels.iter().find(|(i, _)| i == id) {
+            Some((_, level)) => *level,
+            None => 0,
+        }
+    }
+
+    pub fn is_stocked(&self, id: &str) -> bool {
+        self.stocked.iter().any(|i| i == id)
+    }
+
+    // what buying item costs now, every level of an upgrade costs more
+    pub fn price(&self, item: &Item) -> i32 {
+        match item.kind {
+            ItemKind::Upgrade(_) => item.price * (self.level(&item.id) as i32 + 1),
+            ItemKind::Consumable => item.price,
+        }
+    }
+
+    pub fn buy(&mut self, item: &Item) -> Result<(), Refusal> {
+        match item.kind {
+            ItemKind::Upgrade(levels) if self.level(&item.id) >= levels => {
+                return Err(Refusal::MaxedOut)
+            }
+            ItemKind::Consumable if self.is_stocked(&item.id) => {
+                return Err(Refusal::AlreadyStocked)
+            }
+            _ => {}
+        }
+
+        let price = self.price(item);
+        if price > self.money {
+            return Err(Refusal::TooExpensive);
+        }
+        self.money -= price;
+
+        match item.kind {
+            ItemKind::Upgrade(_) => {
+                match self.levels.iter_mut().find(|(i, _)| *i == item.id) {
+                    Some((_, level)) => *level += 1,
+                    None => self.levels.push((item.id.clone(), 1)),
+                }
+            }
+            ItemKind::Consumable => self.stocked.push(item.id.clone()),
+        }
+        Ok(())
+    }
+
+    // the payout of a finished run, which uses up the consumables
+    pub fn end_run(&mut self, earned: i32) {
+        self.money += earned.max(0);
+        self.stocked.clear();
+    }
+
+    // every upgrade at its level and the consumables for the next run.
+    // Things saved that aren't in items any more do nothing
+    pub fn board(&self, items: &[Item]) -> Board {
+        let mut board = Board::default();
+        for item in items.iter() {
+            let times = match item.kind {
+                ItemKind::Upgrade(levels) => self.level(&item.id).min(levels),
+                ItemKind::Consumable => self.is_stocked(&item.id) as u32,
+            };
+            if times == 0 {
+                continue;
+            }
+            for (tunable, change) in item.changes() {
+                board.add(*tunable, change * times as f32);
+            }
+        }
+        board
+    }
+
+    pub fn to_config(&self) -> String {
+        let mut out = format!("money {}\n", self.money);
+        if !self.skater.is_empty() {
+            out.push_str(&format!("skater {}\n", self.skater));
+        }
+        for (id, level) in self.levels.iter() {
+            out.push_str(&format!("upgrade {} {}\n", id, level));
+        }
+        for id in self.stocked.iter() {
+            out.push_str(&format!("stock {}\n", id));
+        }
+        out
+    }
+
+    // lines that don't parse are skipped
+    pub fn from_config(raw_string: &str) -> Progress {
+        let mut progress = Progress::new();
+
+        for line in raw_string.lines() {
+            let words: Vec<&str> = line.split_ascii_whitespace().collect();
+            if words.len() < 2 || line.starts_with('#') {
+                continue;
+            }
+
+            if words[0] == "money" {
+                if let Ok(num) = words[1].parse::<i32>() {
+                    progress.money = num.max(0);
+                }
+            } else if words[0] == "skater" {
+                progress.skater = words[1..].join(" ");
+            } else if words[0] == "upgrade" && words.len() > 2 {
+                if let Ok(num) = words[2].parse::<u32>() {
+                    progress.levels.retain(|(i, _)| i != words[1]);
+                    progress.levels.push((words[1].to_string(), num));
+                }
+            } else if words[0] == "stock" && !progress.is_stocked(words[1]) {
+                progress.stocked.push(words[1].to_string());
+            }
+        }
+
+        progress
+    }
+}
+
+// reads the items in the format of raws/shop.txt, in the order they
+// are sold
+pub fn items_from_config(config: &str) -> Vec<Item> {
+    let mut items: Vec<Item> = Vec::new();
+    for line in config.lines() {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        if words.len() < 2 || words[0].starts_with('#') {
+            continue;
+        }
+
+        if words[0] == "upgrade" {
+            items.push(Item::new(words[1], ItemKind::Upgrade(1)));
+        } else if words[0] == "consumable" {
+            items.push(Item::new(words[1], ItemKind::Consumable));
+        } else if let Some(item) = items.last_mut() {
+            if words[0] == "name" {
+                item.name = words[1..].join(" ");
+            } else if words[0] == "about" {
+                item.about = words[1..].join(" ");
+            } else if words[0] == "price" {
+                if let Ok(num) = words[1].parse::<i32>() {
+                    item.price = num.max(0);
+                }
+            } else if words[0] == "levels" {
+                if let (Ok(num), ItemKind::Upgrade(_)) = (words[1].parse::<u32>(), item.kind) {
+                    item.kind = ItemKind::Upgrade(num);
+                }
+            } else if let Some(tunable) = Tunable::from_name(words[0]) {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    item.add_change(tunable, num);
+                }
+            }
+        }
+    }
+    items
+}
+
+// the items in raws/shop.txt, none if it's missing
+pub fn load_items() -> Vec<Item> {
+    match rltk::embedding::EMBED
+        .lock()
+        .get_resource("raws/shop.txt".to_string())
+    {
+        None => Vec::new(),
+        Some(raw_data) => {
+            let raw_string = std::str::from_utf8(raw_data)
+                .expect("Unable to convert to a valid UTF-8 string.");
+            items_from_config(raw_string)
+        }
+    }
+}
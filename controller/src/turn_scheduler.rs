@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use util::heap::{Heap, HeapHandle};
+use util::vec_ops;
+
+// game time is kept in whole ticks so the order of turns
+// never depends on floating point rounding
+pub const TICKS_PER_TURN: u64 = 1000;
+
+// Decides who moves next. Every actor has the tick at which it may act again,
+// and acting pushes that tick back by the time the move took. Moves take
+// 1 / (1 + |speed|) turns (the same amount every integrator adds to Player::time),
+// so a skater moving at speed 2 gets three moves for every move of a skater
+// standing still.
+pub struct TurnScheduler<K>
+where
+    K: Copy,
+    K: Eq,
+    K: Hash,
+{
+    now: u64,
+
+    // keyed by the tick each actor is ready to act at
+    queue: Heap<u64, K>,
+    handles: HashMap<K, HeapHandle>,
+}
+
+impl<K> TurnScheduler<K>
+where
+    K: Copy,
+    K: Eq,
+    K: Hash,
+{
+    pub fn new() -> Self {
+        TurnScheduler {
+            now: 0,
+            queue: Heap::new(),
+            handles: HashMap::new(),
+        }
+    }
+}
+
+impl<K> Default for TurnScheduler<K>
+where
+    K: Copy,
+    K: Eq,
+    K: Hash,
+{
+    fn default() -> Self {
+        TurnScheduler::new()
+    }
+}
+
+impl<K> TurnScheduler<K>
+where
+    K: Copy,
+    K: Eq,
+    K: Hash,
+{
+    // the number of ticks a move made at this speed takes
+    pub fn turn_ticks(speed: (f32, f32)) -> u64 {
+        let duration = TICKS_PER_TURN as f32 / (1.0 + vec_ops::magnitude(speed));
+        (duration.round() as u64).max(1)
+    }
+
+    pub fn ticks_to_time(ticks: u64) -> f32 {
+        ticks as f32 / TICKS_PER_TURN as f32
+    }
+
+    // current game time in ticks
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    // current game time in turns, comparable with Player::time
+    pub fn time(&self) -> f32 {
+        TurnScheduler::<K>::ticks_to_time(self.now)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.handles.clear();
+        self.now = 0;
+    }
+
+    pub fn contains(&self, actor: K) -> bool {
+        self.handles.contains_key(&actor)
+    }
+
+    // lets actor act again delay ticks from now,
+    // replacing any turn it already had scheduled
+    pub fn schedule(&mut self, actor: K, delay: u64) {
+        self.remove(actor);
+        let handle = self.queue.insert(self.now + delay, actor);
+        self.handles.insert(actor, handle);
+    }
+
+    // schedules actor's next turn after a move made at speed
+    pub fn schedule_after_move(&mut self, actor: K, speed: (f32, f32)) -> u64 {
+        let delay = TurnScheduler::<K>::turn_ticks(speed);
+        self.schedule(actor, delay);
+        delay
+    }
+
+    pub fn remove(&mut self, actor: K) -> bool {
+        match self.handles.remove(&actor) {
+            None => false,
+            Some(handle) => self.queue.remove(handle).is_some(),
+        }
+    }
+
+    // the actor that will act next, without advancing time
+    pub fn peek(&self) -> Option<K> {
+        self.queue.peek().map(|(_, actor)| *actor)
+    }
+
+    // removes the actor that acts next and advances time to its turn.
+    // Actors ready on the same tick go in the order they were scheduled
+    pub fn next_turn(&mut self) -> Option<K> {
+        let (ready_at, actor) = self.queue.extract_min_with_key()?;
+        self.handles.remove(&actor);
+        self.now = self.now.max(ready_at);
+        Some(actor)
+    }
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use controller::look_mode::LookMode;
+use controller::physics::PhysicsModel;
+use controller::player_controller::DIRECTIONS;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::visibility;
+use util::vec_ops;
+
+use common::controller;
+
+fn open_table() -> ObstacleTable {
+    let mut table = ObstacleTable::new(20, 20);
+    table.update_platforms();
+    table
+}
+
+fn assert_same_move(a: &Player, b: &Player) {
+    assert_eq!(a.position, b.position);
+    assert_eq!(a.speed, b.speed);
+    assert_eq!(a.balance, b.balance);
+    assert_eq!(a.recent_event, b.recent_event);
+}
+
+#[test]
+fn full_push_is_a_normal_move() {
+    let pc = controller();
+    let table = open_table();
+    let player = Player::new(10, 10);
+
+    for direction in DIRECTIONS {
+        let full = pc.move_player(&table, &player, direction);
+        assert_same_move(&pc.move_player_analog(&table, &player, direction, 1.0), &full);
+        // stronger than full is still full
+        assert_same_move(&pc.move_player_analog(&table, &player, direction, 3.0), &full);
+    }
+}
+
+#[test]
+fn no_push_is_waiting() {
+    let pc = controller();
+    let table = open_table();
+    let player = Player::new(10, 10);
+
+    assert_same_move(
+        &pc.move_player_analog(&table, &player, (1, 0), 0.0),
+        &pc.move_player(&table, &player, (0, 0)),
+    );
+}
+
+#[test]
+fn gentle_push_adds_less_speed() {
+    let pc = controller();
+    let table = open_table();
+    // already rolling, from a standstill a gentle push is too weak to start
+    let mut player = Player::new(10, 10);
+    player.speed = (1.0, 0.0);
+
+    let gentle = pc.move_player_analog(&table, &player, (1, 0), 0.5);
+    let full = pc.move_player_analog(&table, &player, (1, 0), 1.0);
+
+    assert!(gentle.speed.0 > 0.0);
+    assert!(vec_ops::magnitude(gentle.speed) < vec_ops::magnitude(full.speed));
+}
+
+#[test]
+fn describes_pointed_at_tiles() {
+    let look = LookMode::new();
+    let mut table = open_table();
+    table.set_obstacle((12, 10), Obstacle::Wall);
+    let player = Player::new(10, 10);
+
+    assert_eq!(look.describe_tile(&table, &player, (12, 10)), "Unknown");
+
+    visibility::update_visibility(&mut table, player.xy(), 8, 0.0);
+    assert_eq!(look.describe_tile(&table, &player, (10, 10)), "You");
+    assert_eq!(look.describe_tile(&table, &player, (12, 10)), "Wall");
+    assert_eq!(look.describe_tile(&table, &player, (9, 9)), "Platform");
+    assert_eq!(look.describe_tile(&table, &player, (-1, 9)), "");
+}
+
+#[test]
+fn hard_pushes_against_momentum_cost_more_balance() {
+    let pc = controller();
+    let table = open_table();
+    let mut player = Player::new(10, 10);
+    player.speed = (2.0, 0.0);
+
+    let gentle = pc.move_player_analog(&table, &player, (-1, 0), 0.5);
+    let full = pc.move_player_analog(&table, &player, (-1, 0), 1.0);
+
+    let gentle_balance = vec_ops::magnitude(gentle.balance);
+    let full_balance = vec_ops::magnitude(full.balance);
+    assert!(full_balance > 0.0);
+    assert!(gentle_balance < full_balance);
+}
+
+#[test]
+fn push_strength_is_capped_at_full() {
+    assert_eq!(PhysicsModel::push_strength((0.0, 0.0)), 0.0);
+    assert_eq!(PhysicsModel::push_strength((0.5, 0.0)), 0.5);
+    assert_eq!(PhysicsModel::push_strength((1.0, -1.0)), 1.0);
+}
@@ -0,0 +1,167 @@
+use rltk::VirtualKeyCode;
+
+use controller::bindings::{self, Action, Bindings};
+use model::trick::Trick;
+
+fn raws_bindings() -> Bindings {
+    Bindings::from_config(include_str!("../../game/src/raws/raws/bindings.txt"))
+}
+
+#[test]
+fn raws_file_matches_the_defaults() {
+    assert_eq!(raws_bindings(), Bindings::default());
+    assert!(Bindings::default().conflicts().is_empty());
+}
+
+#[test]
+fn every_action_has_a_key_by_default() {
+    let b = Bindings::default();
+    for action in Action::ALL.iter() {
+        assert!(!b.keys(*action).is_empty(), "{} has no key", action.name());
+    }
+}
+
+#[test]
+fn config_round_trips() {
+    let mut b = Bindings::default();
+    b.rebind(Action::Trick(Trick::Ollie), VirtualKeyCode::O)
+        .unwrap();
+    b.clear(Action::Minimap);
+
+    assert_eq!(Bindings::from_config(&b.to_config()), b);
+}
+
+#[test]
+fn action_names_round_trip() {
+    for action in Action::ALL.iter() {
+        assert_eq!(Action::from_name(action.name()), Some(*action));
+    }
+}
+
+#[test]
+fn looks_up_actions_by_key() {
+    let b = Bindings::default();
+    assert_eq!(b.action(VirtualKeyCode::K), Some(Action::Move(0, -1)));
+    assert_eq!(b.action(VirtualKeyCode::Period), Some(Action::Move(0, 0)));
+    assert_eq!(b.action(VirtualKeyCode::Escape), Some(Action::Menu));
+    assert_eq!(b.action(VirtualKeyCode::I), None);
+}
+
+#[test]
+fn binding_a_used_key_names_its_action() {
+    let mut b = Bindings::default();
+    let before = b.clone();
+
+    assert_eq!(
+        b.bind(Action::Chat, VirtualKeyCode::W),
+        Err(Action::Move(0, -1))
+    );
+    assert_eq!(
+        b.rebind(Action::Chat, VirtualKeyCode::Space),
+        Err(Action::Trick(Trick::Ollie))
+    );
+    assert_eq!(b, before);
+}
+
+#[test]
+fn bind_adds_and_rebind_replaces() {
+    let mut b = Bindings::default();
+
+    b.bind(Action::Chat, VirtualKeyCode::I).unwrap();
+    assert_eq!(b.keys(Action::Chat), &[VirtualKeyCode::G, VirtualKeyCode::I]);
+
+    b.rebind(Action::Chat, VirtualKeyCode::V).unwrap();
+    assert_eq!(b.keys(Action::Chat), &[VirtualKeyCode::V]);
+    assert_eq!(b.action(VirtualKeyCode::G), None);
+
+    // the action's own key is not a conflict
+    assert_eq!(b.rebind(Action::Chat, VirtualKeyCode::V), Ok(()));
+}
+
+#[test]
+fn finds_conflicts_in_a_config() {
+    let b = Bindings::from_config("up W\nchat W G\n");
+
+    assert_eq!(
+        b.conflicts(),
+        vec![(VirtualKeyCode::W, Action::Move(0, -1), Action::Chat)]
+    );
+    // the action listed first wins
+    assert_eq!(b.action(VirtualKeyCode::W), Some(Action::Move(0, -1)));
+}
+
+#[test]
+fn skips_unknown_actions_and_keys() {
+    let b = Bindings::from_config("jump Space\nchat G NotAKey\n");
+
+    assert_eq!(b.action(VirtualKeyCode::Space), None);
+    assert_eq!(b.keys(Action::Chat), &[VirtualKeyCode::G]);
+}
+
+#[test]
+fn describes_keys_for_the_player() {
+    let mut b = Bindings::default();
+    assert_eq!(b.describe(Action::Move(0, -1)), "W or K");
+    assert_eq!(b.describe(Action::Pan(-1, 0)), "←");
+    assert_eq!(b.describe(Action::NewGame), "5");
+
+    b.clear(Action::NewGame);
+    assert_eq!(b.describe(Action::NewGame), "(unbound)");
+}
+
+#[test]
+fn key_names_round_trip() {
+    for key in [VirtualKeyCode::A, VirtualKeyCode::Key7, VirtualKeyCode::Semicolon] {
+        assert!(bindings::is_bindable(key));
+        assert_eq!(bindings::key_from_name(&bindings::key_name(key)), Some(key));
+    }
+    assert!(!bindings::is_bindable(VirtualKeyCode::F1));
+}
+
+#[test]
+fn the_first_courier_starts_with_the_raws_keys() {
+    assert_eq!(bindings::courier_bindings(0), bindings::load_bindings());
+}
+
+#[test]
+fn couriers_sharing_the_keyboard_skate_with_their_own_keys() {
+    let couriers: Vec<Bindings> = (0..3).map(bindings::courier_bindings).collect();
+    assert!(bindings::shared_keys(&couriers).is_empty());
+    for courier in couriers.iter() {
+        assert!(courier.conflicts().is_empty());
+        assert!(courier.can_skate());
+    }
+
+    assert_eq!(couriers[1].action(VirtualKeyCode::Up), Some(Action::Move(0, -1)));
+    assert_eq!(couriers[2].action(VirtualKeyCode::Numpad5), Some(Action::Move(0, 0)));
+    // the rest of the keys are the first courier's
+    assert_eq!(couriers[2].keys(Action::Menu), &[VirtualKeyCode::Escape]);
+    assert_eq!(couriers[1].keys(Action::Pan(0, -1)), &[]);
+}
+
+#[test]
+fn a_fourth_courier_picks_their_own_keys() {
+    let fourth = bindings::courier_bindings(3);
+    assert!(!fourth.can_skate());
+    assert!(fourth.keys(Action::Trick(Trick::Ollie)).is_empty());
+    assert_eq!(fourth.keys(Action::Menu), &[VirtualKeyCode::Escape]);
+}
+
+#[test]
+fn keys_two_couriers_skate_with_are_found() {
+    let first = Bindings::default();
+    let mut second = bindings::courier_bindings(1);
+    second.rebind(Action::Chat, VirtualKeyCode::G).unwrap();
+    // looking around with it is no clash, only the courier whose turn it is does
+    second.rebind(Action::Minimap, VirtualKeyCode::Space).unwrap();
+
+    assert_eq!(
+        bindings::shared_keys(&[first.clone(), second]),
+        vec![(VirtualKeyCode::G, 0, 1)]
+    );
+
+    // the same keys for two couriers share every one they skate with
+    let shared = bindings::shared_keys(&[first.clone(), first]);
+    assert!(shared.contains(&(VirtualKeyCode::W, 0, 1)));
+    assert!(!shared.iter().any(|(key, ..)| *key == VirtualKeyCode::Escape));
+}
@@ -0,0 +1,135 @@
+// Who takes up which tile, with couriers sharing the map with the opponents,
+// and what happens to two skaters when one runs into the other
+
+mod common;
+
+use std::collections::HashSet;
+
+use controller::ai_controller::AIController;
+use controller::collision;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+
+use common::{EPSILON, controller, flat_table, rolling};
+
+#[test]
+fn skaters_trade_speeds_and_the_struck_one_is_shoved_on() {
+    let pc = controller();
+    let table = flat_table(12);
+    let mover = rolling(3, 4, (2.0, 0.0));
+    let struck = rolling(4, 4, (0.5, 0.0));
+
+    let (mover_after, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    assert_eq!(mover_after.speed, struck.speed);
+    assert_eq!(struck_after.speed, mover.speed);
+    assert_eq!(mover_after.position, mover.position);
+    assert!(struck_after.x() > struck.x());
+    assert_eq!(mover_after.recent_event, PlayerEvent::Bumped);
+    assert_eq!(struck_after.recent_event, PlayerEvent::Bumped);
+}
+
+#[test]
+fn the_slower_skater_takes_the_full_knock() {
+    let pc = controller();
+    let table = flat_table(12);
+    let mover = rolling(3, 4, (2.0, 0.0));
+    let struck = rolling(4, 4, (0.5, 0.0));
+    let knock = 1.5 * pc.physics.knock_factor;
+
+    let (mover_after, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    // thrown back and forward along the impact
+    assert!((mover_after.balance.0 + knock * 0.5).abs() < EPSILON);
+    assert!((struck_after.balance.0 - knock).abs() < EPSILON);
+    assert_eq!(mover_after.balance.1, 0.0);
+    assert_eq!(struck_after.balance.1, 0.0);
+}
+
+#[test]
+fn the_slower_skater_is_knocked_over_when_already_leaning() {
+    let pc = controller();
+    let table = flat_table(12);
+    let mover = rolling(3, 4, (2.0, 0.0));
+    let mut struck = rolling(4, 4, (0.5, 0.0));
+    struck.balance = (pc.physics.fallover_threshold - 0.5, 0.0);
+
+    let (mover_after, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    assert_eq!(mover_after.recent_event, PlayerEvent::Bumped);
+    assert_eq!(struck_after.recent_event, PlayerEvent::KnockedOver);
+    assert_eq!(struck_after.n_falls, struck.n_falls + 1);
+    assert_eq!(struck_after.speed, (0.0, 0.0));
+    assert_eq!(struck_after.balance, (0.0, 0.0));
+}
+
+#[test]
+fn a_slow_mover_is_the_one_that_goes_over() {
+    let pc = controller();
+    let table = flat_table(12);
+    let mut mover = rolling(3, 4, (0.0, 0.0));
+    mover.balance = (-(pc.physics.fallover_threshold - 0.5), 0.0);
+    let struck = rolling(4, 4, (-2.0, 0.0));
+
+    let (mover_after, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    assert_eq!(mover_after.recent_event, PlayerEvent::KnockedOver);
+    assert_eq!(struck_after.recent_event, PlayerEvent::Bumped);
+}
+
+#[test]
+fn equal_speeds_bump_without_a_knock() {
+    let pc = controller();
+    let table = flat_table(12);
+    let mover = rolling(3, 4, (1.0, 0.0));
+    let struck = rolling(4, 4, (1.0, 0.0));
+
+    let (mover_after, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    assert_eq!(mover_after.balance, (0.0, 0.0));
+    assert_eq!(struck_after.balance, (0.0, 0.0));
+    assert_eq!(mover_after.recent_event, PlayerEvent::Bumped);
+    assert_eq!(struck_after.recent_event, PlayerEvent::Bumped);
+}
+
+#[test]
+fn knocked_off_a_rail_with_nowhere_to_land_respawns() {
+    let pc = controller();
+    let mut table = ObstacleTable::new(9, 9);
+    for y in 0..9 {
+        for x in 0..9 {
+            table.set_obstacle((x, y), Obstacle::Wall);
+        }
+    }
+    table.set_obstacle((4, 4), Obstacle::Rail(1, 0));
+    let mover = rolling(3, 4, (2.0, 0.0));
+    let mut struck = rolling(4, 4, (0.5, 0.0));
+    struck.balance = (pc.physics.fallover_threshold, 0.0);
+
+    let (_, struck_after) = collision::resolve(&table, &mover, &struck, &pc);
+
+    assert_eq!(struck_after.recent_event, PlayerEvent::Respawn);
+}
+
+#[test]
+fn every_courier_and_live_opponent_blocks_their_tile() {
+    let mut table = ObstacleTable::new(10, 10);
+    let couriers = [Player::new(1, 1), Player::new(8, 8), Player::new(1, 8)];
+    let opponents = vec![AIController::new(5, 5), AIController::new(6, 6)];
+    let dead: HashSet<u32> = [1].into_iter().collect();
+
+    collision::update_blocked(&mut table, &couriers, &opponents, &dead);
+    assert_eq!(table.blocked.len(), 4);
+    for courier in couriers.iter() {
+        assert!(table.blocked.contains_key(&courier.xy()));
+    }
+    assert!(table.blocked.contains_key(&(5, 5)));
+    assert!(!table.blocked.contains_key(&(6, 6)));
+
+    // moving on frees the old tiles
+    collision::update_blocked(&mut table, &couriers[..1], &Vec::new(), &HashSet::new());
+    assert_eq!(table.blocked.len(), 1);
+    assert!(table.blocked.contains_key(&(1, 1)));
+}
@@ -0,0 +1,40 @@
+// Helpers shared by the tests, not every test file uses all of them
+#![allow(dead_code)]
+
+use controller::player_controller::PlayerController;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+
+pub const EPSILON: f32 = 1e-4;
+
+// a controller with the values from model.txt
+pub fn controller() -> PlayerController {
+    rltk::embedding::EMBED.lock().add_resource(
+        "raws/model.txt".to_string(),
+        include_bytes!("../../../game/src/raws/raws/model.txt"),
+    );
+    PlayerController::new()
+}
+
+// the same, with the skater profiles from skaters.txt too
+pub fn controller_with_skaters() -> PlayerController {
+    rltk::embedding::EMBED.lock().add_resource(
+        "raws/skaters.txt".to_string(),
+        include_bytes!("../../../game/src/raws/raws/skaters.txt"),
+    );
+    controller()
+}
+
+// concrete everywhere
+pub fn flat_table(size: u32) -> ObstacleTable {
+    let mut table = ObstacleTable::new(size, size);
+    table.update_platforms();
+    table.compute_unions();
+    table
+}
+
+pub fn rolling(x: i32, y: i32, speed: (f32, f32)) -> Player {
+    let mut player = Player::new(x, y);
+    player.speed = speed;
+    player
+}
@@ -0,0 +1,127 @@
+// The integrators in physics.rs: each one is picked by name from
+// raws/game.txt and moves every skater the same way.
+
+mod common;
+
+use controller::physics::{self, Arcade, Continuous, Discrete, Integrator, PhysicsModel};
+use controller::player_controller::DIRECTIONS;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use util::vec_ops;
+
+use common::{EPSILON, controller, flat_table, rolling};
+
+#[test]
+fn integrators_are_found_by_name() {
+    for name in physics::INTEGRATORS {
+        assert_eq!(physics::integrator(name).unwrap().name(), name);
+    }
+    assert!(physics::integrator("verlet").is_none());
+}
+
+#[test]
+fn the_controller_starts_out_discrete() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(4, 4, (1.5, 0.5));
+
+    assert_eq!(pc.integrator().name(), "discrete");
+    for direction in DIRECTIONS {
+        let inst_v = pc.get_inst_velocity(direction);
+        let a = pc.move_player(&table, &player, direction);
+        let b = Discrete.step(&pc.physics, &table, &player, inst_v);
+        assert_eq!(a.position, b.position);
+        assert_eq!(a.speed, b.speed);
+        assert_eq!(a.balance, b.balance);
+        assert!(a.recent_event == b.recent_event);
+    }
+}
+
+#[test]
+fn one_continuous_substep_is_the_discrete_model() {
+    let pc = controller();
+    let table = flat_table(9);
+    let model = PhysicsModel {
+        substeps: 1,
+        ..pc.physics
+    };
+
+    for direction in DIRECTIONS {
+        let player = rolling(4, 4, (-1.0, 1.5));
+        let inst_v = pc.get_inst_velocity(direction);
+        let a = Continuous.step(&model, &table, &player, inst_v);
+        let b = Discrete.step(&model, &table, &player, inst_v);
+        assert_eq!(a.position, b.position);
+        assert!(vec_ops::magnitude((a.speed.0 - b.speed.0, a.speed.1 - b.speed.1)) < EPSILON);
+        assert!(
+            vec_ops::magnitude((a.balance.0 - b.balance.0, a.balance.1 - b.balance.1)) < EPSILON
+        );
+    }
+}
+
+#[test]
+fn continuous_pushing_stays_under_max_speed() {
+    let mut pc = controller();
+    pc.set_integrator(Box::new(Continuous));
+    let table = flat_table(101);
+    let mut player = Player::new(1, 50);
+
+    for _ in 0..40 {
+        player = pc.move_player(&table, &player, (1, 0));
+        assert!(vec_ops::magnitude(player.speed) <= pc.physics.max_speed + EPSILON);
+    }
+    assert!(vec_ops::magnitude(player.speed) > pc.physics.max_speed * 0.5);
+}
+
+#[test]
+fn continuous_turns_cost_less_balance() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(4, 4, (2.0, 0.0));
+
+    let discrete = Discrete.step(&pc.physics, &table, &player, (0.0, 1.0));
+    let continuous = Continuous.step(&pc.physics, &table, &player, (0.0, 1.0));
+
+    assert!(vec_ops::magnitude(continuous.balance) <= vec_ops::magnitude(discrete.balance));
+}
+
+#[test]
+fn arcade_moves_one_tile_per_push() {
+    let mut pc = controller();
+    pc.set_integrator(Box::new(Arcade));
+    let table = flat_table(9);
+    let player = rolling(4, 4, (2.0, 0.0));
+
+    for direction in DIRECTIONS {
+        let moved = pc.move_player(&table, &player, direction);
+        assert_eq!(moved.position, (4 + direction.0, 4 + direction.1));
+        assert_eq!(moved.balance, (0.0, 0.0));
+        if direction == (0, 0) {
+            assert_eq!(moved.recent_event, PlayerEvent::Wait);
+            assert_eq!(moved.speed, (0.0, 0.0));
+        } else {
+            assert_eq!(moved.recent_event, PlayerEvent::Move);
+        }
+    }
+}
+
+#[test]
+fn arcade_still_falls_into_walls_and_off_the_map() {
+    let table = {
+        let mut table = ObstacleTable::new(5, 5);
+        table.set_obstacle((3, 2), Obstacle::Wall);
+        table.update_platforms();
+        table
+    };
+    let pc = controller();
+
+    let into_wall = Arcade.step(&pc.physics, &table, &Player::new(2, 2), (1.0, 0.0));
+    assert_eq!(into_wall.recent_event, PlayerEvent::FallOver);
+    assert_eq!(into_wall.position, (2, 2));
+
+    let off_map = Arcade.step(&pc.physics, &table, &Player::new(0, 2), (-1.0, 0.0));
+    assert_eq!(off_map.recent_event, PlayerEvent::FallOver);
+    assert_eq!(off_map.position, (0, 2));
+}
@@ -0,0 +1,393 @@
+// Invariants of the skating model (the integrators in physics.rs and the
+// steps they are made of), checked on small hand built tables and on random
+// situations generated from fixed seeds.
+
+mod common;
+
+use controller::physics::{Discrete, Integrator, PhysicsModel};
+use controller::player_controller::{PlayerController, DIRECTIONS};
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use model::surface::Surface;
+use rltk::RandomNumberGenerator;
+use util::vec_ops;
+
+use common::{EPSILON, controller, flat_table, rolling};
+
+#[test]
+fn pushing_from_a_standstill_gets_the_player_moving() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = Player::new(4, 4);
+
+    let moved = pc.move_player(&table, &player, (1, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::Move);
+    assert_eq!(moved.position, (5, 4));
+    assert!((moved.speed.0 - pc.physics.inst_length).abs() < EPSILON);
+    assert_eq!(moved.speed.1, 0.0);
+}
+
+#[test]
+fn waiting_decays_speed_to_zero() {
+    let pc = controller();
+    let table = flat_table(101);
+    let mut player = rolling(50, 50, (pc.physics.max_speed * 0.7, -pc.physics.max_speed * 0.7));
+
+    let mut last = vec_ops::magnitude(player.speed);
+    for _ in 0..20 {
+        player = pc.move_player(&table, &player, (0, 0));
+        let speed = vec_ops::magnitude(player.speed);
+        assert!(speed <= last + EPSILON, "{} after {}", speed, last);
+        last = speed;
+    }
+
+    assert_eq!(player.speed, (0.0, 0.0));
+    assert_eq!(player.recent_event, PlayerEvent::Wait);
+}
+
+#[test]
+fn speed_never_exceeds_max_speed_when_pushing_on() {
+    let pc = controller();
+    let table = flat_table(101);
+
+    for direction in DIRECTIONS {
+        let mut player = Player::new(50, 50);
+        for _ in 0..10 {
+            player = pc.move_player(&table, &player, direction);
+            assert!(vec_ops::magnitude(player.speed) <= pc.physics.max_speed + EPSILON);
+        }
+    }
+}
+
+// a diagonal over max_speed used to be scaled down one component at a
+// time, leaving it faster than max_speed whenever it couldn't move
+#[test]
+fn speed_is_capped_even_when_the_move_is_blocked() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    let mut other = Player::new(5, 3);
+    other.speed = (0.0, 0.0);
+    table.blocked.insert((5, 3), other);
+
+    let player = rolling(4, 4, (pc.physics.max_speed, -pc.physics.max_speed));
+    let moved = pc.move_player(&table, &player, (1, -1));
+
+    assert_eq!(moved.recent_event, PlayerEvent::Collide(5, 3));
+    assert!(vec_ops::magnitude(moved.speed) <= pc.physics.max_speed + EPSILON);
+}
+
+#[test]
+fn reversing_at_speed_raises_balance() {
+    let pc = controller();
+    let table = flat_table(9);
+
+    for (dx, dy) in DIRECTIONS.iter().filter(|d| **d != (0, 0)) {
+        let player = rolling(4, 4, (*dx as f32 * 1.5, *dy as f32 * 1.5));
+        let moved = pc.move_player(&table, &player, (-dx, -dy));
+
+        assert!(
+            moved.recent_event == PlayerEvent::FallOver
+                || vec_ops::magnitude(moved.balance) > 0.0,
+            "reversing {:?} didn't change balance",
+            (dx, dy)
+        );
+    }
+}
+
+#[test]
+fn reversing_costs_more_balance_than_going_straight() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(4, 4, (1.5, 0.0));
+
+    let straight = pc.move_player(&table, &player, (1, 0));
+    let turned = pc.move_player(&table, &player, (0, 1));
+    let reversed = pc.move_player(&table, &player, (-1, 0));
+
+    let lean = |p: &Player| vec_ops::magnitude(p.balance);
+    assert!(lean(&straight) < lean(&turned));
+    assert!(lean(&turned) < lean(&reversed));
+}
+
+#[test]
+fn losing_balance_falls_over() {
+    let pc = controller();
+    let table = flat_table(9);
+    let mut player = rolling(4, 4, (2.0, 0.0));
+    player.balance = (pc.physics.fallover_threshold, 0.0);
+
+    let moved = pc.move_player(&table, &player, (-1, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::FallOver);
+    assert_eq!(moved.n_falls, 1);
+}
+
+#[test]
+fn walls_make_the_player_fall_over() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((5, 4), Obstacle::Wall);
+    let player = rolling(4, 4, (1.0, 0.0));
+
+    let moved = pc.move_player(&table, &player, (1, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::FallOver);
+    assert_eq!(moved.position, (4, 4));
+    assert_eq!(moved.speed, (0.0, 0.0));
+    assert_eq!(moved.balance, (0.0, 0.0));
+    assert_eq!(moved.n_falls, 1);
+}
+
+#[test]
+fn the_edge_of_the_map_is_a_wall() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(8, 4, (1.0, 0.0));
+
+    let moved = pc.move_player(&table, &player, (1, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::FallOver);
+    assert_eq!(moved.position, (8, 4));
+}
+
+#[test]
+fn pits_respawn_the_player() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((5, 4), Obstacle::Pit);
+    let player = rolling(4, 4, (1.0, 0.0));
+
+    let moved = pc.move_player(&table, &player, (1, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::Respawn);
+}
+
+#[test]
+fn rails_carry_the_player_along() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((4, 4), Obstacle::Rail(1, 0));
+    table.set_obstacle((5, 4), Obstacle::Rail(1, 0));
+    let player = rolling(4, 4, (1.0, 0.0));
+
+    let moved = pc.move_player(&table, &player, (0, 0));
+
+    assert_eq!(moved.position, (5, 4));
+    assert!(moved.speed.0 > player.speed.0 * pc.physics.speed_damp);
+    assert!(vec_ops::magnitude(moved.speed) <= pc.physics.max_speed + EPSILON);
+}
+
+// stalling on a rail used to count the fall but report a Wait,
+// and then put the player straight back on the rail
+#[test]
+fn stalling_on_a_rail_falls_off_it() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((4, 4), Obstacle::Rail(-1, 0));
+    // the rail's boost cancels what is left of the speed
+    let player = rolling(4, 4, (pc.physics.rail_boost / pc.physics.speed_damp, 0.0));
+
+    let moved = pc.move_player(&table, &player, (0, 0));
+
+    assert_eq!(moved.recent_event, PlayerEvent::FallOver);
+    assert_eq!(moved.n_falls, 1);
+    assert!(table.get_obstacle(moved.x(), moved.y()) == Obstacle::Platform);
+}
+
+#[test]
+fn ramps_slow_going_up_and_speed_up_going_down() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_surface((5, 4), Surface::Ramp(1, 0));
+    table.set_surface((3, 4), Surface::Ramp(1, 0));
+    let flat = pc.move_player(&flat_table(9), &rolling(4, 4, (1.0, 0.0)), (1, 0));
+
+    let up = pc.move_player(&table, &rolling(4, 4, (1.0, 0.0)), (1, 0));
+    let down = pc.move_player(&table, &rolling(4, 4, (-1.0, 0.0)), (-1, 0));
+
+    assert!(up.speed.0 < flat.speed.0);
+    assert!(down.speed.0.abs() > flat.speed.0);
+}
+
+#[test]
+fn discrete_movement_without_speed_damp() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(4, 4, (2.0, 0.0));
+
+    // with speed_damp 0 only the push decides where the player goes
+    let model = PhysicsModel {
+        speed_damp: 0.0,
+        inst_length: 1.0,
+        ..pc.physics
+    };
+    let moved = Discrete.step(&model, &table, &player, (0.0, 1.0));
+
+    assert_eq!(moved.position, (4, 5));
+    assert_eq!(moved.speed, (0.0, 1.0));
+}
+
+fn random_obstacle(rng: &mut RandomNumberGenerator) -> Obstacle {
+    match rng.range(0, 10) {
+        0 => Obstacle::Wall,
+        1 => Obstacle::Pit,
+        2 => {
+            let (dx, dy) = DIRECTIONS[rng.range(0, 8)];
+            Obstacle::Rail(dx, dy)
+        }
+        _ => Obstacle::Platform,
+    }
+}
+
+fn random_surface(rng: &mut RandomNumberGenerator) -> Surface {
+    match rng.range(0, 6) {
+        0 => Surface::Gravel,
+        1 => Surface::Wet,
+        2 => {
+            let (dx, dy) = [(1, 0), (-1, 0), (0, 1), (0, -1)][rng.range(0, 4)];
+            Surface::Ramp(dx, dy)
+        }
+        _ => Surface::Concrete,
+    }
+}
+
+fn random_vector(rng: &mut RandomNumberGenerator, max_length: f32) -> (f32, f32) {
+    let angle = rng.range(0.0, std::f32::consts::TAU);
+    let length = rng.range(0.0, max_length);
+    (angle.cos() * length, angle.sin() * length)
+}
+
+// a 5x5 table of random tiles and skaters with the player in
+// the middle, rolling and leaning in random directions
+fn random_situation(
+    rng: &mut RandomNumberGenerator,
+    pc: &PlayerController,
+) -> (ObstacleTable, Player) {
+    let mut table = ObstacleTable::new(5, 5);
+    for x in 0..5 {
+        for y in 0..5 {
+            table.set_obstacle((x, y), random_obstacle(rng));
+            table.set_surface((x, y), random_surface(rng));
+        }
+    }
+    // skaters stand on platforms or ride rails
+    if let Obstacle::Wall | Obstacle::Pit = table.get_obstacle(2, 2) {
+        table.set_obstacle((2, 2), Obstacle::Platform);
+    }
+    table.update_platforms();
+
+    // other skaters in the way
+    for (dx, dy) in DIRECTIONS.iter().filter(|d| **d != (0, 0)) {
+        if rng.range(0, 4) == 0 {
+            let at = (2 + dx, 2 + dy);
+            table.blocked.insert(at, Player::new(at.0, at.1));
+        }
+    }
+
+    let mut player = Player::new(2, 2);
+    player.speed = random_vector(rng, pc.physics.max_speed);
+    player.balance = random_vector(rng, pc.physics.fallover_threshold * 0.99);
+    player.time = rng.range(0.0, 100.0);
+
+    (table, player)
+}
+
+#[test]
+fn random_moves_keep_the_invariants() {
+    let pc = controller();
+
+    for seed in 0..2000 {
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let (table, player) = random_situation(&mut rng, &pc);
+        let direction = DIRECTIONS[rng.range(0, DIRECTIONS.len())];
+        let strength = rng.range(0.0, 1.0);
+
+        let moved = pc.move_player_analog(&table, &player, direction, strength);
+        let context = format!(
+            "seed {}: {:?} {:?} pushing {:?} at {} -> {:?} at {:?}, {:?}",
+            seed,
+            player.speed,
+            player.balance,
+            direction,
+            strength,
+            moved.recent_event,
+            moved.position,
+            moved.speed
+        );
+
+        assert!(
+            vec_ops::magnitude(moved.speed) <= pc.physics.max_speed + EPSILON,
+            "too fast, {}",
+            context
+        );
+        assert!(moved.time > player.time, "no time passed, {}", context);
+        assert!(
+            (moved.x() - player.x()).abs() <= 1 && (moved.y() - player.y()).abs() <= 1,
+            "moved too far, {}",
+            context
+        );
+        assert!(
+            table.get_obstacle(moved.x(), moved.y()) != Obstacle::Wall,
+            "inside a wall, {}",
+            context
+        );
+
+        match moved.recent_event {
+            PlayerEvent::FallOver => {
+                assert_eq!(moved.speed, (0.0, 0.0), "{}", context);
+                assert_eq!(moved.balance, (0.0, 0.0), "{}", context);
+                assert_eq!(moved.n_falls, player.n_falls + 1, "{}", context);
+            }
+            PlayerEvent::Respawn => {}
+            _ => {
+                assert!(
+                    vec_ops::magnitude(moved.balance) < pc.physics.fallover_threshold,
+                    "should have fallen, {}",
+                    context
+                );
+                assert_eq!(moved.n_falls, player.n_falls, "{}", context);
+            }
+        }
+    }
+}
+
+#[test]
+fn random_moves_are_deterministic() {
+    let pc = controller();
+
+    for seed in 0..200 {
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let (table, player) = random_situation(&mut rng, &pc);
+        let direction = DIRECTIONS[rng.range(0, DIRECTIONS.len())];
+
+        let first = pc.move_player(&table, &player, direction);
+        let second = pc.move_player(&table, &player, direction);
+        assert_eq!(first.position, second.position);
+        assert_eq!(first.speed, second.speed);
+        assert_eq!(first.balance, second.balance);
+        assert_eq!(first.recent_event, second.recent_event);
+    }
+}
+
+#[test]
+fn random_waits_never_speed_up_on_flat_concrete() {
+    let pc = controller();
+    let table = flat_table(21);
+
+    for seed in 0..500 {
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let player = rolling(10, 10, random_vector(&mut rng, pc.physics.max_speed));
+
+        let moved = pc.move_player(&table, &player, (0, 0));
+        assert!(
+            vec_ops::magnitude(moved.speed) <= vec_ops::magnitude(player.speed) + EPSILON,
+            "seed {}: {:?} -> {:?}",
+            seed,
+            player.speed,
+            moved.speed
+        );
+    }
+}
@@ -0,0 +1,155 @@
+// Kinds of skater (raws/skaters.txt): each one changes some of the values
+// in model.txt, for whoever skates with it, human or AI.
+
+mod common;
+
+use controller::ai_controller::AIController;
+use controller::collision;
+use controller::physics::Tunable;
+use controller::player_controller::PlayerController;
+use controller::profile::{self, Profile};
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use util::vec_ops;
+
+use common::{EPSILON, controller_with_skaters, flat_table};
+
+fn profile_idx(pc: &PlayerController, name: &str) -> usize {
+    pc.profiles.iter().position(|p| p.name == name).unwrap()
+}
+
+#[test]
+fn profiles_are_read_in_order_with_their_values() {
+    let profiles = profile::profiles_from_config(
+        "# comment
+skater Big Wheels
+about Rolls over anything
+inst_length 0.5
+not_a_value 3
+max_speed fast
+
+skater Plain
+",
+    );
+
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0].name, "Big Wheels");
+    assert_eq!(profiles[0].about, "Rolls over anything");
+    assert_eq!(profiles[0].overrides(), &[(Tunable::InstLength, 0.5)]);
+    assert!(profiles[1].overrides().is_empty());
+
+    assert_eq!(profile::profiles_from_config(""), vec![Profile::default()]);
+}
+
+#[test]
+fn a_profile_only_changes_its_own_values() {
+    let base = controller_with_skaters().physics;
+    let mut heavy = Profile::new("Heavy", "");
+    heavy.set(Tunable::FalloverThreshold, 2.5);
+    heavy.set(Tunable::FalloverThreshold, 3.0);
+    assert_eq!(heavy.overrides().len(), 1);
+
+    let physics = heavy.apply(&base);
+    assert_eq!(physics.fallover_threshold, 3.0);
+    assert_eq!(physics.max_speed, base.max_speed);
+    assert_eq!(physics.turn_factor, base.turn_factor);
+    assert_eq!(Profile::default().apply(&base), base);
+}
+
+#[test]
+fn the_skaters_file_has_the_archetypes() {
+    let pc = controller_with_skaters();
+    let names: Vec<&str> = pc.profiles.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["Standard", "Heavy", "Nimble", "Grinder"]);
+
+    let standard = pc.physics_for(&Player::new(0, 0));
+    assert_eq!(standard, pc.physics);
+
+    let mut player = Player::new(0, 0);
+    player.profile = profile_idx(&pc, "Heavy");
+    let heavy = pc.physics_for(&player);
+    player.profile = profile_idx(&pc, "Nimble");
+    let nimble = pc.physics_for(&player);
+    player.profile = profile_idx(&pc, "Grinder");
+    let grinder = pc.physics_for(&player);
+
+    assert!(heavy.inst_length < standard.inst_length);
+    assert!(heavy.fallover_threshold > standard.fallover_threshold);
+    assert!(nimble.turn_factor < standard.turn_factor);
+    assert!(nimble.fallover_threshold < standard.fallover_threshold);
+    assert!(grinder.rail_boost > standard.rail_boost);
+
+    // an unknown profile moves like model.txt
+    player.profile = 99;
+    assert_eq!(pc.physics_for(&player), pc.physics);
+}
+
+#[test]
+fn heavy_skaters_get_going_slower_but_lean_further() {
+    let pc = controller_with_skaters();
+    let table = flat_table(21);
+    let standard = Player::new(2, 10);
+    let mut heavy = standard;
+    heavy.profile = profile_idx(&pc, "Heavy");
+
+    let moved_standard = pc.move_player(&table, &standard, (1, 0));
+    let moved_heavy = pc.move_player(&table, &heavy, (1, 0));
+    assert!(moved_heavy.speed.0 < moved_standard.speed.0);
+    assert_eq!(moved_heavy.profile, heavy.profile);
+
+    // leaning past what model.txt allows, but not past the heavy's limit
+    let lean = (pc.physics.fallover_threshold + pc.physics_for(&heavy).fallover_threshold) / 2.0;
+    let mut tipped = Player::new(10, 10);
+    tipped.balance = (lean / pc.physics.balance_damp, 0.0);
+    tipped.profile = heavy.profile;
+    assert_ne!(pc.move_player(&table, &tipped, (0, 0)).recent_event, PlayerEvent::FallOver);
+
+    tipped.profile = 0;
+    assert_eq!(
+        pc.move_player(&table, &tipped, (0, 0)).recent_event,
+        PlayerEvent::FallOver
+    );
+}
+
+#[test]
+fn ai_moves_are_simulated_with_their_own_profile() {
+    let pc = controller_with_skaters();
+    let table = flat_table(15);
+    let mut ai = AIController::new(7, 7);
+    ai.player.profile = profile_idx(&pc, "Heavy");
+    let inst_length = pc.physics_for(&ai.player).inst_length;
+
+    let mut moves = Vec::new();
+    for _ in 0..20 {
+        ai.choose_goal(&table, 8);
+        let player = ai.player;
+        moves = ai.get_moves(&player, &table, &pc);
+        if !moves.is_empty() {
+            break;
+        }
+    }
+
+    assert!(!moves.is_empty());
+    for (moved, _) in moves {
+        let speed = vec_ops::magnitude(moved.speed);
+        assert!(speed < EPSILON || (speed - inst_length).abs() < EPSILON);
+    }
+}
+
+#[test]
+fn knocks_are_measured_against_each_skaters_threshold() {
+    let pc = controller_with_skaters();
+    let table = flat_table(9);
+
+    let mut mover = Player::new(3, 4);
+    mover.speed = (2.0, 0.0);
+    let mut struck = Player::new(4, 4);
+    struck.balance = (0.9, 0.0);
+
+    let (_, knocked) = collision::resolve(&table, &mover, &struck, &pc);
+    assert_eq!(knocked.recent_event, PlayerEvent::KnockedOver);
+
+    struck.profile = profile_idx(&pc, "Heavy");
+    let (_, steady) = collision::resolve(&table, &mover, &struck, &pc);
+    assert_ne!(steady.recent_event, PlayerEvent::KnockedOver);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use controller::physics::Tunable;
+use controller::sandbox::{Sandbox, ARENA_SIZE};
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use model::surface::Surface;
+
+use common::controller;
+
+#[test]
+fn every_tunable_reads_back_what_was_set() {
+    let mut physics = controller().physics;
+    for tunable in Tunable::ALL {
+        let (min, max) = tunable.range();
+        let value = (min + max) / 2.0;
+        tunable.set(&mut physics, value);
+        assert!((tunable.get(&physics) - value).abs() <= 0.5, "{}", tunable.name());
+
+        tunable.set(&mut physics, max + 100.0);
+        assert_eq!(tunable.get(&physics), max, "{}", tunable.name());
+        tunable.set(&mut physics, min - 100.0);
+        assert_eq!(tunable.get(&physics), min, "{}", tunable.name());
+    }
+}
+
+#[test]
+fn changing_max_speed_works_out_speed_damp_again() {
+    let mut physics = controller().physics;
+    let before = physics.speed_damp;
+
+    let faster = physics.max_speed * 2.0;
+    Tunable::MaxSpeed.set(&mut physics, faster);
+    assert!(physics.speed_damp > before);
+
+    Tunable::TurnFactor.set(&mut physics, 0.1);
+    assert!(physics.speed_damp > before);
+}
+
+#[test]
+fn adjusting_changes_the_selected_value_by_its_step() {
+    let mut physics = controller().physics;
+    let mut sandbox = Sandbox::new(10);
+    sandbox.select_next();
+    assert_eq!(sandbox.selected(), Tunable::ALL[1]);
+
+    let before = sandbox.selected().get(&physics);
+    sandbox.adjust(&mut physics, 3);
+    let step = sandbox.selected().step();
+    assert!((sandbox.selected().get(&physics) - (before + 3.0 * step)).abs() < 1e-4);
+
+    sandbox.select_previous();
+    sandbox.select_previous();
+    assert_eq!(sandbox.selected(), Tunable::Substeps);
+}
+
+#[test]
+fn the_trace_keeps_the_last_moves_and_reset_clears_it() {
+    let pc = controller();
+    let mut sandbox = Sandbox::new(5);
+    assert_eq!(sandbox.start, (ARENA_SIZE.0 as i32 / 2, ARENA_SIZE.1 as i32 / 2));
+
+    for _ in 0..8 {
+        let moved = pc.move_player(&sandbox.table, &sandbox.player, (1, 0));
+        sandbox.record(moved);
+    }
+    assert_eq!(sandbox.trace().len(), 5);
+    assert_eq!(sandbox.trace().back().unwrap().xy(), sandbox.player.xy());
+    assert!(sandbox.player.x() > sandbox.start.0);
+
+    sandbox.reset();
+    assert!(sandbox.trace().is_empty());
+    assert_eq!(sandbox.player.xy(), sandbox.start);
+    assert_eq!(sandbox.player.speed, (0.0, 0.0));
+}
+
+#[test]
+fn dropping_into_a_pit_goes_back_to_the_start() {
+    let mut sandbox = Sandbox::new(5);
+    let mut fell = Player::new(3, 3);
+    fell.speed = (2.0, 0.0);
+    fell.recent_event = PlayerEvent::Respawn;
+
+    sandbox.record(fell);
+    assert_eq!(sandbox.player.xy(), sandbox.start);
+    assert_eq!(sandbox.player.speed, (0.0, 0.0));
+    assert_eq!(sandbox.trace().len(), 1);
+}
+
+#[test]
+fn a_copied_map_keeps_the_layout_but_not_the_skaters() {
+    let mut table = ObstacleTable::new(6, 4);
+    table.set_obstacle((2, 1), Obstacle::Wall);
+    table.set_surface((4, 2), Surface::Gravel);
+    table.update_platforms();
+    table.blocked.insert((1, 1), Player::new(1, 1));
+
+    let mut sandbox = Sandbox::new(5);
+    sandbox.copy_map(&table, (0, 0));
+    assert!(sandbox.run_map);
+    assert!(sandbox.table.get_obstacle(2, 1) == Obstacle::Wall);
+    assert_eq!(sandbox.table.get_surface(4, 2), Surface::Gravel);
+    assert!(sandbox.table.blocked.is_empty());
+    assert_eq!(sandbox.player.xy(), (0, 0));
+
+    sandbox.empty_map();
+    assert!(!sandbox.run_map);
+    assert_eq!(sandbox.table.width(), ARENA_SIZE.0);
+}
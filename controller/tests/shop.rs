@@ -0,0 +1,164 @@
+// Spending the money from deliveries between runs (raws/shop.txt):
+// upgrades stay on the board, consumables last one run.
+
+mod common;
+
+use controller::physics::Tunable;
+use controller::profile::Board;
+use controller::shop::{self, Item, ItemKind, Progress, Refusal};
+use model::player::Player;
+
+use common::{EPSILON, controller};
+
+fn shop_items() -> Vec<Item> {
+    shop::items_from_config(include_str!("../../game/src/raws/raws/shop.txt"))
+}
+
+fn item<'a>(items: &'a [Item], id: &str) -> &'a Item {
+    items.iter().find(|i| i.id == id).unwrap()
+}
+
+#[test]
+fn items_are_read_in_order_with_their_changes() {
+    let items = shop::items_from_config(
+        "# comment
+upgrade big_wheels
+name Big Wheels
+about Rolls over anything
+price 40
+levels 2
+inst_length 0.1
+not_a_value 3
+max_speed fast
+
+consumable tea
+price lots
+levels 4
+",
+    );
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].id, "big_wheels");
+    assert_eq!(items[0].name, "Big Wheels");
+    assert_eq!(items[0].about, "Rolls over anything");
+    assert_eq!(items[0].price, 40);
+    assert_eq!(items[0].kind, ItemKind::Upgrade(2));
+    assert_eq!(items[0].changes(), &[(Tunable::InstLength, 0.1)]);
+
+    // a consumable has no levels, and the name defaults to the id
+    assert_eq!(items[1].name, "tea");
+    assert_eq!(items[1].price, 0);
+    assert_eq!(items[1].kind, ItemKind::Consumable);
+}
+
+#[test]
+fn upgrades_cost_more_every_level_until_maxed_out() {
+    let items = shop_items();
+    let bearings = item(&items, "bearings");
+    let levels = match bearings.kind {
+        ItemKind::Upgrade(levels) => levels,
+        ItemKind::Consumable => panic!("bearings should be an upgrade"),
+    };
+
+    let mut progress = Progress::new();
+    progress.money = 10_000;
+    let mut spent = 0;
+    for level in 0..levels {
+        let price = progress.price(bearings);
+        assert_eq!(price, bearings.price * (level as i32 + 1));
+        assert_eq!(progress.buy(bearings), Ok(()));
+        spent += price;
+    }
+
+    assert_eq!(progress.level("bearings"), levels);
+    assert_eq!(progress.money, 10_000 - spent);
+    assert_eq!(progress.buy(bearings), Err(Refusal::MaxedOut));
+    assert_eq!(progress.money, 10_000 - spent);
+}
+
+#[test]
+fn nothing_is_sold_on_credit_or_twice_for_a_run() {
+    let items = shop_items();
+    let wax = item(&items, "wax");
+
+    let mut progress = Progress::new();
+    progress.money = wax.price - 1;
+    assert_eq!(progress.buy(wax), Err(Refusal::TooExpensive));
+    assert_eq!(progress.money, wax.price - 1);
+    assert!(!progress.is_stocked("wax"));
+
+    progress.money = wax.price * 2;
+    assert_eq!(progress.buy(wax), Ok(()));
+    assert_eq!(progress.buy(wax), Err(Refusal::AlreadyStocked));
+    assert_eq!(progress.money, wax.price);
+}
+
+#[test]
+fn a_finished_run_pays_out_and_uses_up_the_consumables() {
+    let items = shop_items();
+    let mut progress = Progress::new();
+    progress.money = 200;
+    progress.buy(item(&items, "wax")).unwrap();
+    progress.buy(item(&items, "grip_tape")).unwrap();
+    let left = progress.money;
+
+    let board = progress.board(&items);
+    assert!(board.changes().iter().any(|(t, _)| *t == Tunable::RailBoost));
+
+    progress.end_run(120);
+    assert_eq!(progress.money, left + 120);
+    assert!(!progress.is_stocked("wax"));
+    assert_eq!(progress.level("grip_tape"), 1);
+
+    let board = progress.board(&items);
+    assert!(!board.changes().iter().any(|(t, _)| *t == Tunable::RailBoost));
+    assert!(board.changes().iter().any(|(t, _)| *t == Tunable::TurnFactor));
+
+    progress.end_run(-50);
+    assert_eq!(progress.money, left + 120);
+}
+
+#[test]
+fn the_upgrades_change_how_the_board_rides() {
+    let items = shop_items();
+    let mut pc = controller();
+    let stock = pc.physics;
+
+    let mut progress = Progress::new();
+    progress.money = 10_000;
+    for id in ["bearings", "bearings", "grip_tape", "wide_deck"] {
+        progress.buy(item(&items, id)).unwrap();
+    }
+    pc.boards = vec![Board::default(), progress.board(&items)];
+
+    let mut player = Player::new(0, 0);
+    player.board = 1;
+    let upgraded = pc.physics_for(&player);
+    assert!(upgraded.max_speed > stock.max_speed);
+    assert!(upgraded.speed_damp > stock.speed_damp);
+    assert!(upgraded.turn_factor < stock.turn_factor);
+    assert!(upgraded.fallover_threshold > stock.fallover_threshold);
+
+    let bearings = item(&items, "bearings").changes()[0].1;
+    assert!((upgraded.max_speed - (stock.max_speed + 2.0 * bearings)).abs() < EPSILON);
+
+    // everyone else rides a stock board
+    assert_eq!(pc.physics_for(&Player::new(0, 0)), stock);
+}
+
+#[test]
+fn progress_is_saved_and_read_back() {
+    let items = shop_items();
+    let mut progress = Progress::new();
+    progress.money = 500;
+    progress.skater = "Big Wheels".to_string();
+    progress.buy(item(&items, "bearings")).unwrap();
+    progress.buy(item(&items, "energy_drink")).unwrap();
+
+    let saved = progress.to_config();
+    assert_eq!(Progress::from_config(&saved), progress);
+
+    let damaged = format!("{}money lots\nupgrade wide_deck\n# upgrade bearings 9\n", saved);
+    assert_eq!(Progress::from_config(&damaged), progress);
+    assert_eq!(Progress::from_config(""), Progress::default());
+}
@@ -0,0 +1,101 @@
+// What gravel, wet patches and ramps do to a skater (see physics.rs)
+
+mod common;
+
+use model::surface::Surface;
+use util::vec_ops;
+
+use common::{EPSILON, controller, flat_table, rolling};
+
+#[test]
+fn gravel_pushes_balance_sideways() {
+    let pc = controller();
+    let concrete = flat_table(21);
+    let mut gravel = flat_table(21);
+    gravel.set_surface((10, 10), Surface::Gravel);
+    gravel.set_surface((11, 10), Surface::Gravel);
+    let player = rolling(10, 10, (1.5, 0.0));
+
+    let smooth = pc.move_player(&concrete, &player, (1, 0));
+    let drifted = pc.move_player(&gravel, &player, (1, 0));
+
+    // across the direction of travel, not along it
+    let drift = drifted.balance.1 - smooth.balance.1;
+    assert!(drift.abs() > EPSILON);
+    assert!((drifted.balance.0 - smooth.balance.0).abs() < EPSILON);
+
+    // and the next tile pushes the other way
+    let next = pc.move_player(&gravel, &rolling(11, 10, (1.5, 0.0)), (1, 0));
+    let next_smooth = pc.move_player(&concrete, &rolling(11, 10, (1.5, 0.0)), (1, 0));
+    assert!((next.balance.1 - next_smooth.balance.1).signum() != drift.signum());
+}
+
+#[test]
+fn faster_skaters_drift_more_on_gravel() {
+    let pc = controller();
+    let mut gravel = flat_table(21);
+    for x in 0..21 {
+        gravel.set_surface((x, 10), Surface::Gravel);
+    }
+
+    let concrete = flat_table(21);
+    let drift = |speed: f32| {
+        let player = rolling(10, 10, (speed, 0.0));
+        let on_gravel = pc.move_player(&gravel, &player, (1, 0));
+        let on_concrete = pc.move_player(&concrete, &player, (1, 0));
+        (on_gravel.balance.1 - on_concrete.balance.1).abs()
+    };
+
+    assert!(drift(1.5) > drift(0.5));
+}
+
+#[test]
+fn wet_patches_give_pushes_less_grip() {
+    let pc = controller();
+    let concrete = flat_table(9);
+    let mut wet = flat_table(9);
+    wet.set_surface((4, 4), Surface::Wet);
+    let player = rolling(4, 4, (1.0, 0.0));
+
+    let dry_push = pc.move_player(&concrete, &player, (1, 0));
+    let wet_push = pc.move_player(&wet, &player, (1, 0));
+
+    let lost = pc.physics.inst_length * (1.0 - pc.physics.surface_factors.wet_grip);
+    assert!((dry_push.speed.0 - wet_push.speed.0 - lost).abs() < EPSILON);
+}
+
+#[test]
+fn turning_on_a_wet_patch_costs_more_balance() {
+    let pc = controller();
+    let concrete = flat_table(9);
+    let mut wet = flat_table(9);
+    wet.set_surface((4, 4), Surface::Wet);
+    let player = rolling(4, 4, (1.0, 0.0));
+
+    let dry_turn = pc.move_player(&concrete, &player, (0, 1));
+    let wet_turn = pc.move_player(&wet, &player, (0, 1));
+
+    assert!(vec_ops::magnitude(wet_turn.balance) > vec_ops::magnitude(dry_turn.balance));
+}
+
+#[test]
+fn a_long_ramp_slows_the_skater_once() {
+    let pc = controller();
+    let mut table = flat_table(12);
+    for x in 4..7 {
+        table.set_surface((x, 5), Surface::Ramp(1, 0));
+    }
+    let flat = flat_table(12);
+
+    // onto the ramp, then along it
+    let mut on_ramp = rolling(3, 5, (1.0, 0.0));
+    let mut on_flat = on_ramp;
+    on_ramp = pc.move_player(&table, &on_ramp, (0, 0));
+    on_flat = pc.move_player(&flat, &on_flat, (0, 0));
+    assert!((on_ramp.speed.0 - on_flat.speed.0 * pc.physics.up_speed_factor).abs() < EPSILON);
+
+    let entered = on_ramp.speed.0;
+    let along = pc.move_player(&table, &rolling(4, 5, (entered, 0.0)), (1, 0));
+    let flat_along = pc.move_player(&flat, &rolling(4, 5, (entered, 0.0)), (1, 0));
+    assert!((along.speed.0 - flat_along.speed.0).abs() < EPSILON);
+}
@@ -0,0 +1,153 @@
+// Ollies, manuals and bails (PlayerController::perform_trick)
+
+mod common;
+
+use controller::physics::Tunable;
+use controller::profile::Profile;
+use model::obstacle::Obstacle;
+use model::player_event::PlayerEvent;
+use model::trick::Trick;
+use util::vec_ops;
+
+use common::{EPSILON, controller, flat_table, rolling};
+
+#[test]
+fn an_ollie_clears_a_pit() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((3, 4), Obstacle::Pit);
+    let player = rolling(2, 4, (1.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+
+    assert_eq!(jumped.recent_event, PlayerEvent::Ollie);
+    assert_eq!(jumped.position, (4, 4));
+    assert!((jumped.speed.0 - 1.5 * (1.0 - pc.physics.ollie_speed_cost)).abs() < EPSILON);
+}
+
+#[test]
+fn an_ollie_clears_a_rail() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((3, 4), Obstacle::Rail(0, 1));
+    let player = rolling(2, 4, (1.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+
+    assert_eq!(jumped.recent_event, PlayerEvent::Ollie);
+    assert_eq!(jumped.position, (4, 4));
+}
+
+#[test]
+fn an_ollie_on_flat_ground_hops_one_tile() {
+    let pc = controller();
+    let table = flat_table(9);
+    let player = rolling(2, 4, (1.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+
+    assert_eq!(jumped.recent_event, PlayerEvent::Ollie);
+    assert_eq!(jumped.position, (3, 4));
+}
+
+#[test]
+fn an_ollie_into_a_wall_falls_over() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((3, 4), Obstacle::Pit);
+    table.set_obstacle((4, 4), Obstacle::Wall);
+    let player = rolling(2, 4, (1.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+
+    assert_eq!(jumped.recent_event, PlayerEvent::FallOver);
+    assert_eq!(jumped.n_falls, player.n_falls + 1);
+}
+
+#[test]
+fn an_ollie_too_slow_to_leave_the_ground_is_a_wait() {
+    let pc = controller();
+    let mut table = flat_table(9);
+    table.set_obstacle((3, 4), Obstacle::Pit);
+    let player = rolling(2, 4, (pc.physics.ollie_min_speed * 0.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+    let waited = pc.move_player_analog(&table, &player, (0, 0), 0.0);
+
+    assert_ne!(jumped.recent_event, PlayerEvent::Ollie);
+    assert_eq!(jumped.position, waited.position);
+    assert_eq!(jumped.speed, waited.speed);
+    assert_eq!(jumped.balance, waited.balance);
+}
+
+#[test]
+fn a_manual_keeps_more_speed_than_coasting() {
+    let pc = controller();
+    let table = flat_table(21);
+    let player = rolling(5, 10, (1.5, 0.0));
+
+    let manual = pc.perform_trick(&table, &player, Trick::Manual);
+    let coast = pc.move_player_analog(&table, &player, (0, 0), 0.0);
+
+    assert_eq!(manual.recent_event, PlayerEvent::Manual);
+    assert!(vec_ops::magnitude(manual.speed) > vec_ops::magnitude(coast.speed));
+    // leaning back, against the direction of travel
+    assert!(manual.balance.0 < 0.0);
+}
+
+#[test]
+fn a_manual_held_too_long_runs_out_of_balance() {
+    let mut pc = controller();
+    // tips back faster than balance recovers, so it has to end in a fall
+    pc.physics.manual_balance_cost = 0.8;
+    let table = flat_table(101);
+    let mut player = rolling(5, 50, (1.5, 0.0));
+
+    let mut manuals = 0;
+    while player.recent_event != PlayerEvent::FallOver {
+        player = pc.perform_trick(&table, &player, Trick::Manual);
+        if player.recent_event == PlayerEvent::Manual {
+            manuals += 1;
+        }
+        assert!(manuals < 20, "never fell");
+    }
+
+    assert!(manuals > 0);
+    assert_eq!(player.n_falls, 1);
+    assert_eq!(player.balance, (0.0, 0.0));
+}
+
+#[test]
+fn tricks_go_by_the_skaters_profile() {
+    let mut pc = controller();
+    let mut clumsy = Profile::new("Clumsy", "");
+    clumsy.set(Tunable::from_name("ollie_min_speed").unwrap(), 2.0);
+    pc.profiles = vec![Profile::default(), clumsy];
+    let table = flat_table(9);
+    let mut player = rolling(2, 4, (1.5, 0.0));
+
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+    assert_eq!(jumped.recent_event, PlayerEvent::Ollie);
+
+    // too slow for them to get off the ground
+    player.profile = 1;
+    let jumped = pc.perform_trick(&table, &player, Trick::Ollie);
+    assert_ne!(jumped.recent_event, PlayerEvent::Ollie);
+}
+
+#[test]
+fn a_bail_stops_without_counting_as_a_fall() {
+    let pc = controller();
+    let table = flat_table(9);
+    let mut player = rolling(4, 4, (2.0, 0.0));
+    player.n_falls = 2;
+    player.balance = (1.0, 0.0);
+
+    let bailed = pc.perform_trick(&table, &player, Trick::Bail);
+
+    assert_eq!(bailed.recent_event, PlayerEvent::Bail);
+    assert_eq!(bailed.n_falls, 2);
+    assert_eq!(bailed.speed, (0.0, 0.0));
+    assert_eq!(bailed.balance, (0.0, 0.0));
+    assert_eq!(bailed.position, player.position);
+}
@@ -0,0 +1,87 @@
+use controller::turn_scheduler::{TurnScheduler, TICKS_PER_TURN};
+
+// runs the scheduler until the clock passes `turns` whole turns, rescheduling
+// every actor with its fixed speed, and returns the order actors acted in
+fn run(speeds: &[(f32, f32)], turns: u64) -> Vec<usize> {
+    let mut scheduler = TurnScheduler::new();
+    for index in 0..speeds.len() {
+        scheduler.schedule(index, 0);
+    }
+
+    let mut order = Vec::new();
+    while let Some(actor) = scheduler.next_turn() {
+        if scheduler.now() >= turns * TICKS_PER_TURN {
+            break;
+        }
+        order.push(actor);
+        scheduler.schedule_after_move(actor, speeds[actor]);
+    }
+
+    order
+}
+
+#[test]
+fn faster_skaters_act_more_often() {
+    let order = run(&[(0.0, 0.0), (2.0, 0.0), (0.0, 1.0)], 300);
+    let count = |actor: usize| order.iter().filter(|a| **a == actor).count() as f32;
+
+    // moves take 1 / (1 + |speed|) turns
+    assert!((count(0) - 300.0).abs() <= 1.0);
+    assert!((count(1) - 900.0).abs() <= 1.0);
+    assert!((count(2) - 600.0).abs() <= 1.0);
+}
+
+#[test]
+fn equal_speeds_take_turns() {
+    let order = run(&[(1.0, 1.0), (1.0, 1.0), (1.0, 1.0)], 50);
+    for window in order.chunks(3) {
+        if window.len() == 3 {
+            assert_eq!(window, &[0, 1, 2]);
+        }
+    }
+}
+
+#[test]
+fn schedule_is_deterministic() {
+    let speeds = [(0.3, 1.7), (2.4, 0.0), (0.0, 0.0), (1.1, -0.9), (-2.0, 0.5)];
+    assert_eq!(run(&speeds, 100), run(&speeds, 100));
+}
+
+#[test]
+fn ties_go_in_insertion_order() {
+    let mut scheduler = TurnScheduler::new();
+    scheduler.schedule('b', 10);
+    scheduler.schedule('a', 10);
+    scheduler.schedule('c', 5);
+
+    assert_eq!(scheduler.next_turn(), Some('c'));
+    assert_eq!(scheduler.now(), 5);
+    assert_eq!(scheduler.next_turn(), Some('b'));
+    assert_eq!(scheduler.next_turn(), Some('a'));
+    assert_eq!(scheduler.next_turn(), None);
+    assert_eq!(scheduler.now(), 10);
+}
+
+#[test]
+fn rescheduling_replaces_the_old_turn() {
+    let mut scheduler = TurnScheduler::new();
+    scheduler.schedule(0, 100);
+    scheduler.schedule(1, 50);
+    scheduler.schedule(0, 10);
+
+    assert_eq!(scheduler.len(), 2);
+    assert_eq!(scheduler.peek(), Some(0));
+    assert!(scheduler.remove(0));
+    assert!(!scheduler.contains(0));
+    assert_eq!(scheduler.next_turn(), Some(1));
+}
+
+#[test]
+fn time_matches_player_time() {
+    let ticks = TurnScheduler::<u8>::turn_ticks((0.0, 3.0));
+    assert_eq!(ticks, TICKS_PER_TURN / 4);
+    assert!((TurnScheduler::<u8>::ticks_to_time(ticks) - 0.25).abs() < 0.001);
+
+    let ticks = TurnScheduler::<u8>::turn_ticks((3.0, 4.0));
+    assert!((TurnScheduler::<u8>::ticks_to_time(ticks) - 1.0 / 6.0).abs() < 0.001);
+}
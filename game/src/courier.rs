@@ -0,0 +1,31 @@
+use model::player::Player;
+use model::run_stats::RunStats;
+use rltk::RGB;
+
+// most couriers that can share one screen
+pub const MAX_COURIERS: usize = 4;
+
+// One of the humans delivering packages. The map, the opponents and the
+// jobs on it are shared, everything here is theirs alone
+pub struct Courier {
+    pub player: Player,
+    // where the package they picked up was, None while empty handed
+    pub package: Option<(i32, i32)>,
+    pub score: i32,
+    // their run so far
+    pub stats: RunStats,
+    // what the hint key tells them
+    pub lookmode_string: (String, RGB),
+}
+
+impl Courier {
+    pub fn new(player: Player, stats: RunStats) -> Self {
+        Courier {
+            player,
+            package: None,
+            score: 0,
+            stats,
+            lookmode_string: ("Find the package".to_string(), RGB::named(rltk::WHITE)),
+        }
+    }
+}
@@ -4,25 +4,67 @@ use std::vec;
 use controller::collision;
 use model::direction::Direction;
 use model::map_gen;
+use model::memory::MemoryDecay;
 use rltk::{GameState, Point, RandomNumberGenerator, VirtualKeyCode, RGB};
-use util::heap::Heap;
-
-use util::vec_ops;
 
 use model::goal_table::GoalTable;
+use model::high_scores::HighScores;
 use model::obstacle::Obstacle;
 use model::obstacle_table::ObstacleTable;
 use model::player::{Player, PlayerType};
 use model::player_event::PlayerEvent;
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
 use model::state::ProcState;
-
+use model::trick::Trick;
+use model::visibility;
+
+use view::bindings_viewer::Capture;
+use view::camera::Camera;
+use view::frame::{Frame, Scene};
+use view::canvas::Canvas;
+use view::message_log::MessageKind;
+use view::{main_menu_viewer, main_viewer};
 use view::view_manager::ViewManager;
 
 use controller::ai_controller::AIController;
+use controller::bindings::{self, Action, Bindings};
 use controller::look_mode::LookMode;
+use controller::physics;
 use controller::player_controller::PlayerController;
-
+use controller::profile::Board;
+use controller::sandbox::Sandbox;
+use controller::shop::{self, Item, Progress};
+use controller::turn_scheduler::TurnScheduler;
+
+use net::checksum::Checksum;
+use net::client::{Client, ClientEvent};
+use net::connection::{Connection, NetError};
+use net::lockstep::Lockstep;
+use net::protocol::{Command, RunSetup};
+
+use crate::courier::{Courier, MAX_COURIERS};
+use crate::input::Input;
 use crate::spawning;
+use crate::storage;
+
+// saved with storage, best runs first
+const HIGH_SCORES_FILE: &str = "highscores.txt";
+const MAX_HIGH_SCORES: usize = 10;
+// the player's own keys, when they changed any. Other couriers' keys
+// go in bindings_courier<n>.txt
+const BINDINGS_FILE: &str = "bindings.txt";
+// money, skater and board, see shop.rs
+const PROGRESS_FILE: &str = "progress.txt";
+// the human's board in PlayerController::boards
+const HUMAN_BOARD: usize = 1;
+
+// what the human chose to do with their turn
+enum HumanAction {
+    // a push in a direction, from 0 (none) to 1 (full strength)
+    Move((i32, i32), f32),
+    Trick(Trick),
+}
 
 pub struct Game {
     obs_table: ObstacleTable,
@@ -31,22 +73,67 @@ pub struct Game {
     viewer: ViewManager,
 
     player_control: PlayerController,
+    // each courier's keys, for as many as can play so they
+    // outlast changing how many do
+    bindings: Vec<Bindings>,
 
     opponents: Vec<AIController>,
-
-    _lookmode: LookMode,
-    lookmode_string: (String, RGB),
-
-    pub player: Player,
-    pub recipient_idx: i32,
-    pub score: i32,
+    turns: TurnScheduler<(usize, PlayerType)>,
+
+    lookmode: LookMode,
+
+    // last cell the mouse was over and the size of the screen
+    // it was drawn on, to work out what it points at
+    mouse: Option<(i32, i32)>,
+    screen_size: (u32, u32),
+
+    // the humans skating this run, picked from the main menu
+    couriers: Vec<Courier>,
+    n_couriers: usize,
+    // the courier taking their turn. Set from the turn order when they
+    // press a key, so it stays theirs until the results of the move are in
+    active: usize,
+
+    high_scores: HighScores,
+    // place of the last finished run in high_scores, if it made it
+    new_high_score: Option<usize>,
+
+    // picked from the main menu, modes[mode_idx] decides when a run ends
+    modes: Vec<RunMode>,
+    mode_idx: usize,
+    // the integrator everyone moves with in each of modes, see physics.rs
+    mode_physics: Vec<String>,
+    // the skater the player picked from PlayerController::profiles
+    profile_idx: usize,
+    // what's for sale between runs and what the player has bought
+    shop_items: Vec<Item>,
+    progress: Progress,
+    // the run shown on the results screen and why it ended
+    last_run: RunStats,
+    run_end: Option<RunEnd>,
+    // every courier's run (courier index, run), best first
+    standings: Vec<(usize, RunStats)>,
+
+    // the server this game plays a networked run with, and which courier
+    // in it is this machine's once it has started (see net/)
+    online: Option<Client>,
+    online_courier: Option<usize>,
+    // the run is played in step with other machines, so only what comes
+    // from the run's setup may change it
+    lockstep: bool,
 
     pub n_opponents: u32,
     pub ai_sight_radius: u32,
+    pub sight_radius: u32,
     pub giveup_turns: u32,
+    // strength of a gentle push, used instead of a full one when
+    // gentle pushes are switched on or Shift is held (but not both)
+    pub gentle_push: f32,
+    push_gentle: bool,
+    // a skater on their own for trying out model.txt values
+    sandbox: Sandbox,
     turns_to_giveup: Vec<u32>,
     waiting_to_respawn_idx: HashSet<u32>,
-    shirt_colors: [RGB; 8],
 
     state: ProcState,
     last_state: ProcState,
@@ -67,29 +154,50 @@ impl Game {
             viewer: ViewManager::new(),
 
             player_control: PlayerController::new(),
+            bindings: vec![Bindings::default(); MAX_COURIERS],
             opponents: Vec::new(),
-            _lookmode: LookMode::new(),
-            lookmode_string: ("Find the package".to_string(), RGB::named(rltk::WHITE)),
-
-            player: Player::new(table_width as i32 / 2, table_height as i32 / 2),
-            recipient_idx: -1,
-            score: 0,
+            turns: TurnScheduler::new(),
+            lookmode: LookMode::new(),
+            mouse: None,
+            screen_size: (0, 0),
+
+            couriers: vec![Courier::new(
+                Player::new(table_width as i32 / 2, table_height as i32 / 2),
+                RunStats::new(0, (table_width, table_height)),
+            )],
+            n_couriers: 1,
+            active: 0,
+
+            high_scores: HighScores::new(MAX_HIGH_SCORES),
+            new_high_score: None,
+
+            modes: vec![
+                RunMode::TimedShift(300.0),
+                RunMode::Quota(5),
+                RunMode::Survival(3),
+            ],
+            mode_idx: 0,
+            mode_physics: vec!["discrete".to_string(); 3],
+            profile_idx: 0,
+            shop_items: shop::load_items(),
+            progress: Progress::new(),
+            last_run: RunStats::new(0, (table_width, table_height)),
+            run_end: None,
+            standings: Vec::new(),
+
+            online: None,
+            online_courier: None,
+            lockstep: false,
 
             n_opponents: 2,
             ai_sight_radius: 8,
+            sight_radius: 16,
             giveup_turns: 3,
+            gentle_push: 0.5,
+            push_gentle: false,
+            sandbox: Sandbox::default(),
             turns_to_giveup: Vec::new(),
             waiting_to_respawn_idx: HashSet::new(),
-            shirt_colors: [
-                RGB::named(rltk::CYAN),
-                RGB::named(rltk::MAGENTA),
-                RGB::named(rltk::AQUAMARINE),
-                RGB::named(rltk::FORESTGREEN),
-                RGB::named(rltk::TEAL),
-                RGB::named(rltk::BURLYWOOD),
-                RGB::named(rltk::BROWN1),
-                RGB::named(rltk::GRAY),
-            ],
 
             state: ProcState::MainMenu,
             last_state: ProcState::MainMenu,
@@ -107,11 +215,125 @@ impl Game {
     }
 }
 
+impl Game {
+    // plays the run the server behind connection hosts, once it starts
+    pub fn join(&mut self, connection: Box<dyn Connection>, name: &str) -> Result<(), NetError> {
+        self.online = Some(Client::join(connection, name)?);
+        self.viewer.main_view.add_message(
+            MessageKind::System,
+            "Waiting for the other couriers to join".to_string(),
+        );
+        self.set_state(ProcState::Playing);
+        Ok(())
+    }
+
+    // a networked run for couriers on a new seed, in the mode and
+    // with the skater picked on this machine
+    pub fn run_setup(&self, couriers: usize) -> RunSetup {
+        RunSetup {
+            seed: RandomNumberGenerator::new().next_u64(),
+            couriers,
+            mode: self.mode_idx,
+            skater: self.profile_idx,
+        }
+    }
+}
+
+impl Lockstep for Game {
+    fn start(&mut self, setup: &RunSetup) {
+        self.mode_idx = setup.mode.min(self.modes.len() - 1);
+        self.profile_idx = setup.skater.min(self.player_control.profiles.len() - 1);
+        self.n_couriers = setup.couriers.clamp(1, MAX_COURIERS);
+        self.lockstep = true;
+        // the physics everyone starts from, not this machine's sandbox
+        self.player_control.physics = PlayerController::new().physics;
+        self.reset_game_seeded(setup.seed);
+        self.gameover_done = false;
+        self.set_state(ProcState::Playing);
+    }
+
+    fn turn_courier(&self) -> Option<usize> {
+        match self.state {
+            ProcState::GameOver => None,
+            _ => Some(self.moving_courier()),
+        }
+    }
+
+    // a whole turn, with everything the move set off
+    fn apply(&mut self, courier: usize, command: Command) {
+        if self.turn_courier() != Some(courier) {
+            return;
+        }
+        // the turn plays behind whatever this machine has open
+        let screen = (self.state, self.last_state);
+        self.active = courier;
+        self.play_command(command);
+        while matches!(
+            self.state,
+            ProcState::PostMove
+                | ProcState::Chat
+                | ProcState::GotPackage(..)
+                | ProcState::DeliveredPackage
+        ) {
+            self.process(Input::default());
+        }
+        if self.state == ProcState::Playing {
+            (self.state, self.last_state) = screen;
+        }
+    }
+
+    // everything a turn can change that the next turns depend on
+    fn checksum(&self) -> u64 {
+        let mut sum = Checksum::new();
+        let add_player = |sum: &mut Checksum, player: &Player| {
+            sum.add_i32(player.x());
+            sum.add_i32(player.y());
+            sum.add_f32(player.speed.0);
+            sum.add_f32(player.speed.1);
+            sum.add_f32(player.balance.0);
+            sum.add_f32(player.balance.1);
+            sum.add_f32(player.time);
+            sum.add_u64(player.n_falls as u64);
+            sum.add_u64(player.n_delivered as u64);
+        };
+        for courier in self.couriers.iter() {
+            add_player(&mut sum, &courier.player);
+            sum.add_i32(courier.score);
+            if let Some((x, y)) = courier.package {
+                sum.add_i32(x);
+                sum.add_i32(y);
+            }
+        }
+        for opponent in self.opponents.iter() {
+            add_player(&mut sum, &opponent.player);
+        }
+
+        let mut goals: Vec<(i32, i32, usize)> = self
+            .goal_table
+            .goals
+            .iter()
+            .map(|(&(x, y), &(recipient, _))| (x, y, recipient))
+            .collect();
+        goals.sort();
+        for (x, y, recipient) in goals {
+            sum.add_i32(x);
+            sum.add_i32(y);
+            sum.add_u64(recipient as u64);
+        }
+        let mut waiting: Vec<u32> = self.waiting_to_respawn_idx.iter().copied().collect();
+        waiting.sort();
+        for index in waiting {
+            sum.add_u64(index as u64);
+        }
+        sum.finish()
+    }
+}
+
 impl GameState for Game {
     fn tick(&mut self, ctx: &mut rltk::BTerm) {
         // crashes the page on the web
         /*
-        match ctx.key {
+        match key {
             None => {}
             Some(key) => match key {
                 VirtualKeyCode::C => {
@@ -124,30 +346,74 @@ impl GameState for Game {
         }
         */
 
-        let _playing = self.handle_input(ctx);
+        let _playing = self.frame(Input::from_context(ctx), ctx);
 
         /* crashes the webpage
         if !playing {
             std::process::exit(0);
         }
         */
-
-        if self.first_draw {
-            ctx.cls();
-            self.print_screen(ctx);
-            self.first_draw = false;
-        } else if self.redraw {
-            ctx.cls();
-            self.print_screen(ctx);
-        }
     }
 }
 
 impl Game {
     fn init(g: &mut Game) {
         g.properties_from_file();
+        if let Some(saved) = storage::load(HIGH_SCORES_FILE) {
+            g.high_scores = HighScores::from_config(&saved, MAX_HIGH_SCORES);
+        }
+
+        for index in 0..MAX_COURIERS {
+            g.bindings[index] = match storage::load(&bindings_file(index)) {
+                Some(saved) => Bindings::from_config(&saved),
+                None => bindings::courier_bindings(index),
+            };
+        }
+        if let Some(saved) = storage::load(PROGRESS_FILE) {
+            g.progress = Progress::from_config(&saved);
+        }
+        let profiles = &g.player_control.profiles;
+        if let Some(idx) = profiles.iter().position(|p| p.name == g.progress.skater) {
+            g.profile_idx = idx;
+        }
+        g.fit_board();
 
         g.reset_game();
+        g.report_conflicts();
+    }
+
+    // keys bound twice by the couriers playing, and keys more than one of them skates with
+    fn report_conflicts(&mut self) {
+        let couriers = &self.bindings[..self.n_couriers];
+        let mut messages = Vec::new();
+        for (index, courier) in couriers.iter().enumerate() {
+            let whose = match couriers.len() {
+                1 => String::new(),
+                _ => format!("Courier {}: ", index + 1),
+            };
+            for (key, first, second) in courier.conflicts() {
+                messages.push(format!(
+                    "{}{} is bound to both {} and {}, using it for {}",
+                    whose,
+                    bindings::key_label(key),
+                    first.description(),
+                    second.description(),
+                    first.description()
+                ));
+            }
+        }
+        for (key, first, second) in bindings::shared_keys(couriers) {
+            messages.push(format!(
+                "{} is a key of both Courier {} and Courier {}",
+                bindings::key_label(key),
+                first + 1,
+                second + 1
+            ));
+        }
+
+        for message in messages {
+            self.viewer.main_view.add_message(MessageKind::System, message);
+        }
     }
     // regen opponent
     fn _add_opponent_tunnel(&mut self) {
@@ -163,10 +429,10 @@ impl Game {
                 self.obs_table.height() as i32 / 2 - 1,
             );
 
-        if !(x == self.player.x() && y == self.player.y())
+        if !self.couriers.iter().any(|c| c.player.xy() == (x, y))
             && !self.obs_table.blocked.contains_key(&(x, y))
         {
-            self.opponents.push(AIController::new(x, y));
+            self.push_opponent(x, y);
             self.turns_to_giveup.push(self.giveup_turns);
             self.obs_table.set_obstacle((x, y), Obstacle::Platform);
 
@@ -177,13 +443,24 @@ impl Game {
     fn add_opponent_platform(&mut self) {
         let (x, y) = spawning::random_platform(&self.obs_table, &mut self.rng);
 
-        self.opponents.push(AIController::new(x, y));
+        self.push_opponent(x, y);
         self.turns_to_giveup.push(self.giveup_turns);
     }
 
+    // opponents come in every kind of skater. Their choices come from the
+    // run's seed too, so a run can be replayed from it
+    fn push_opponent(&mut self, x: i32, y: i32) {
+        let mut ai = AIController::seeded(x, y, self.rng.next_u64());
+        ai.player.profile = self.rng.range(0, self.player_control.profiles.len());
+        self.opponents.push(ai);
+    }
+
     pub fn properties_from_file(&mut self) {
         let mut width = self.obs_table.width();
         let mut height = self.obs_table.height();
+        let mut decay_mode = "never".to_string();
+        let mut decay_amount = 0.0;
+        let mut run_mode = String::new();
         // Retrieve the raw data as an array of u8 (8-bit unsigned chars)
         let raw_data = rltk::embedding::EMBED
             .lock()
@@ -209,6 +486,70 @@ impl Game {
                 if let Ok(num) = words[1].parse::<u32>() {
                     self.ai_sight_radius = num;
                 }
+            } else if words[0] == "sight_radius" {
+                if let Ok(num) = words[1].parse::<u32>() {
+                    self.sight_radius = num;
+                }
+            } else if words[0] == "memory_decay" {
+                decay_mode = words[1].to_string();
+            } else if words[0] == "memory_decay_amount" {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    decay_amount = num;
+                }
+            } else if words[0] == "camera_dead_zone_x" {
+                if let Ok(num) = words[1].parse::<i32>() {
+                    self.viewer.main_view.camera.dead_zone.0 = num;
+                }
+            } else if words[0] == "camera_dead_zone_y" {
+                if let Ok(num) = words[1].parse::<i32>() {
+                    self.viewer.main_view.camera.dead_zone.1 = num;
+                }
+            } else if words[0] == "camera_look_ahead" {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    self.viewer.main_view.camera.look_ahead = num;
+                }
+            } else if words[0] == "trajectory_steps" {
+                if let Ok(num) = words[1].parse::<usize>() {
+                    self.viewer.main_view.trajectory_steps = num;
+                }
+            } else if words[0] == "reveal_map" {
+                if let Ok(num) = words[1].parse::<u32>() {
+                    self.viewer.main_view.reveal_all = num != 0;
+                }
+            } else if words[0] == "shift_time" {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    self.modes[0] = RunMode::TimedShift(num);
+                }
+            } else if words[0] == "quota" {
+                if let Ok(num) = words[1].parse::<u32>() {
+                    self.modes[1] = RunMode::Quota(num);
+                }
+            } else if words[0] == "survival_falls" {
+                if let Ok(num) = words[1].parse::<u32>() {
+                    self.modes[2] = RunMode::Survival(num);
+                }
+            } else if words[0] == "shift_physics"
+                || words[0] == "quota_physics"
+                || words[0] == "survival_physics"
+            {
+                let idx = match words[0] {
+                    "shift_physics" => 0,
+                    "quota_physics" => 1,
+                    _ => 2,
+                };
+                if physics::integrator(words[1]).is_some() {
+                    self.mode_physics[idx] = words[1].to_string();
+                }
+            } else if words[0] == "run_mode" {
+                run_mode = words[1].to_string();
+            } else if words[0] == "gentle_push" {
+                if let Ok(num) = words[1].parse::<f32>() {
+                    self.gentle_push = num;
+                }
+            } else if words[0] == "sandbox_trace" {
+                if let Ok(num) = words[1].parse::<usize>() {
+                    self.sandbox = Sandbox::new(num);
+                }
             } else if words[0] == "giveup_turns" {
                 if let Ok(num) = words[1].parse::<u32>() {
                     self.giveup_turns = num;
@@ -227,44 +568,103 @@ impl Game {
         }
 
         self.obs_table.resize(width, height);
+        self.mode_idx = match run_mode.as_str() {
+            "quota" => 1,
+            "survival" => 2,
+            _ => 0,
+        };
+        if let Some(decay) = MemoryDecay::from_config(&decay_mode, decay_amount) {
+            self.obs_table.memory.decay = decay;
+        }
     }
 
-    pub fn handle_input(&mut self, ctx: &mut rltk::Rltk) -> bool {
+    // handles one key press (or none) and redraws canvas if anything changed.
+    // Returns false when the player asked to quit
+    pub fn frame(&mut self, input: Input, canvas: &mut dyn Canvas) -> bool {
+        self.poll_online();
+        let playing = self.handle_input(input);
+
+        if self.first_draw {
+            canvas.cls();
+            self.print_screen(canvas);
+            self.first_draw = false;
+        } else if self.redraw {
+            canvas.cls();
+            self.print_screen(canvas);
+        }
+
+        playing
+    }
+
+    pub fn handle_input(&mut self, input: Input) -> bool {
         self.redraw = false;
-        self.process(ctx)
+        self.process(input)
     }
 
-    pub fn print_screen(&mut self, ctx: &mut rltk::Rltk) {
-        self.viewer.get_screen(
-            ctx,
-            &self.state,
-            &mut self.obs_table,
-            &self.goal_table,
-            &self.player,
-            &self.opponents,
-            &self.player_control,
-            self.player_control.max_speed,
-            self.player_control.fallover_threshold,
-            ctx.get_char_size().0,
-            ctx.get_char_size().1,
-            self.score,
-        );
+    pub fn print_screen(&mut self, ctx: &mut dyn Canvas) {
+        let (width, height) = (ctx.width(), ctx.height());
+        self.screen_size = (width, height);
+
+        let turn = self.viewed_courier();
+        let player = self.couriers[turn].player;
+        let bindings = match self.state {
+            ProcState::Bindings => &self.bindings[self.viewer.bindings.courier],
+            _ => &self.bindings[self.courier_keys()],
+        };
+        // sharing the screen splits it between the couriers, online every
+        // machine shows its own courier with the others on the map
+        let mut couriers: Vec<(Player, i32)> = Vec::new();
+        self.viewer.main_view.other_couriers.clear();
+        for (index, courier) in self.couriers.iter().enumerate() {
+            if self.online_courier.is_none() || index == turn {
+                couriers.push((courier.player, courier.score));
+            } else {
+                self.viewer
+                    .main_view
+                    .other_couriers
+                    .push((courier.player, main_viewer::courier_color(index)));
+            }
+        }
+        let shown = if self.online_courier.is_some() { 0 } else { turn };
+
+        let frame = Frame {
+            state: self.state,
+            scene: Scene {
+                table: &self.obs_table,
+                goals: &self.goal_table,
+                opponents: &self.opponents,
+                controller: &self.player_control,
+            },
+            player: &player,
+            score: self.couriers[turn].score,
+            package: self.couriers[turn].package,
+            couriers: &couriers,
+            active: shown,
+            mode: &self.modes[self.mode_idx],
+            last_run: &self.last_run,
+            run_end: self.run_end,
+            standings: &self.standings,
+            high_scores: &self.high_scores,
+            new_high_score: self.new_high_score,
+            bindings,
+            sandbox: &self.sandbox,
+            shop_items: &self.shop_items,
+            progress: &self.progress,
+        };
+        self.viewer.get_screen(ctx, &frame);
     }
 
-    fn process(&mut self, ctx: &mut rltk::Rltk) -> bool {
+    fn process(&mut self, input: Input) -> bool {
+        let key = input.key;
         match self.state {
-            ProcState::MainMenu => {
-                return self.process_main_menu(ctx);
-            }
+            ProcState::MainMenu => self.process_main_menu(input),
             ProcState::Help => {
-                return self.process_help(ctx);
+                return self.process_help(key);
             }
             ProcState::GameOver => {
-                return self.process_gameover(ctx);
-            }
-            ProcState::Playing => {
-                return self.process_playing(ctx);
+                return self.process_gameover(key);
             }
+            ProcState::Playing => self.process_playing(input),
             ProcState::PostMove => {
                 return self.process_post_move();
             }
@@ -278,11 +678,17 @@ impl Game {
                 return self.process_delivered();
             }
             ProcState::LookMode => {
-                return self.process_lookmode(ctx);
+                return self.process_lookmode(key);
             }
             ProcState::Restart => {
                 return self.process_restart();
-            } /*
+            }
+            ProcState::MessageHistory => self.process_history(key),
+            ProcState::HighScores => self.process_high_scores(key),
+            ProcState::Bindings => self.process_bindings(key),
+            ProcState::Sandbox => self.process_sandbox(input),
+            ProcState::Shop => self.process_shop(key),
+            /*
               _  => {
                   return false;
               },*/
@@ -291,65 +697,50 @@ impl Game {
 
     fn process_chat(&mut self) -> bool {
         let chat_radius = 1;
-        let fov = rltk::field_of_view(
-            Point::new(self.player.x(), self.player.y()),
-            chat_radius,
-            &self.obs_table,
-        );
+        let me = self.couriers[self.active].player;
+        let fov = rltk::field_of_view(Point::new(me.x(), me.y()), chat_radius, &self.obs_table);
         let chat_str = "Stick it to the man".to_string();
         let mut alone = true;
         let mut next_state = ProcState::Playing;
         for p in fov.iter() {
-            if p.x == self.player.x() && p.y == self.player.y() {
+            if p.x == me.x() && p.y == me.y() {
                 continue;
             }
 
             if self.obs_table.blocked.contains_key(&(p.x, p.y)) {
                 alone = false;
-                if self.recipient_idx != -1 {
-                    if let Some(pack_pos) = self
-                        .goal_table
-                        .index_map
-                        .get(&(self.recipient_idx as usize))
-                    {
-                        if !self.goal_table.picked_up.contains(pack_pos) {
+                match self.couriers[self.active].package {
+                    // empty handed, so they point out the nearest job
+                    None => {
+                        if let Some(pack_pos) = self.goal_table.nearest_job((p.x, p.y)) {
                             let dir = vec_to_direction(pack_pos.0 - p.x, pack_pos.1 - p.y);
                             let mut message = "There's a job ".to_string();
                             let dirstr = self.direction_string(dir);
                             match &dirstr.1 {
-                                None => {
-                                    message.push_str(&dirstr.0);
-                                    self.viewer
-                                        .main_view
-                                        .add_string(message, RGB::named(rltk::GREEN));
-                                }
-                                Some(_) => {
-                                    message.push_str("here");
-                                    self.viewer
-                                        .main_view
-                                        .add_string(message, RGB::named(rltk::GREEN));
-                                }
+                                None => message.push_str(&dirstr.0),
+                                Some(_) => message.push_str("here"),
                             }
-                        } else {
-                            let recv_pos =
-                                self.opponents[self.recipient_idx as usize].player.position;
-                            let dir = vec_to_direction(recv_pos.0 - p.x, recv_pos.1 - p.y);
-                            let mut message = "Last saw them ".to_string();
-                            let dirstr = self.direction_string(dir);
-                            match &dirstr.1 {
-                                None => {
-                                    message.push_str(&dirstr.0);
-                                    self.viewer
-                                        .main_view
-                                        .add_string(message, RGB::named(rltk::GREEN));
-                                }
-                                Some(score) => {
-                                    self.score += score;
-                                    self.viewer
-                                        .main_view
-                                        .add_string(dirstr.0, RGB::named(rltk::GREEN));
-                                    next_state = ProcState::DeliveredPackage;
-                                }
+                            self.tell(self.active, MessageKind::Hint, message);
+                        }
+                    }
+                    Some(pack_pos) => {
+                        let recipient = match self.goal_table.goals.get(&pack_pos) {
+                            None => continue,
+                            Some((idx, _)) => *idx,
+                        };
+                        let recv_pos = self.opponents[recipient].player.position;
+                        let dir = vec_to_direction(recv_pos.0 - p.x, recv_pos.1 - p.y);
+                        let mut message = "Last saw them ".to_string();
+                        let dirstr = self.direction_string(dir);
+                        match &dirstr.1 {
+                            None => {
+                                message.push_str(&dirstr.0);
+                                self.tell(self.active, MessageKind::Hint, message);
+                            }
+                            Some(score) => {
+                                self.couriers[self.active].score += score;
+                                self.tell(self.active, MessageKind::Delivery, dirstr.0);
+                                next_state = ProcState::DeliveredPackage;
                             }
                         }
                     }
@@ -358,9 +749,7 @@ impl Game {
         }
 
         if alone {
-            self.viewer
-                .main_view
-                .add_string(chat_str, RGB::named(rltk::DARKGRAY));
+            self.tell(self.active, MessageKind::Chat, chat_str);
         }
 
         self.set_state(next_state);
@@ -372,7 +761,7 @@ impl Game {
         match dir {
             Direction::Center => {
                 let score = ((self.obs_table.width() as f32 * self.obs_table.height() as f32)
-                    / self.player.time as f32)
+                    / self.couriers[self.active].player.time)
                     .round() as i32;
                 return (
                     format!("Thanks. Here's ${} for your hard work", score),
@@ -391,45 +780,72 @@ impl Game {
     }
 
     fn process_delivered(&mut self) -> bool {
-        let pos = self
-            .goal_table
-            .index_map
-            .get(&(self.recipient_idx as usize));
-        match pos {
-            None => {}
-            Some(pos) => {
-                self.goal_table.picked_up.remove(&*pos);
-                self.goal_table.remove_goal_if_reached((pos.0, pos.1));
-            }
-        }
-
-        self.lookmode_string = ("Find the package".to_string(), RGB::named(rltk::WHITE));
+        let courier = &mut self.couriers[self.active];
+        let delivered = courier.package.take();
+        courier.lookmode_string = ("Find the package".to_string(), RGB::named(rltk::WHITE));
 
         // for computing the player's score
-        self.player.n_delivered += 1;
+        courier.player.n_delivered += 1;
+        courier.stats.delivered += 1;
+
+        let mut recipient = None;
+        if let Some(pos) = delivered {
+            recipient = self.goal_table.goals.get(&pos).map(|(idx, _)| *idx);
+            self.goal_table.picked_up.remove(&pos);
+            self.goal_table.remove_goal_if_reached(pos);
+        }
 
         // spawn a new package
-        let mut aiidx = self.rng.range(0, self.opponents.len());
-        while aiidx == self.recipient_idx as usize {
-            aiidx = self.rng.range(0, self.opponents.len());
+        self.add_job(recipient);
+
+        self.set_state(ProcState::Playing);
+        self.check_run_over();
+
+        true
+    }
+
+    // a package for an opponent who isn't waiting for one already, other
+    // than last (who just got theirs) unless there's nobody else
+    fn add_job(&mut self, last: Option<usize>) {
+        let free: Vec<usize> = (0..self.opponents.len())
+            .filter(|idx| !self.goal_table.index_map.contains_key(idx))
+            .collect();
+        let others: Vec<usize> = free.iter().copied().filter(|idx| Some(*idx) != last).collect();
+        let choices = if others.is_empty() { free } else { others };
+        if choices.is_empty() {
+            return;
         }
-        self.recipient_idx = aiidx as i32;
+        let recipient = choices[self.rng.range(0, choices.len())];
 
-        let coloridx = self.rng.range(0, self.shirt_colors.len());
+        // one draw however many shirts the theme has, so players
+        // on different themes stay in step
+        let shirts = &self.viewer.theme().shirts;
+        let shirt = shirts[(self.rng.next_u64() % shirts.len() as u64) as usize];
 
         self.goal_table.add_goal(
-            spawning::random_platform(&mut self.obs_table, &mut self.rng),
-            (aiidx, self.shirt_colors[coloridx]),
+            spawning::random_platform(&self.obs_table, &mut self.rng),
+            (recipient, shirt),
         );
-
-        self.set_state(ProcState::Playing);
-
-        return true;
     }
 
-    fn process_main_menu(&mut self, ctx: &mut rltk::Rltk) -> bool {
-        match ctx.key {
+    fn process_main_menu(&mut self, input: Input) -> bool {
+        // clicking a row does the same as pressing its key
+        let clicked = match input.mouse {
+            Some((_, y)) if input.click => main_menu_viewer::key_at(y, self.screen_size.1),
+            _ => None,
+        };
+
+        match input.key.or(clicked) {
             None => {}
+            // these start a new run, which a networked one can't
+            Some(VirtualKeyCode::M | VirtualKeyCode::C | VirtualKeyCode::N | VirtualKeyCode::P)
+                if self.lockstep =>
+            {
+                self.viewer.main_view.add_message(
+                    MessageKind::System,
+                    "That can't change during a networked run".to_string(),
+                );
+            }
             Some(key) => match key {
                 VirtualKeyCode::Q => {
                     return false;
@@ -437,12 +853,46 @@ impl Game {
                 VirtualKeyCode::Escape => {
                     self.set_state(ProcState::Help);
                 }
-                VirtualKeyCode::Return => {
-                    self.set_state(match self.last_state {
+                VirtualKeyCode::T => {
+                    self.next_theme();
+                }
+                VirtualKeyCode::S => {
+                    self.new_high_score = None;
+                    self.set_state(ProcState::HighScores);
+                }
+                VirtualKeyCode::M => {
+                    self.next_mode();
+                }
+                VirtualKeyCode::C => {
+                    self.next_profile();
+                }
+                VirtualKeyCode::N => {
+                    self.next_couriers();
+                }
+                VirtualKeyCode::B => {
+                    self.set_state(ProcState::Bindings);
+                }
+                VirtualKeyCode::P => {
+                    self.sandbox.reset();
+                    self.follow_sandbox();
+                    self.set_state(ProcState::Sandbox);
+                }
+                VirtualKeyCode::Return => match self.courier_without_keys() {
+                    // a courier who can't move would hold up the run
+                    Some(courier) => {
+                        let viewer = &mut self.viewer.bindings;
+                        viewer.courier = courier;
+                        viewer.message = Some((
+                            format!("Courier {} needs keys to skate with first", courier + 1),
+                            RGB::named(rltk::ORANGE),
+                        ));
+                        self.set_state(ProcState::Bindings);
+                    }
+                    None => self.set_state(match self.last_state {
                         ProcState::LookMode => ProcState::LookMode,
                         _ => ProcState::Playing,
-                    });
-                }
+                    }),
+                },
                 _ => {}
             },
         }
@@ -450,13 +900,82 @@ impl Game {
         return true;
     }
 
-    fn process_help(&mut self, ctx: &mut rltk::Rltk) -> bool {
-        match ctx.key {
+    // the first courier sharing the screen with a direction or waiting left unbound
+    fn courier_without_keys(&self) -> Option<usize> {
+        if self.online.is_some() {
+            return None;
+        }
+        (0..self.n_couriers).find(|index| !self.bindings[*index].can_skate())
+    }
+
+    // a run in one mode can't go on in another, so this starts a new one
+    fn next_mode(&mut self) {
+        self.mode_idx = (self.mode_idx + 1) % self.modes.len();
+        self.reset_game();
+        self.viewer.main_view.clear_log();
+        self.redraw = true;
+    }
+
+    // a different skater starts a new run too
+    fn next_profile(&mut self) {
+        let profiles = &self.player_control.profiles;
+        self.profile_idx = (self.profile_idx + 1) % profiles.len();
+        let profile = &profiles[self.profile_idx];
+        let message = format!("{}: {}", profile.name, profile.about);
+        self.progress.skater = profile.name.clone();
+        storage::save(PROGRESS_FILE, &self.progress.to_config());
+
+        self.reset_game();
+        self.viewer.main_view.clear_log();
+        self.viewer
+            .main_view
+            .add_message(MessageKind::System, message);
+        self.redraw = true;
+    }
+
+    // another courier joins the next run, back to one after MAX_COURIERS
+    fn next_couriers(&mut self) {
+        self.n_couriers = self.n_couriers % MAX_COURIERS + 1;
+        self.viewer.bindings.couriers = self.n_couriers;
+        self.viewer.bindings.courier = 0;
+
+        self.reset_game();
+        self.viewer.main_view.clear_log();
+        self.report_conflicts();
+        self.redraw = true;
+    }
+
+    // recolors the shirts already handed out so recipients
+    // keep their shirt when the theme changes
+    fn next_theme(&mut self) {
+        let old_shirts = self.viewer.theme().shirts.clone();
+        let new_shirts = self.viewer.next_theme().shirts.clone();
+
+        let recolor = |c: RGB| match old_shirts.iter().position(|old| *old == c) {
+            Some(i) => new_shirts[i % new_shirts.len()],
+            None => c,
+        };
+
+        for (_, color) in self.goal_table.goals.values_mut() {
+            *color = recolor(*color);
+        }
+        for courier in self.couriers.iter_mut() {
+            courier.lookmode_string.1 = recolor(courier.lookmode_string.1);
+        }
+
+        self.redraw = true;
+    }
+
+    fn process_help(&mut self, key: Option<VirtualKeyCode>) -> bool {
+        match key {
             None => {}
             Some(key) => match key {
                 VirtualKeyCode::Escape => {
                     self.set_state(ProcState::MainMenu);
                 }
+                VirtualKeyCode::B => {
+                    self.set_state(ProcState::Bindings);
+                }
                 _ => {}
             },
         }
@@ -464,19 +983,226 @@ impl Game {
         return true;
     }
 
-    fn process_gameover(&mut self, ctx: &mut rltk::Rltk) -> bool {
+    // pick an action, then press the key for it. Changes are saved
+    // straight away so they survive closing the game
+    fn process_bindings(&mut self, key: Option<VirtualKeyCode>) -> bool {
+        let key = match key {
+            None => return true,
+            Some(key) => key,
+        };
+        let viewer = &mut self.viewer.bindings;
+        let action = viewer.selected();
+        let courier = viewer.courier;
+
+        match viewer.capture {
+            Some(_) if key == VirtualKeyCode::Escape => {
+                viewer.capture = None;
+                viewer.message = None;
+            }
+            Some(_) if !bindings::is_bindable(key) => {
+                viewer.message = Some((
+                    format!("{:?} can't be bound", key),
+                    RGB::named(rltk::ORANGE),
+                ));
+            }
+            Some(capture) => {
+                let result = match capture {
+                    Capture::Replace => self.bindings[courier].rebind(action, key),
+                    Capture::Add => self.bindings[courier].bind(action, key),
+                };
+                viewer.message = Some(match result {
+                    Ok(()) => (
+                        format!(
+                            "{} is now {}",
+                            action.description(),
+                            self.bindings[courier].describe(action)
+                        ),
+                        RGB::named(rltk::GREEN),
+                    ),
+                    Err(other) => (
+                        format!(
+                            "{} is already bound to {}",
+                            bindings::key_label(key),
+                            other.description()
+                        ),
+                        RGB::named(rltk::ORANGE),
+                    ),
+                });
+                viewer.capture = None;
+                storage::save(&bindings_file(courier), &self.bindings[courier].to_config());
+            }
+            None => match key {
+                VirtualKeyCode::Escape => {
+                    viewer.message = None;
+                    self.set_state(self.last_state);
+                }
+                VirtualKeyCode::Up | VirtualKeyCode::K => viewer.select_previous(),
+                VirtualKeyCode::Down | VirtualKeyCode::J => viewer.select_next(),
+                VirtualKeyCode::Return => viewer.capture = Some(Capture::Replace),
+                VirtualKeyCode::A => viewer.capture = Some(Capture::Add),
+                VirtualKeyCode::Tab => {
+                    viewer.message = None;
+                    viewer.next_courier();
+                }
+                // without a menu key there would be no way back out of a run
+                VirtualKeyCode::Back | VirtualKeyCode::Delete if action == Action::Menu => {
+                    viewer.message = Some((
+                        "Menu needs a key".to_string(),
+                        RGB::named(rltk::ORANGE),
+                    ));
+                }
+                VirtualKeyCode::Back | VirtualKeyCode::Delete => {
+                    self.bindings[courier].clear(action);
+                    viewer.message = None;
+                    storage::save(&bindings_file(courier), &self.bindings[courier].to_config());
+                }
+                VirtualKeyCode::R => {
+                    self.bindings[courier] = bindings::courier_bindings(courier);
+                    viewer.message =
+                        Some(("Restored the default keys".to_string(), RGB::named(rltk::GREEN)));
+                    storage::save(&bindings_file(courier), &self.bindings[courier].to_config());
+                }
+                _ => return true,
+            },
+        }
+        self.redraw = true;
+
+        true
+    }
+
+    // skate on your own while changing the values from model.txt. The
+    // changes last (for every skater) until the game is closed, so they
+    // can be tried out in a run straight away
+    fn process_sandbox(&mut self, input: Input) -> bool {
+        let key = match input.key {
+            None => return true,
+            Some(key) => key,
+        };
+        let steps = if input.shift { 10 } else { 1 };
+
+        match key {
+            VirtualKeyCode::Escape => self.leave_sandbox(),
+            VirtualKeyCode::Up => self.sandbox.select_previous(),
+            VirtualKeyCode::Down => self.sandbox.select_next(),
+            VirtualKeyCode::Left => self.sandbox.adjust(&mut self.player_control.physics, -steps),
+            VirtualKeyCode::Right => self.sandbox.adjust(&mut self.player_control.physics, steps),
+            VirtualKeyCode::R => self.sandbox.reset(),
+            VirtualKeyCode::I => {
+                let current = physics::INTEGRATORS
+                    .iter()
+                    .position(|name| *name == self.player_control.integrator().name())
+                    .unwrap_or(0);
+                let next = physics::INTEGRATORS[(current + 1) % physics::INTEGRATORS.len()];
+                if let Some(integrator) = physics::integrator(next) {
+                    self.player_control.set_integrator(integrator);
+                }
+            }
+            VirtualKeyCode::O => {
+                if self.sandbox.run_map {
+                    self.sandbox.empty_map();
+                } else {
+                    self.sandbox
+                        .copy_map(&self.obs_table, self.couriers[self.active].player.xy());
+                }
+            }
+            VirtualKeyCode::Back => {
+                self.player_control
+                    .set_physics(PlayerController::new().physics);
+            }
+            _ => match self.bindings[0].action(key) {
+                Some(Action::Move(dx, dy)) => {
+                    let strength = if self.push_gentle != input.shift {
+                        self.gentle_push
+                    } else {
+                        1.0
+                    };
+                    let moved = self.player_control.move_player_analog(
+                        &self.sandbox.table,
+                        &self.sandbox.player,
+                        (dx, dy),
+                        strength,
+                    );
+                    self.sandbox.record(moved);
+                }
+                Some(Action::Trick(trick)) => {
+                    let moved = self.player_control.perform_trick(
+                        &self.sandbox.table,
+                        &self.sandbox.player,
+                        trick,
+                    );
+                    self.sandbox.record(moved);
+                }
+                Some(Action::PushStrength) => self.toggle_push_strength(),
+                Some(Action::Menu) => self.leave_sandbox(),
+                _ => return true,
+            },
+        }
+        self.follow_sandbox();
+        self.redraw = true;
+
+        true
+    }
+
+    fn follow_sandbox(&mut self) {
+        let player = &self.sandbox.player;
+        self.viewer
+            .sandbox
+            .table_view
+            .camera
+            .follow(player.xy(), player.speed);
+    }
+
+    // back to the menu, moving with the run mode's physics again
+    fn leave_sandbox(&mut self) {
+        self.use_mode_physics();
+        self.set_state(ProcState::MainMenu);
+    }
+
+    fn use_mode_physics(&mut self) {
+        if let Some(integrator) = physics::integrator(&self.mode_physics[self.mode_idx]) {
+            self.player_control.set_integrator(integrator);
+        }
+    }
+
+    fn toggle_push_strength(&mut self) {
+        self.push_gentle = !self.push_gentle;
+        self.viewer.main_view.push_strength = if self.push_gentle {
+            self.gentle_push
+        } else {
+            1.0
+        };
+        self.redraw = true;
+    }
+
+    fn process_high_scores(&mut self, key: Option<VirtualKeyCode>) -> bool {
+        if let Some(VirtualKeyCode::Escape) = key {
+            self.set_state(ProcState::MainMenu);
+        }
+
+        true
+    }
+
+    fn process_gameover(&mut self, key: Option<VirtualKeyCode>) -> bool {
         if !self.gameover_done {
+            // a networked run is over for everyone, the next one is local
+            self.online = None;
+            self.online_courier = None;
+            self.lockstep = false;
             self.reset_game();
             self.gameover_done = true;
         }
 
-        match ctx.key {
+        match key {
             None => {}
             Some(key) => match key {
                 VirtualKeyCode::R => {
                     self.set_state(ProcState::Playing);
                     self.gameover_done = false;
                 }
+                VirtualKeyCode::S => {
+                    self.viewer.shop.message = None;
+                    self.set_state(ProcState::Shop);
+                }
                 VirtualKeyCode::Escape => {
                     self.set_state(ProcState::MainMenu);
                 }
@@ -487,6 +1213,56 @@ impl Game {
         return true;
     }
 
+    // spend the money from deliveries between runs. What's bought is
+    // saved straight away and goes on the board for the next run
+    fn process_shop(&mut self, key: Option<VirtualKeyCode>) -> bool {
+        let key = match key {
+            None => return true,
+            Some(key) => key,
+        };
+        let n_items = self.shop_items.len();
+        let viewer = &mut self.viewer.shop;
+
+        match key {
+            VirtualKeyCode::Escape => self.set_state(ProcState::GameOver),
+            VirtualKeyCode::Up | VirtualKeyCode::K => viewer.select_previous(n_items),
+            VirtualKeyCode::Down | VirtualKeyCode::J => viewer.select_next(n_items),
+            VirtualKeyCode::Return => {
+                if let Some(idx) = viewer.selected(n_items) {
+                    let item = &self.shop_items[idx];
+                    viewer.message = Some(match self.progress.buy(item) {
+                        Ok(()) => (format!("Bought {}", item.name), RGB::named(rltk::GREEN)),
+                        Err(refusal) => {
+                            (refusal.description().to_string(), RGB::named(rltk::ORANGE))
+                        }
+                    });
+                    storage::save(PROGRESS_FILE, &self.progress.to_config());
+                    self.fit_board();
+                }
+            }
+            _ => return true,
+        }
+        self.redraw = true;
+
+        true
+    }
+
+    // puts what the player owns on their board. In shared runs everyone
+    // rides a stock board, the shop is for skating alone
+    fn fit_board(&mut self) {
+        let board = self.progress.board(&self.shop_items);
+        self.player_control.boards = vec![Board::default(), board];
+        let board = if self.is_shared_run() { 0 } else { HUMAN_BOARD };
+        for courier in self.couriers.iter_mut() {
+            courier.player.board = board;
+        }
+    }
+
+    // more than one courier on this screen, or couriers elsewhere
+    fn is_shared_run(&self) -> bool {
+        self.couriers.len() > 1 || self.lockstep
+    }
+
     fn process_restart(&mut self) -> bool {
         self.reset_game();
         self.set_state(ProcState::Playing);
@@ -494,86 +1270,360 @@ impl Game {
         return true;
     }
 
-    fn process_playing(&mut self, ctx: &mut rltk::Rltk) -> bool {
-        match ctx.key {
-            None => {}
+    fn process_playing(&mut self, input: Input) -> bool {
+        self.active = self.viewed_courier();
+
+        if input.mouse != self.mouse {
+            self.mouse = input.mouse;
+            self.update_hover();
+        }
+
+        let strength = if self.push_gentle != input.shift {
+            self.gentle_push
+        } else {
+            1.0
+        };
+
+        // clicking a tile next to the player pushes towards it
+        if input.click {
+            if let Some(direction) = self.clicked_direction(input.mouse) {
+                self.take_turn(Command::Move(direction, strength));
+                return true;
+            }
+        }
+
+        let keys = self.courier_keys();
+        let action = match input.key.and_then(|key| self.bindings[keys].action(key)) {
+            None => return true,
+            Some(action) => action,
+        };
+
+        match action {
+            Action::Move(dx, dy) => self.take_turn(Command::Move((dx, dy), strength)),
+            Action::PushStrength => self.toggle_push_strength(),
+            Action::Trick(trick) => self.take_turn(Command::Trick(trick)),
+            Action::Hint => self.set_state(ProcState::LookMode),
+            Action::Chat => self.take_turn(Command::Chat),
+            Action::History => {
+                self.viewer.history.scroll_to_end();
+                self.set_state(ProcState::MessageHistory);
+            }
+            Action::Pan(dx, dy) => self.pan_camera(dx, dy),
+            Action::Minimap => {
+                self.viewer.main_view.show_minimap = !self.viewer.main_view.show_minimap;
+                self.redraw = true;
+            }
+            Action::Trajectory => {
+                self.viewer.main_view.show_trajectory = !self.viewer.main_view.show_trajectory;
+                self.redraw = true;
+            }
+            Action::NewGame if self.lockstep => {}
+            Action::NewGame => self.set_state(ProcState::Restart),
+            Action::Menu => self.set_state(ProcState::MainMenu),
+        }
+
+        return true;
+    }
+
+    // scroll back through the messages of this run
+    fn process_history(&mut self, key: Option<VirtualKeyCode>) -> bool {
+        let keys = self.courier_keys();
+        let closes = key.and_then(|key| self.bindings[keys].action(key)) == Some(Action::History);
+        let history = &mut self.viewer.history;
+
+        match key {
+            None => return true,
             Some(key) => match key {
                 VirtualKeyCode::Escape => {
-                    self.set_state(ProcState::MainMenu);
+                    self.set_state(ProcState::Playing);
                 }
-                VirtualKeyCode::Semicolon => {
-                    self.set_state(ProcState::LookMode);
-                }
-                VirtualKeyCode::G => {
-                    self.set_state(ProcState::Chat);
-                }
-                VirtualKeyCode::Key5 => {
-                    self.set_state(ProcState::Restart);
-                }
-                _ => {
-                    let keysv = self.player_control.get_keys();
-                    for keycode in keysv {
-                        if keycode == key {
-                            // compute turn order
-                            let mut heap = Heap::new();
-
-                            // insert the human player
-                            heap.insert(
-                                (100.0 / (1.0 + vec_ops::magnitude(self.player.speed))) as u32,
-                                (999, PlayerType::Human),
-                            );
-
-                            // insert the ai opponents
-                            for index in 0..self.opponents.len() {
-                                heap.insert(
-                                    (100.0
-                                        / (1.0
-                                            + vec_ops::magnitude(
-                                                self.opponents[index].player.speed,
-                                            ))) as u32,
-                                    (index, PlayerType::AI),
-                                );
-                            }
-
-                            while !heap.empty() {
-                                let goes_next = heap.extract_min();
-                                match goes_next.1 {
-                                    PlayerType::Human => {
-                                        self.process_move_human(keycode);
-                                    }
-                                    PlayerType::AI => {
-                                        self.process_ai(goes_next.0);
-                                    }
-                                }
-
-                                collision::update_blocked(
-                                    &mut self.obs_table,
-                                    &self.player,
-                                    &self.opponents,
-                                    &self.waiting_to_respawn_idx,
-                                );
-                            }
-                            break;
-                        }
-                    }
+                _ if closes => {
+                    self.set_state(ProcState::Playing);
                 }
+                VirtualKeyCode::Up | VirtualKeyCode::K => history.scroll_up(1),
+                VirtualKeyCode::Down | VirtualKeyCode::J => history.scroll_down(1),
+                VirtualKeyCode::PageUp => history.page_up(),
+                VirtualKeyCode::PageDown => history.page_down(),
+                VirtualKeyCode::Home => history.scroll_up(usize::MAX / 2),
+                VirtualKeyCode::End => history.scroll_to_end(),
+                VirtualKeyCode::Key0 => history.set_filter(0),
+                VirtualKeyCode::Key1 => history.set_filter(1),
+                VirtualKeyCode::Key2 => history.set_filter(2),
+                VirtualKeyCode::Key3 => history.set_filter(3),
+                VirtualKeyCode::Key4 => history.set_filter(4),
+                VirtualKeyCode::Key5 => history.set_filter(5),
+                _ => return true,
             },
         }
+        self.redraw = true;
 
-        return true;
+        true
+    }
+
+    // the push towards the tile under the mouse,
+    // None unless it's the courier's tile or one next to it
+    fn clicked_direction(&self, mouse: Option<(i32, i32)>) -> Option<(i32, i32)> {
+        let (x, y) = self.viewer.main_view.tile_at(mouse?)?;
+        let player = &self.couriers[self.active].player;
+        let direction = (x - player.x(), y - player.y());
+        if direction.0.abs() > 1 || direction.1.abs() > 1 {
+            return None;
+        }
+        Some(direction)
+    }
+
+    // describes the tile under the mouse under the map
+    fn update_hover(&mut self) {
+        let hover = self
+            .mouse
+            .and_then(|mouse| self.viewer.main_view.tile_at(mouse))
+            .map(|tile| {
+                let player = &self.couriers[self.active].player;
+                self.lookmode.describe_tile(&self.obs_table, player, tile)
+            })
+            .filter(|description| !description.is_empty());
+
+        if hover != self.viewer.main_view.hover {
+            self.viewer.main_view.hover = hover;
+            self.redraw = true;
+        }
+    }
+
+    // look around without moving, until the next move
+    fn pan_camera(&mut self, dx: i32, dy: i32) {
+        let step = 4;
+        let map_size = (self.obs_table.width(), self.obs_table.height());
+        if let Some(camera) = self.camera(self.active) {
+            camera.pan(dx * step, dy * step, map_size);
+        }
+        self.redraw = true;
+    }
+
+    // the camera following courier index, each has their own when the
+    // screen is split. Online only this machine's courier has one
+    fn camera(&mut self, index: usize) -> Option<&mut Camera> {
+        match self.online_courier {
+            Some(courier) if courier == index => Some(&mut self.viewer.main_view.camera),
+            Some(_) => None,
+            None if self.couriers.len() > 1 => Some(self.viewer.main_view.split_camera(index)),
+            None => Some(&mut self.viewer.main_view.camera),
+        }
+    }
+
+    // the courier acts, then every opponent whose turn comes up
+    // before the next courier's
+    fn play_turn(&mut self, action: HumanAction) {
+        let index = self.active;
+        if let Some(camera) = self.camera(index) {
+            camera.reset_pan();
+        }
+        // the map moves under the mouse, so wait for it to move too
+        self.viewer.main_view.hover = None;
+        // it is always a courier's turn while we wait for input,
+        // so advance the clock to it
+        self.turns.next_turn();
+        self.process_move_human(action);
+        self.turns
+            .schedule_after_move((index, PlayerType::Human), self.couriers[index].player.speed);
+
+        self.update_blocked();
+
+        // faster skaters may move more than once
+        while let Some((index, PlayerType::AI)) = self.turns.peek() {
+            self.turns.next_turn();
+            self.process_ai(index);
+            self.turns
+                .schedule_after_move((index, PlayerType::AI), self.opponents[index].player.speed);
+
+            self.update_blocked();
+        }
+
+        self.update_visibility();
+        self.follow_couriers();
+        self.check_run_over();
+    }
+
+    // every camera catches up with its courier once the turn is played,
+    // drawing only looks through them
+    fn follow_couriers(&mut self) {
+        for index in 0..self.couriers.len() {
+            let player = self.couriers[index].player;
+            if let Some(camera) = self.camera(index) {
+                camera.follow(player.xy(), player.speed);
+            }
+        }
+    }
+
+    // the courier's command, played straight away on this machine. Online
+    // it goes to the server and is played once it comes back (see poll_online)
+    fn take_turn(&mut self, command: Command) {
+        let mut client = match self.online.take() {
+            None => return self.play_command(command),
+            Some(client) => client,
+        };
+        if !client.can_move(self) {
+            let message = match client.courier() {
+                None => "Waiting for the other couriers to join".to_string(),
+                // their last move hasn't come back yet
+                Some(courier) if courier == self.moving_courier() => {
+                    "Waiting for the server".to_string()
+                }
+                Some(_) => format!("Waiting for Courier {}", self.moving_courier() + 1),
+            };
+            self.viewer.main_view.add_message(MessageKind::System, message);
+            self.redraw = true;
+        } else if let Err(error) = client.send(command) {
+            return self.leave_online(error);
+        }
+        self.online = Some(client);
+    }
+
+    fn play_command(&mut self, command: Command) {
+        match command {
+            Command::Move(direction, strength) => {
+                self.play_turn(HumanAction::Move(direction, strength))
+            }
+            Command::Trick(trick) => self.play_turn(HumanAction::Trick(trick)),
+            Command::Chat => self.set_state(ProcState::Chat),
+        }
+    }
+
+    // plays the turns the server has sent since the last frame
+    fn poll_online(&mut self) {
+        let mut client = match self.online.take() {
+            None => return,
+            Some(client) => client,
+        };
+        let events = match client.poll(self) {
+            Ok(events) => events,
+            Err(error) => return self.leave_online(error),
+        };
+        self.online = Some(client);
+
+        for event in events {
+            match event {
+                ClientEvent::Started { courier } => {
+                    self.online_courier = Some(courier);
+                    self.viewer.main_view.clear_log();
+                    self.viewer.main_view.add_message(
+                        MessageKind::System,
+                        format!("The run has started, you are Courier {}", courier + 1),
+                    );
+                    self.update_visibility();
+                    let xy = self.couriers[courier].player.xy();
+                    self.viewer.main_view.camera.snap_to(xy);
+                }
+                ClientEvent::Turn { .. } => {}
+            }
+            self.redraw = true;
+        }
+    }
+
+    // back to the menu with a local run, the networked one can't go on here
+    fn leave_online(&mut self, error: NetError) {
+        self.online = None;
+        self.online_courier = None;
+        self.lockstep = false;
+        self.reset_game();
+        self.viewer.main_view.add_message(
+            MessageKind::System,
+            format!("Left the networked run: {}", error.description()),
+        );
+        self.set_state(ProcState::MainMenu);
+    }
+
+    // the courier whose turn it is. Between turns that is the courier who
+    // just moved, until the results of their move are in
+    fn moving_courier(&self) -> usize {
+        match self.turns.peek() {
+            Some((index, PlayerType::Human)) => index,
+            _ => self.active,
+        }
+    }
+
+    // the courier the screen is about: the one to move when the couriers
+    // share the screen, online always this machine's courier
+    fn viewed_courier(&self) -> usize {
+        match self.online_courier {
+            Some(courier) if courier < self.couriers.len() => courier,
+            _ => self.moving_courier(),
+        }
+    }
+
+    // whose keys are read, online there's one player per machine
+    fn courier_keys(&self) -> usize {
+        match self.online {
+            Some(_) => 0,
+            None => self.active,
+        }
+    }
+
+    fn actor_player(&self, actor: (usize, PlayerType)) -> Player {
+        match actor.1 {
+            PlayerType::Human => self.couriers[actor.0].player,
+            PlayerType::AI => self.opponents[actor.0].player,
+        }
+    }
+
+    fn update_blocked(&mut self) {
+        let humans: Vec<Player> = self.couriers.iter().map(|c| c.player).collect();
+        collision::update_blocked(
+            &mut self.obs_table,
+            &humans,
+            &self.opponents,
+            &self.waiting_to_respawn_idx,
+        );
+    }
+
+    // a message for courier index, saying who it's for when they share the
+    // screen. Online the other couriers' messages are theirs to read
+    fn tell(&mut self, index: usize, kind: MessageKind, message: String) {
+        if self.online_courier.is_some_and(|courier| courier != index) {
+            return;
+        }
+        let message = self.addressed(index, message);
+        self.viewer.main_view.add_message(kind, message);
+    }
+
+    fn tell_colored(&mut self, index: usize, kind: MessageKind, message: String, color: RGB) {
+        if self.online_courier.is_some_and(|courier| courier != index) {
+            return;
+        }
+        let message = self.addressed(index, message);
+        self.viewer.main_view.add_colored_message(kind, message, color);
+    }
+
+    fn addressed(&self, index: usize, message: String) -> String {
+        if self.couriers.len() > 1 && self.online_courier.is_none() {
+            format!("Courier {}: {}", index + 1, message)
+        } else {
+            message
+        }
+    }
+
+    // ends the run once any courier reaches the mode's goal or limit,
+    // and keeps the HUD up to date until then
+    fn check_run_over(&mut self) {
+        let mode = self.modes[self.mode_idx];
+        self.viewer.main_view.run_status = mode.status(&self.couriers[self.viewed_courier()].stats);
+
+        if let Some(end) = self.couriers.iter().find_map(|c| mode.check(&c.stats)) {
+            self.run_end = Some(end);
+            self.record_run();
+            self.set_state(ProcState::GameOver);
+        }
     }
 
     fn process_ai(&mut self, index: usize) {
         if self.waiting_to_respawn_idx.contains(&(index as u32)) {
-            let mut rng = RandomNumberGenerator::new();
             let x = (self.obs_table.width() as i32 / 2)
-                + rng.range(
+                + self.rng.range(
                     -(self.obs_table.width() as i32) / 2 + 1,
                     self.obs_table.width() as i32 / 2,
                 )
                 - 1;
             let y = (self.obs_table.height() as i32 / 2)
-                + rng.range(
+                + self.rng.range(
                     -(self.obs_table.height() as i32) / 2 + 1,
                     self.obs_table.height() as i32 / 2 - 1,
                 );
@@ -588,6 +1638,9 @@ impl Game {
                 self.turns_to_giveup[index] = self.giveup_turns;
                 self.waiting_to_respawn_idx.remove(&(index as u32));
             }
+
+            // waiting to respawn still takes a turn
+            self.opponents[index].player.time += 1.0;
             return;
         }
 
@@ -597,6 +1650,7 @@ impl Game {
         let last_pos = self.opponents[index].player.position;
 
         self.opponents[index].move_player(&self.obs_table, &self.player_control);
+        self.resolve_collision((index, PlayerType::AI));
         let new_pos = self.opponents[index].player.position;
 
         if last_pos.0 == new_pos.0 && last_pos.1 == new_pos.1 {
@@ -608,7 +1662,7 @@ impl Game {
 
         if self.opponents[index]
             .reached_goal(5.0)
-            || self.turns_to_giveup[index] <= 0
+            || self.turns_to_giveup[index] == 0
         {
             self.opponents[index].choose_goal(&self.obs_table, self.ai_sight_radius);
             self.turns_to_giveup[index] = self.giveup_turns;
@@ -623,24 +1677,53 @@ impl Game {
         self.redraw = true;
     }
 
-    fn process_move_human(&mut self, keycode: VirtualKeyCode) {
-        // move player according to the key pressed
-        let result = self
-            .player_control
-            .move_player(&self.obs_table, &self.player, keycode);
-        self.player = result;
+    fn process_move_human(&mut self, action: HumanAction) {
+        let index = self.active;
+        let player = self.couriers[index].player;
+        // move the courier according to the key pressed
+        let result = match action {
+            HumanAction::Move(direction, strength) => self.player_control.move_player_analog(
+                &self.obs_table,
+                &player,
+                direction,
+                strength,
+            ),
+            HumanAction::Trick(trick) => {
+                self.player_control
+                    .perform_trick(&self.obs_table, &player, trick)
+            }
+        };
+        let courier = &mut self.couriers[index];
+        courier.stats.record_move(&player, &result);
+        courier.player = result;
+
+        match result.recent_event {
+            PlayerEvent::Ollie => {
+                self.tell(index, MessageKind::System, "Ollie!".to_string());
+            }
+            PlayerEvent::Bail => {
+                self.tell(index, MessageKind::Fall, "You bailed".to_string());
+            }
+            PlayerEvent::FallOver => {
+                self.tell(index, MessageKind::Fall, "You fell over".to_string());
+            }
+            _ => {}
+        }
+        self.resolve_collision((index, PlayerType::Human));
 
-        // check if we reached a goal
-        if self.goal_table.at_goal(self.player.xy())
-            && !self.goal_table.picked_up.contains(&self.player.xy())
+        // check if we reached a job, one package at a time
+        let player = self.couriers[index].player;
+        if self.couriers[index].package.is_none()
+            && self.goal_table.at_goal(player.xy())
+            && !self.goal_table.picked_up.contains(&player.xy())
         {
-            self.set_state(ProcState::GotPackage(self.player.x(), self.player.y()));
+            self.set_state(ProcState::GotPackage(player.x(), player.y()));
         }
 
         // check if move player returned a player with a Respawn event
         // and respawn them
-        if self.player.recent_event == PlayerEvent::Respawn {
-            self.reset_player_continue();
+        if player.recent_event == PlayerEvent::Respawn {
+            self.reset_player_continue(index);
         }
         // go to the state where we update the message log
         // after computing the result of the turn
@@ -652,6 +1735,78 @@ impl Game {
         }
     }
 
+    // if actor ran into another skater this turn, work out what happened to both
+    // of them and tell the couriers involved about it
+    fn resolve_collision(&mut self, actor: (usize, PlayerType)) {
+        let mover = self.actor_player(actor);
+
+        let at = match mover.recent_event {
+            PlayerEvent::Collide(x, y) => (x, y),
+            _ => return,
+        };
+
+        let struck_actor = match self.couriers.iter().position(|c| c.player.xy() == at) {
+            Some(index) => (index, PlayerType::Human),
+            None => {
+                let found = self.opponents.iter().enumerate().position(|(index, ai)| {
+                    ai.player.xy() == at && !self.waiting_to_respawn_idx.contains(&(index as u32))
+                });
+                match found {
+                    None => return,
+                    Some(index) => (index, PlayerType::AI),
+                }
+            }
+        };
+
+        let struck = self.actor_player(struck_actor);
+
+        let (mover, struck) =
+            collision::resolve(&self.obs_table, &mover, &struck, &self.player_control);
+
+        for (who, player) in [(actor, mover), (struck_actor, struck)] {
+            match who.1 {
+                PlayerType::Human => {
+                    let courier = &mut self.couriers[who.0];
+                    if let PlayerEvent::FallOver | PlayerEvent::KnockedOver = player.recent_event {
+                        courier.stats.record_fall();
+                    }
+                    courier.player = player;
+
+                    // the courier's own move handles respawning after it resolves
+                    if who != actor && player.recent_event == PlayerEvent::Respawn {
+                        self.reset_player_continue(who.0);
+                    }
+                }
+                PlayerType::AI => {
+                    self.opponents[who.0].player = player;
+                    if player.recent_event == PlayerEvent::Respawn {
+                        self.waiting_to_respawn_idx.insert(who.0 as u32);
+                    }
+                }
+            }
+        }
+
+        if actor.1 == PlayerType::Human {
+            let message = match (mover.recent_event, struck.recent_event) {
+                (PlayerEvent::Bumped, PlayerEvent::KnockedOver) => "You knocked a skater over",
+                // knocked off a rail with nowhere to land
+                (PlayerEvent::Bumped, PlayerEvent::Respawn) => "You knocked a skater off the rail",
+                (PlayerEvent::Bumped, _) => "You bumped into a skater",
+                _ => "You crashed into a skater and fell",
+            };
+            self.tell(actor.0, MessageKind::Fall, message.to_string());
+        }
+        if struck_actor.1 == PlayerType::Human {
+            let message = match struck.recent_event {
+                PlayerEvent::Bumped => "A skater bumped into you",
+                _ => "A skater knocked you over",
+            };
+            self.tell(struck_actor.0, MessageKind::Fall, message.to_string());
+        }
+
+        self.redraw = true;
+    }
+
     // dummy state for the purposes of updating the view after process_move
     fn process_post_move(&mut self) -> bool {
         self.set_state(ProcState::Playing);
@@ -659,11 +1814,9 @@ impl Game {
         return true;
     }
 
-    fn process_lookmode(&mut self, _ctx: &mut rltk::Rltk) -> bool {
-        self.viewer.main_view.add_string(
-            String::clone(&self.lookmode_string.0),
-            self.lookmode_string.1,
-        );
+    fn process_lookmode(&mut self, _key: Option<VirtualKeyCode>) -> bool {
+        let (message, color) = self.couriers[self.active].lookmode_string.clone();
+        self.tell_colored(self.active, MessageKind::Hint, message, color);
 
         self.set_state(ProcState::Playing);
 
@@ -671,20 +1824,23 @@ impl Game {
     }
 
     fn process_got_package(&mut self, x: i32, y: i32) -> bool {
-        if let Some(idx_color) = self.goal_table.goals.get(&(x, y)) {
-            self.recipient_idx = idx_color.0 as i32;
-            self.viewer.main_view.add_string(
+        if let Some(idx_color) = self.goal_table.goals.get(&(x, y)).copied() {
+            self.tell_colored(
+                self.active,
+                MessageKind::Delivery,
                 String::from("Picked up package, find the skater wearing this color shirt"),
                 idx_color.1,
             );
 
-            self.lookmode_string = (
+            let courier = &mut self.couriers[self.active];
+            courier.package = Some((x, y));
+            courier.lookmode_string = (
                 "Find the skater wearing this color shirt".to_string(),
                 idx_color.1,
             );
+            courier.player.time = 0.0;
 
             self.goal_table.picked_up.insert((x, y));
-            self.player.time = 0.0;
         }
 
         self.set_state(ProcState::Playing);
@@ -698,59 +1854,168 @@ impl Game {
         self.redraw = true;
     }
 
+    // adds the run that just ended to the high scores and saves them.
+    // Couriers in a shared run only get ranked against each other
+    fn record_run(&mut self) {
+        for courier in self.couriers.iter_mut() {
+            courier.stats.score = courier.score;
+        }
+        let mut standings: Vec<(usize, RunStats)> = self
+            .couriers
+            .iter()
+            .enumerate()
+            .map(|(index, courier)| (index, courier.stats))
+            .collect();
+        standings.sort_by_key(|(_, stats)| std::cmp::Reverse((stats.score, stats.delivered)));
+        self.last_run = standings[0].1;
+        self.standings = standings;
+
+        if self.is_shared_run() {
+            self.new_high_score = None;
+            return;
+        }
+
+        self.new_high_score = if self.last_run.is_empty() {
+            None
+        } else {
+            self.high_scores.add(self.last_run)
+        };
+
+        if self.new_high_score.is_some() {
+            storage::save(HIGH_SCORES_FILE, &self.high_scores.to_config());
+        }
+
+        // the payout is the player's to spend in the shop
+        self.progress.end_run(self.last_run.score);
+        storage::save(PROGRESS_FILE, &self.progress.to_config());
+        self.fit_board();
+    }
+
+    // a new seed every run, kept with the run's stats
     fn reset_game(&mut self) {
-        self.lookmode_string = ("Find the package".to_string(), RGB::named(rltk::WHITE));
+        self.reset_game_seeded(RandomNumberGenerator::new().next_u64());
+    }
+
+    // the same seed and settings always make the same run
+    fn reset_game_seeded(&mut self, seed: u64) {
+        self.rng = RandomNumberGenerator::seeded(seed);
 
-        self.obs_table.revealed = vec![false; self.obs_table.width() as usize * self.obs_table.height() as usize];
         self.obs_table.regen_table();
+        self.obs_table.reset_visibility();
 
-        map_gen::voronoi_mapgen(&mut self.obs_table);
+        map_gen::voronoi_mapgen(&mut self.obs_table, &mut self.rng);
         map_gen::tunnel_pockets(&mut self.obs_table, &mut self.rng);
 
         self.opponents.clear();
         self.turns_to_giveup.clear();
-        for _ in 0..self.n_opponents {
+        // one more recipient than couriers, so there's always a new job to hand out
+        for _ in 0..self.n_opponents.max(self.n_couriers as u32 + 1) {
             self.add_opponent_platform();
         }
 
+        // a job for every courier to race for
         self.goal_table.clear();
-        let mut aiidx = self.rng.range(0, self.opponents.len()) as i32;
-        while aiidx == self.recipient_idx {
-            aiidx = self.rng.range(0, self.opponents.len() as i32);
+        for _ in 0..self.n_couriers {
+            self.add_job(None);
         }
-        self.recipient_idx = aiidx;
 
-        let coloridx = self.rng.range(0, self.shirt_colors.len());
-        self.goal_table.add_goal(
-            spawning::random_platform(&self.obs_table, &mut self.rng),
-            (aiidx as usize, self.shirt_colors[coloridx]),
-        );
-
-        let (x, y) = spawning::tunnel_spawn(&mut self.obs_table, &mut self.rng);
+        let mut spawns: Vec<(i32, i32)> = Vec::new();
+        for _ in 0..self.n_couriers {
+            let mut spawn = spawning::tunnel_spawn(&mut self.obs_table, &mut self.rng);
+            let mut tries = 0;
+            while spawns.contains(&spawn) && tries < 10 {
+                spawn = spawning::tunnel_spawn(&mut self.obs_table, &mut self.rng);
+                tries += 1;
+            }
+            spawns.push(spawn);
+        }
 
         self.obs_table.update_platforms();
         self.obs_table.compute_unions();
+        map_gen::place_surfaces(&mut self.obs_table, &mut self.rng);
+
+        self.use_mode_physics();
+
+        let size = (self.obs_table.width(), self.obs_table.height());
+        let mut couriers = Vec::new();
+        for (index, (x, y)) in spawns.into_iter().enumerate() {
+            let last = match self.couriers.get(index) {
+                Some(courier) => courier.player,
+                None => Player::new(x, y),
+            };
+            let mut player = PlayerController::reset_player_gameover(&self.obs_table, &last, x, y);
+            player.profile = self.profile_idx;
+            couriers.push(Courier::new(player, RunStats::new(seed, size)));
+        }
+        self.couriers = couriers;
+        self.active = 0;
+        self.fit_board();
+
+        self.viewer.main_view.run_status = self.modes[self.mode_idx].status(&self.couriers[0].stats);
+        for index in 0..self.couriers.len() {
+            let xy = self.couriers[index].player.xy();
+            if let Some(camera) = self.camera(index) {
+                camera.snap_to(xy);
+            }
+        }
 
-        self.player = PlayerController::reset_player_gameover(&self.obs_table, &self.player, x, y);
+        // the couriers move first in order, then the opponents in the order they were added
+        self.turns.clear();
+        for index in 0..self.couriers.len() {
+            self.turns.schedule((index, PlayerType::Human), 0);
+        }
+        for index in 0..self.opponents.len() {
+            self.turns.schedule((index, PlayerType::AI), 0);
+        }
 
-        collision::update_blocked(
-            &mut self.obs_table,
-            &self.player,
-            &self.opponents,
-            &self.waiting_to_respawn_idx,
-        );
+        self.update_blocked();
+        self.update_visibility();
     }
 
-    fn reset_player_continue(&mut self) {
+    fn reset_player_continue(&mut self, index: usize) {
         let spawn_at = spawning::random_platform(&self.obs_table, &mut self.rng);
-        self.player = PlayerController::reset_player_continue(
+        let courier = &mut self.couriers[index];
+        let before = courier.player.time;
+        courier.player = PlayerController::reset_player_continue(
             &self.obs_table,
-            &self.player,
+            &courier.player,
             spawn_at.0,
             spawn_at.1,
         );
+        courier.stats.add_time(courier.player.time - before);
+        let xy = courier.player.xy();
+        if let Some(camera) = self.camera(index) {
+            camera.snap_to(xy);
+        }
+        self.update_visibility();
         self.redraw = true;
     }
+
+    // what the couriers can see now, and what the one on
+    // screen remembers having seen
+    fn update_visibility(&mut self) {
+        let turn = self.viewed_courier();
+        let mut viewers = vec![self.couriers[turn].player.xy()];
+        for (index, courier) in self.couriers.iter().enumerate() {
+            if index != turn {
+                viewers.push(courier.player.xy());
+            }
+        }
+        visibility::update_shared_visibility(
+            &mut self.obs_table,
+            &viewers,
+            self.sight_radius as i32,
+            self.turns.time(),
+        );
+    }
+}
+
+// where courier index's keys are saved
+fn bindings_file(index: usize) -> String {
+    match index {
+        0 => BINDINGS_FILE.to_string(),
+        _ => format!("bindings_courier{}.txt", index + 1),
+    }
 }
 
 fn vec_to_direction(x: i32, y: i32) -> Direction {
@@ -0,0 +1,36 @@
+use rltk::VirtualKeyCode;
+
+// What the player did since the last frame, from the keyboard and the mouse
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Input {
+    pub key: Option<VirtualKeyCode>,
+
+    // held with a movement key or a click for a gentler push
+    pub shift: bool,
+
+    // the cell under the mouse, None when it isn't over the screen
+    pub mouse: Option<(i32, i32)>,
+    pub click: bool,
+}
+
+impl Input {
+    pub fn from_key(key: Option<VirtualKeyCode>) -> Self {
+        Input {
+            key,
+            ..Input::default()
+        }
+    }
+
+    pub fn from_context(ctx: &rltk::BTerm) -> Self {
+        let (x, y) = ctx.mouse_pos();
+        let (width, height) = ctx.get_char_size();
+        let on_screen = x >= 0 && y >= 0 && x < width as i32 && y < height as i32;
+
+        Input {
+            key: ctx.key,
+            shift: ctx.shift,
+            mouse: if on_screen { Some((x, y)) } else { None },
+            click: on_screen && ctx.left_click,
+        }
+    }
+}
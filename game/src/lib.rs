@@ -0,0 +1,9 @@
+pub mod courier;
+pub mod game;
+pub mod input;
+pub mod online;
+pub mod raws;
+pub mod spawning;
+pub mod storage;
+#[cfg(feature = "terminal")]
+pub mod terminal;
@@ -1,8 +1,7 @@
-pub mod game;
-pub mod raws;
-pub mod spawning;
-
-use game::Game;
+use game::game::Game;
+use game::{online, raws};
+#[cfg(feature = "terminal")]
+use game::terminal;
 use rltk::BError;
 
 const TABLEWIDTH: u32 = 80;
@@ -11,7 +10,27 @@ const TABLEHEIGHT: u32 = 50;
 const WINDOWWIDTH: u32 = 80;
 const WINDOWHEIGHT: u32 = 50;
 
+// couriers in a hosted run when --couriers isn't given
+const SERVE_COURIERS: usize = 2;
+
 fn main() -> BError {
+    // --serve <port> [--couriers <n>] hosts a run without a window
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(port) = arg_value("--serve") {
+        let port = port.parse().map_err(|_| "--serve needs a port number")?;
+        let couriers = arg_value("--couriers")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(SERVE_COURIERS);
+        online::serve(load_game(), port, couriers).map_err(|e| e.description())?;
+        return Ok(());
+    }
+
+    #[cfg(feature = "terminal")]
+    if std::env::args().any(|arg| arg == "--terminal") {
+        terminal::main_loop(load_game(), WINDOWWIDTH, WINDOWHEIGHT)?;
+        return Ok(());
+    }
+
     use rltk::RltkBuilder;
     let try_context = RltkBuilder::simple80x50()
         .with_dimensions(WINDOWWIDTH * 2, WINDOWHEIGHT * 2)
@@ -30,5 +49,37 @@ fn main() -> BError {
 
 fn load_game() -> Game {
     raws::load_raws();
-    Game::new(TABLEWIDTH, TABLEHEIGHT)
+    let mut game = Game::new(TABLEWIDTH, TABLEHEIGHT);
+    if let Some(address) = join_address() {
+        let name = arg_value("--name").unwrap_or_default();
+        let joined = online::connect(&address).and_then(|c| game.join(c, &name));
+        if let Err(error) = joined {
+            println!("Couldn't join {}: {}", address, error.description());
+        }
+    }
+    game
+}
+
+// the value after name on the command line
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != name);
+    args.next()?;
+    args.next()
+}
+
+// --join <address> [--name <name>] plays a hosted run
+#[cfg(not(target_arch = "wasm32"))]
+fn join_address() -> Option<String> {
+    arg_value("--join")
+}
+
+// the page's ?join=<address>, the web build has no command line
+#[cfg(target_arch = "wasm32")]
+fn join_address() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("join="))
+        .map(|address| address.to_string())
 }
@@ -0,0 +1,90 @@
+// Networked runs, see net/. A server hosts one run for couriers on other
+// machines, each joining with a TCP connection from a native build or a
+// WebSocket from the browser. Hosting is native only, it needs to listen
+
+use net::connection::{Connection, NetError};
+use net::web_socket::WebSocketConnection;
+
+// a connection to the server at address, like localhost:7878 or
+// ws://localhost:7879. The web build can only use WebSockets
+#[cfg(not(target_arch = "wasm32"))]
+pub fn connect(address: &str) -> Result<Box<dyn Connection>, NetError> {
+    use net::tcp::TcpConnection;
+
+    if address.starts_with("ws://") {
+        return Ok(Box::new(WebSocketConnection::connect(address)?));
+    }
+    Ok(Box::new(TcpConnection::connect(address)?))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn connect(address: &str) -> Result<Box<dyn Connection>, NetError> {
+    let url = if address.starts_with("ws://") || address.starts_with("wss://") {
+        address.to_string()
+    } else {
+        format!("ws://{}", address)
+    };
+    Ok(Box::new(WebSocketConnection::connect(&url)?))
+}
+
+// hosts one run for couriers without a window of its own. Native couriers
+// connect on port, browsers on the port after it. Returns once the run is
+// over, or everyone who joined has left
+#[cfg(not(target_arch = "wasm32"))]
+pub fn serve(game: crate::game::Game, port: u16, couriers: usize) -> Result<(), NetError> {
+    use std::thread;
+    use std::time::Duration;
+
+    use net::listener::{Listener, Transport};
+    use net::server::{Server, ServerEvent};
+
+    let listeners = [
+        Listener::bind(("0.0.0.0", port), Transport::Tcp)?,
+        Listener::bind(("0.0.0.0", port + 1), Transport::WebSocket)?,
+    ];
+    let setup = game.run_setup(couriers);
+    let mut server = Server::new(game, setup);
+    println!(
+        "Hosting a run for {} couriers on port {} (WebSocket on {}), seed {}",
+        setup.couriers,
+        port,
+        port + 1,
+        setup.seed
+    );
+
+    loop {
+        for listener in listeners.iter() {
+            match listener.accept() {
+                Ok(Some(connection)) => server.add_connection(connection),
+                Ok(None) => {}
+                Err(error) => println!("A courier couldn't connect: {}", error.description()),
+            }
+        }
+
+        for event in server.poll() {
+            match event {
+                ServerEvent::Joined { name, courier } => {
+                    println!("{} joined as Courier {}", name, courier + 1)
+                }
+                ServerEvent::Refused { name, reason } => {
+                    println!("Refused {}: {}", name, reason)
+                }
+                ServerEvent::Started => println!("The run has started"),
+                ServerEvent::Turn { .. } => {}
+                ServerEvent::Desync { courier, turn } => {
+                    println!("Courier {} fell out of step on turn {}", courier + 1, turn)
+                }
+                ServerEvent::Left { name, courier } => match courier {
+                    Some(courier) => println!("{} (Courier {}) left", name, courier + 1),
+                    None => println!("{} left", name),
+                },
+                ServerEvent::RunOver => println!("The run is over"),
+            }
+        }
+
+        if server.is_over() || (server.is_started() && server.connected() == 0) {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+}
@@ -1,7 +1,15 @@
 rltk::embedded_resource!(GAME_CONFIG, "raws/game.txt");
 rltk::embedded_resource!(MODEL_CONFIG, "raws/model.txt");
+rltk::embedded_resource!(THEME_CONFIG, "raws/themes.txt");
+rltk::embedded_resource!(BINDINGS_CONFIG, "raws/bindings.txt");
+rltk::embedded_resource!(SKATERS_CONFIG, "raws/skaters.txt");
+rltk::embedded_resource!(SHOP_CONFIG, "raws/shop.txt");
 
 pub fn load_raws() {
     rltk::link_resource!(GAME_CONFIG, "raws/game.txt");
     rltk::link_resource!(MODEL_CONFIG, "raws/model.txt");
+    rltk::link_resource!(THEME_CONFIG, "raws/themes.txt");
+    rltk::link_resource!(BINDINGS_CONFIG, "raws/bindings.txt");
+    rltk::link_resource!(SKATERS_CONFIG, "raws/skaters.txt");
+    rltk::link_resource!(SHOP_CONFIG, "raws/shop.txt");
 }
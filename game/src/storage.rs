@@ -0,0 +1,54 @@
+// Small text files that outlive a run, like the high score table.
+// Native builds keep them in the user's data directory, the web build
+// in the browser's local storage. Failing to read or write is not an
+// error worth stopping the game for, so both just give up quietly.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn data_dir() -> PathBuf {
+        let base = match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => match std::env::var_os("HOME") {
+                Some(home) => PathBuf::from(home).join(".local").join("share"),
+                None => PathBuf::from("."),
+            },
+        };
+        base.join("dangerous_deliveries")
+    }
+
+    pub fn load(name: &str) -> Option<String> {
+        fs::read_to_string(data_dir().join(name)).ok()
+    }
+
+    pub fn save(name: &str, contents: &str) -> bool {
+        let dir = data_dir();
+        fs::create_dir_all(&dir).is_ok() && fs::write(dir.join(name), contents).is_ok()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    fn key(name: &str) -> String {
+        format!("dangerous_deliveries/{}", name)
+    }
+
+    pub fn load(name: &str) -> Option<String> {
+        local_storage()?.get_item(&key(name)).ok()?
+    }
+
+    pub fn save(name: &str, contents: &str) -> bool {
+        match local_storage() {
+            None => false,
+            Some(storage) => storage.set_item(&key(name), contents).is_ok(),
+        }
+    }
+}
+
+pub use backend::{load, save};
@@ -0,0 +1,134 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+};
+use crossterm::{cursor, execute, terminal};
+use rltk::VirtualKeyCode;
+
+use view::terminal_canvas::TerminalCanvas;
+
+use crate::game::Game;
+use crate::input::Input;
+
+// how often the game is updated while no key is pressed,
+// rltk ticks every frame so the game expects it
+const TICK: Duration = Duration::from_millis(33);
+
+// Plays the game in the terminal it was started from instead of a window,
+// so it can be played over SSH. Returns when the player quits or presses Ctrl+C
+pub fn main_loop(mut game: Game, width: u32, height: u32) -> io::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(
+        out,
+        terminal::EnterAlternateScreen,
+        cursor::Hide,
+        event::EnableMouseCapture
+    )?;
+
+    let result = run(&mut game, &mut out, width, height);
+
+    execute!(
+        out,
+        event::DisableMouseCapture,
+        cursor::Show,
+        terminal::LeaveAlternateScreen
+    )?;
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+fn run<W: Write>(game: &mut Game, out: &mut W, width: u32, height: u32) -> io::Result<()> {
+    let mut canvas = TerminalCanvas::new(width, height);
+    // the terminal only says where the mouse is when it moves
+    let mut mouse = None;
+
+    loop {
+        let mut input = Input::default();
+        if event::poll(TICK)? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                    ..
+                }) => return Ok(()),
+                Event::Key(press) if press.kind == KeyEventKind::Press => {
+                    input.key = to_virtual_key(press.code);
+                    input.shift = press.modifiers.contains(KeyModifiers::SHIFT)
+                        || matches!(press.code, KeyCode::Char(c) if c.is_ascii_uppercase());
+                }
+                Event::Mouse(action) => {
+                    mouse = Some((action.column as i32, action.row as i32));
+                    input.shift = action.modifiers.contains(KeyModifiers::SHIFT);
+                    input.click = action.kind == MouseEventKind::Down(MouseButton::Left);
+                }
+                Event::Resize(..) => {
+                    execute!(out, terminal::Clear(terminal::ClearType::All))?;
+                    canvas.invalidate();
+                }
+                _ => {}
+            }
+        }
+
+        input.mouse = mouse;
+        if !game.frame(input, &mut canvas) {
+            return Ok(());
+        }
+        canvas.present(out)?;
+    }
+}
+
+fn to_virtual_key(code: KeyCode) -> Option<VirtualKeyCode> {
+    match code {
+        KeyCode::Esc => Some(VirtualKeyCode::Escape),
+        KeyCode::Enter => Some(VirtualKeyCode::Return),
+        KeyCode::Tab => Some(VirtualKeyCode::Tab),
+        KeyCode::Char(c) => match c.to_ascii_lowercase() {
+            'a' => Some(VirtualKeyCode::A),
+            'b' => Some(VirtualKeyCode::B),
+            'c' => Some(VirtualKeyCode::C),
+            'd' => Some(VirtualKeyCode::D),
+            'e' => Some(VirtualKeyCode::E),
+            'f' => Some(VirtualKeyCode::F),
+            'g' => Some(VirtualKeyCode::G),
+            'h' => Some(VirtualKeyCode::H),
+            'i' => Some(VirtualKeyCode::I),
+            'j' => Some(VirtualKeyCode::J),
+            'k' => Some(VirtualKeyCode::K),
+            'l' => Some(VirtualKeyCode::L),
+            'm' => Some(VirtualKeyCode::M),
+            'n' => Some(VirtualKeyCode::N),
+            'o' => Some(VirtualKeyCode::O),
+            'p' => Some(VirtualKeyCode::P),
+            'q' => Some(VirtualKeyCode::Q),
+            'r' => Some(VirtualKeyCode::R),
+            's' => Some(VirtualKeyCode::S),
+            't' => Some(VirtualKeyCode::T),
+            'u' => Some(VirtualKeyCode::U),
+            'v' => Some(VirtualKeyCode::V),
+            'w' => Some(VirtualKeyCode::W),
+            'x' => Some(VirtualKeyCode::X),
+            'y' => Some(VirtualKeyCode::Y),
+            'z' => Some(VirtualKeyCode::Z),
+            '0' => Some(VirtualKeyCode::Key0),
+            '1' => Some(VirtualKeyCode::Key1),
+            '2' => Some(VirtualKeyCode::Key2),
+            '3' => Some(VirtualKeyCode::Key3),
+            '4' => Some(VirtualKeyCode::Key4),
+            '5' => Some(VirtualKeyCode::Key5),
+            '6' => Some(VirtualKeyCode::Key6),
+            '7' => Some(VirtualKeyCode::Key7),
+            '8' => Some(VirtualKeyCode::Key8),
+            '9' => Some(VirtualKeyCode::Key9),
+            '.' => Some(VirtualKeyCode::Period),
+            ',' => Some(VirtualKeyCode::Comma),
+            ';' => Some(VirtualKeyCode::Semicolon),
+            ' ' => Some(VirtualKeyCode::Space),
+            _ => None,
+        },
+        _ => None,
+    }
+}
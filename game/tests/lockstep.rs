@@ -0,0 +1,104 @@
+// Copies of the game on different machines only ever share a seed and the
+// couriers' commands, so the same ones have to play out the same way
+
+use game::game::Game;
+use game::raws;
+use model::trick::Trick;
+use net::lockstep::Lockstep;
+use net::protocol::{Command, RunSetup};
+use rltk::RandomNumberGenerator;
+
+const TURNS: usize = 400;
+
+fn setup(seed: u64, couriers: usize) -> RunSetup {
+    RunSetup {
+        seed,
+        couriers,
+        mode: 0,
+        skater: 0,
+    }
+}
+
+// a copy built from scratch, as it would be on another machine
+fn started(setup: &RunSetup) -> Game {
+    raws::load_raws();
+    let mut game = Game::new(80, 50);
+    game.start(setup);
+    game
+}
+
+// mostly pushes, with some tricks and chats in between
+fn commands(seed: u64) -> Vec<Command> {
+    let mut rng = RandomNumberGenerator::seeded(seed);
+    (0..TURNS)
+        .map(|_| match rng.range(0, 10) {
+            0 => Command::Trick(Trick::Ollie),
+            1 => Command::Trick(Trick::Manual),
+            2 => Command::Chat,
+            _ => Command::Move(
+                (rng.range(-1, 2), rng.range(-1, 2)),
+                [0.5, 1.0][rng.range(0, 2) as usize],
+            ),
+        })
+        .collect()
+}
+
+// plays commands on every copy in turn, checking they agree after each
+// turn. Returns how many turns were played before the run ended
+fn play_in_step(copies: &mut [Game], commands: &[Command]) -> usize {
+    let mut played = 0;
+    for command in commands.iter() {
+        let courier = match copies[0].turn_courier() {
+            None => break,
+            Some(courier) => courier,
+        };
+        for copy in copies.iter_mut() {
+            assert_eq!(copy.turn_courier(), Some(courier), "turn {}", played);
+            copy.apply(courier, *command);
+        }
+        let sum = copies[0].checksum();
+        for copy in copies[1..].iter() {
+            assert_eq!(copy.checksum(), sum, "turn {}", played);
+        }
+        played += 1;
+    }
+    played
+}
+
+#[test]
+fn copies_with_the_same_seed_start_the_same() {
+    for couriers in 1..=4 {
+        let setup = setup(77, couriers);
+        assert_eq!(started(&setup).checksum(), started(&setup).checksum());
+    }
+}
+
+#[test]
+fn copies_fed_the_same_commands_stay_in_step() {
+    for (seed, couriers) in [(1, 1), (2024, 2), (99, 3), (31337, 4)] {
+        let setup = setup(seed, couriers);
+        let mut copies = [started(&setup), started(&setup), started(&setup)];
+        let played = play_in_step(&mut copies, &commands(seed));
+        assert!(played > TURNS / 2, "seed {} ended after {} turns", seed, played);
+    }
+}
+
+#[test]
+fn different_commands_are_caught_by_the_checksum() {
+    let setup = setup(5, 2);
+    let mut copies = [started(&setup), started(&setup)];
+    let streams = [commands(5), commands(6)];
+    let mut caught = false;
+    for turn in 0..TURNS {
+        for (copy, stream) in copies.iter_mut().zip(streams.iter()) {
+            if let Some(courier) = copy.turn_courier() {
+                copy.apply(courier, stream[turn]);
+            }
+        }
+        if copies[0].checksum() != copies[1].checksum() {
+            caught = true;
+            break;
+        }
+    }
+    assert!(caught);
+}
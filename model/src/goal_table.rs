@@ -41,6 +41,15 @@ impl GoalTable {
         return false;
     }
 
+    // the closest package to from that nobody has picked up yet
+    pub fn nearest_job(&self, (x, y): (i32, i32)) -> Option<(i32, i32)> {
+        self.goals
+            .keys()
+            .filter(|pos| !self.picked_up.contains(pos))
+            .min_by_key(|(gx, gy)| ((gx - x).pow(2) + (gy - y).pow(2), (*gx, *gy)))
+            .copied()
+    }
+
     pub fn remove_goal_index(&mut self, index: usize) -> bool {
         if let Some(entry) = self.index_map.remove(&index) {
             match self.goals.remove(&entry) {
@@ -0,0 +1,102 @@
+use super::run_stats::RunStats;
+
+// The best runs so far, best first. Saved as text, one run per line:
+// "run <score> <delivered> <time> <falls> <distance> <seed> <width> <height>"
+pub struct HighScores {
+    pub runs: Vec<RunStats>,
+    max_runs: usize,
+}
+
+impl HighScores {
+    pub fn new(max_runs: usize) -> Self {
+        HighScores {
+            runs: Vec::new(),
+            max_runs,
+        }
+    }
+}
+
+impl HighScores {
+    // keeps run if it makes the table and returns its place, 0 is the best.
+    // Ties go below the runs already in the table
+    pub fn add(&mut self, run: RunStats) -> Option<usize> {
+        let rank = self
+            .runs
+            .iter()
+            .position(|r| better(&run, r))
+            .unwrap_or(self.runs.len());
+        if rank >= self.max_runs {
+            return None;
+        }
+
+        self.runs.insert(rank, run);
+        self.runs.truncate(self.max_runs);
+        Some(rank)
+    }
+
+    pub fn best(&self) -> Option<&RunStats> {
+        self.runs.first()
+    }
+
+    pub fn to_config(&self) -> String {
+        let mut out = String::from(
+            "# run <score> <delivered> <time> <falls> <distance> <seed> <width> <height>\n",
+        );
+        for r in self.runs.iter() {
+            out.push_str(&format!(
+                "run {} {} {} {} {} {} {} {}\n",
+                r.score,
+                r.delivered,
+                r.time,
+                r.n_falls,
+                r.distance,
+                r.seed,
+                r.map_size.0,
+                r.map_size.1
+            ));
+        }
+        out
+    }
+
+    // lines that don't parse are skipped, so a damaged file
+    // loses those runs instead of the whole table
+    pub fn from_config(raw_string: &str, max_runs: usize) -> HighScores {
+        let mut scores = HighScores::new(max_runs);
+
+        for line in raw_string.lines() {
+            let words: Vec<&str> = line.split_ascii_whitespace().collect();
+            if words.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if words[0] == "run" {
+                if let Some(run) = parse_run(&words[1..]) {
+                    scores.add(run);
+                }
+            }
+        }
+
+        scores
+    }
+}
+
+// more money first, then more deliveries, then the faster run
+fn better(a: &RunStats, b: &RunStats) -> bool {
+    (a.score, a.delivered) > (b.score, b.delivered)
+        || ((a.score, a.delivered) == (b.score, b.delivered) && a.time < b.time)
+}
+
+fn parse_run(words: &[&str]) -> Option<RunStats> {
+    if words.len() < 8 {
+        return None;
+    }
+    Some(RunStats {
+        score: words[0].parse().ok()?,
+        delivered: words[1].parse().ok()?,
+        time: words[2].parse().ok()?,
+        n_falls: words[3].parse().ok()?,
+        distance: words[4].parse().ok()?,
+        seed: words[5].parse().ok()?,
+        map_size: (words[6].parse().ok()?, words[7].parse().ok()?),
+    })
+}
@@ -1,11 +1,16 @@
 pub mod player;
 pub mod player_event;
+pub mod trick;
 
 pub mod obstacle;
 pub mod obstacle_automata;
 pub mod obstacle_table;
+pub mod surface;
 
 pub mod goal_table;
+pub mod high_scores;
+pub mod run_mode;
+pub mod run_stats;
 
 pub mod direction;
 pub mod traversability;
@@ -14,4 +19,5 @@ pub mod state;
 
 pub mod map_gen;
 
+pub mod memory;
 pub mod visibility;
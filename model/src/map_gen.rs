@@ -8,6 +8,7 @@ use util::{
 
 use crate::{
     goal_table::GoalTable, obstacle::Obstacle, obstacle_automata, obstacle_table::ObstacleTable,
+    surface::Surface,
 };
 
 // will loop infinitely if the platform density of table is too low
@@ -130,8 +131,11 @@ pub fn tunnel_position(table: &mut ObstacleTable, (x, y): (i32, i32), rng: &mut
 
 // tunnels into the nearest open space
 pub fn tunnel_goals(table: &mut ObstacleTable, goals: &GoalTable, rng: &mut RandomNumberGenerator) {
-    for goal in goals.goals.keys() {
-        tunnel_position(table, *goal, rng);
+    // in a fixed order, so the rng is drawn from the same way every time
+    let mut positions: Vec<(i32, i32)> = goals.goals.keys().copied().collect();
+    positions.sort();
+    for goal in positions {
+        tunnel_position(table, goal, rng);
     }
 }
 
@@ -165,7 +169,7 @@ pub fn tunnel_pockets(table: &mut ObstacleTable, rng: &mut RandomNumberGenerator
     table.compute_unions();
 }
 
-pub fn voronoi_mapgen(obs_table: &mut ObstacleTable) {
+pub fn voronoi_mapgen(obs_table: &mut ObstacleTable, rng: &mut RandomNumberGenerator) {
     let a = obs_table.width() / 6;
     let b = obs_table.height() / 6;
 
@@ -179,6 +183,7 @@ pub fn voronoi_mapgen(obs_table: &mut ObstacleTable) {
         a as usize * b as usize,
         obs_table.width(),
         obs_table.height(),
+        rng,
     );
     for _ in 0..1 {
         apply_voronoi(obs_table, &seeds);
@@ -190,6 +195,70 @@ pub fn voronoi_mapgen(obs_table: &mut ObstacleTable) {
     obs_table.compute_unions();
 }
 
+// scatters patches of gravel and wet ground over the platforms
+// and lays short ramps along open stretches
+pub fn place_surfaces(table: &mut ObstacleTable, rng: &mut RandomNumberGenerator) {
+    let n_patches = table.platforms.len() / 120;
+    for _ in 0..n_patches {
+        place_patch(table, Surface::Gravel, rng);
+        place_patch(table, Surface::Wet, rng);
+    }
+
+    let directions = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+    let ramp_length = 3;
+    let n_ramps = table.platforms.len() / 200;
+    for _ in 0..n_ramps {
+        let start = match rng.random_slice_entry(&table.platforms) {
+            None => return,
+            Some(start) => *start,
+        };
+        let dir = directions[rng.range(0, directions.len())];
+
+        let ramp: Vec<(i32, i32)> = (0..ramp_length)
+            .map(|step| (start.0 + dir.0 * step, start.1 + dir.1 * step))
+            .collect();
+        let fits = ramp.iter().all(|pos| {
+            pos.0 >= 0
+                && pos.0 < table.width() as i32
+                && pos.1 >= 0
+                && pos.1 < table.height() as i32
+                && table.get_obstacle(pos.0, pos.1) == Obstacle::Platform
+        });
+
+        if fits {
+            for pos in ramp {
+                table.set_surface(pos, Surface::Ramp(dir.0, dir.1));
+            }
+        }
+    }
+}
+
+// a random walk over platforms starting somewhere random
+fn place_patch(table: &mut ObstacleTable, surface: Surface, rng: &mut RandomNumberGenerator) {
+    let mut pos = match rng.random_slice_entry(&table.platforms) {
+        None => return,
+        Some(pos) => *pos,
+    };
+
+    for _ in 0..rng.range(6, 15) {
+        table.set_surface(pos, surface);
+
+        let nbrs: Vec<(i32, i32)> = neighbors(
+            pos,
+            (0, 0),
+            (table.width() as i32 - 1, table.height() as i32 - 1),
+        )
+        .into_iter()
+        .filter(|nbr| table.get_obstacle(nbr.0, nbr.1) == Obstacle::Platform)
+        .collect();
+
+        match rng.random_slice_entry(&nbrs) {
+            None => return,
+            Some(next) => pos = *next,
+        }
+    }
+}
+
 pub fn apply_voronoi_inv(table: &mut ObstacleTable, seeds: &HashSet<(i32, i32)>) {
     let vmembers = voronoi::voronoi_membership(seeds, table.width(), table.height());
     for x in 0..table.width() {
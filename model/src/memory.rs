@@ -0,0 +1,119 @@
+use crate::obstacle::Obstacle;
+use crate::surface::Surface;
+
+// how remembered tiles are forgotten
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MemoryDecay {
+    // remembered for the whole run
+    Never,
+
+    // forgotten once this many turns of game time have passed since it was seen
+    Time(f32),
+
+    // forgotten while further than this many tiles from the viewer
+    Distance(i32),
+}
+
+impl MemoryDecay {
+    // parses "never", "time" or "distance" with the amount that goes with it
+    pub fn from_config(mode: &str, amount: f32) -> Option<MemoryDecay> {
+        match mode {
+            "never" => Some(MemoryDecay::Never),
+            "time" => Some(MemoryDecay::Time(amount)),
+            "distance" => Some(MemoryDecay::Distance(amount as i32)),
+            _ => None,
+        }
+    }
+}
+
+// what a tile looked like the last time it was in view
+#[derive(Clone, Copy, PartialEq)]
+pub struct TileMemory {
+    pub obstacle: Obstacle,
+    pub surface: Surface,
+
+    // a skater was standing on it
+    pub occupied: bool,
+
+    // game time (in turns) when it was seen. Not Player::time, which
+    // starts over when a package is picked up
+    pub seen_at: f32,
+}
+
+// One slot per tile holding its last sighting. Seeing a tile overwrites its
+// slot and forgetting is worked out when asked, so both are O(1) no matter
+// how long the run has gone on.
+// It's what the couriers have seen, for drawing. Opponents go by the live
+// table instead: memory fades from the courier on this machine's screen,
+// so choices made from it would differ between players of a shared run
+pub struct Memory {
+    width: u32,
+    tiles: Vec<Option<TileMemory>>,
+    pub decay: MemoryDecay,
+
+    // when and where the viewer was at the last update, used for decay
+    now: f32,
+    viewer: (i32, i32),
+}
+
+impl Memory {
+    pub fn new(width: u32, height: u32, decay: MemoryDecay) -> Self {
+        Memory {
+            width,
+            tiles: vec![None; width as usize * height as usize],
+            decay,
+            now: 0.0,
+            viewer: (0, 0),
+        }
+    }
+}
+
+impl Memory {
+    // forget everything and match a table of width x height
+    pub fn reset(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.tiles = vec![None; width as usize * height as usize];
+        self.now = 0.0;
+        self.viewer = (0, 0);
+    }
+
+    // moves the clock and the viewer that decay is measured against
+    pub fn set_viewer(&mut self, now: f32, viewer: (i32, i32)) {
+        self.now = now;
+        self.viewer = viewer;
+    }
+
+    pub fn remember(&mut self, (x, y): (i32, i32), tile: TileMemory) {
+        let idx = self.idx(x, y);
+        self.tiles[idx] = Some(tile);
+    }
+
+    // the last sighting of the tile and how many turns ago it was,
+    // None if it was never seen or has been forgotten
+    pub fn last_seen(&self, x: i32, y: i32) -> Option<(TileMemory, f32)> {
+        let tile = self.tiles[self.idx(x, y)]?;
+        let age = (self.now - tile.seen_at).max(0.0);
+
+        let remembered = match self.decay {
+            MemoryDecay::Never => true,
+            MemoryDecay::Time(turns) => age <= turns,
+            MemoryDecay::Distance(tiles) => {
+                (x - self.viewer.0).abs().max((y - self.viewer.1).abs()) <= tiles
+            }
+        };
+
+        if remembered {
+            Some((tile, age))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_remembered(&self, x: i32, y: i32) -> bool {
+        self.last_seen(x, y).is_some()
+    }
+
+    fn idx(&self, x: i32, y: i32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
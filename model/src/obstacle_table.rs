@@ -4,7 +4,9 @@ use util::vec_ops;
 
 use crate::player::Player;
 
+use super::memory::{Memory, MemoryDecay};
 use super::obstacle::Obstacle;
+use super::surface::Surface;
 use super::traversability::Traversability;
 
 use std::collections::{HashMap};
@@ -13,12 +15,12 @@ pub struct ObstacleTable {
     width: u32,
     height: u32,
     table: Vec<Vec<Obstacle>>,
+    surfaces: Vec<Vec<Surface>>,
     pub platforms: Vec<(i32, i32)>,
     pub ufind: UnionFind<u32>,
     pub blocked: HashMap<(i32, i32), Player>,
-    pub revealed: Vec<bool>,
-    pub memory: Vec<(i32, i32)>,
-    pub memory_size: usize,
+    pub visible: Vec<bool>,
+    pub memory: Memory,
 }
 
 impl BaseMap for ObstacleTable {
@@ -90,18 +92,20 @@ impl ObstacleTable {
             width,
             height,
             table: Vec::new(),
+            surfaces: Vec::new(),
             platforms: Vec::new(),
             blocked: HashMap::new(),
             ufind: UnionFind::new(width as usize * height as usize),
-            revealed: vec![false; width as usize * height as usize],
-            memory: Vec::new(),
-            memory_size: 4096,
+            visible: vec![false; width as usize * height as usize],
+            memory: Memory::new(width, height, MemoryDecay::Never),
         };
 
         for x in 0..width {
             ct.table.push(Vec::new());
+            ct.surfaces.push(Vec::new());
             for _ in 0..height {
                 ct.table[x as usize].push(Obstacle::Platform);
+                ct.surfaces[x as usize].push(Surface::Concrete);
             }
         }
 
@@ -137,6 +141,31 @@ impl ObstacleTable {
         self.table[x as usize][y as usize]
     }
 
+    // in view of the player right now
+    pub fn is_visible(&self, x: i32, y: i32) -> bool {
+        self.visible[self.xy_flat(x, y)]
+    }
+
+    // seen at some point and not forgotten yet
+    pub fn is_revealed(&self, x: i32, y: i32) -> bool {
+        self.memory.is_remembered(x, y)
+    }
+
+    // forget everything that has been seen
+    pub fn reset_visibility(&mut self) {
+        let size = self.width as usize * self.height as usize;
+        self.visible = vec![false; size];
+        self.memory.reset(self.width, self.height);
+    }
+
+    pub fn get_surface(&self, x: i32, y: i32) -> Surface {
+        self.surfaces[x as usize][y as usize]
+    }
+
+    pub fn set_surface(&mut self, (x, y): (i32, i32), surface: Surface) {
+        self.surfaces[x as usize][y as usize] = surface;
+    }
+
     pub fn get_direction(&self, x: i32, y: i32) -> Option<(f32, f32)> {
         match self.table[x as usize][y as usize] {
             Obstacle::Platform => None,
@@ -178,6 +207,16 @@ impl ObstacleTable {
             && x_diff.abs() <= 1
             && y_diff.abs() <= 1
         {
+            // moving with the slope of a ramp is going up it
+            if let Surface::Ramp(x_dir, y_dir) = self.get_surface(to_x, to_y) {
+                let along = x_diff * x_dir + y_diff * y_dir;
+                if along > 0 {
+                    return Traversability::Up;
+                } else if along < 0 {
+                    return Traversability::Down;
+                }
+            }
+
             return Traversability::Flat;
         }
 
@@ -195,7 +234,9 @@ impl ObstacleTable {
         }
     }
 
+    // starts over each time, so regions of an earlier map don't stay joined
     pub fn compute_unions(&mut self) {
+        self.ufind = UnionFind::new(self.width as usize * self.height as usize);
         for x in 0..self.width {
             for y in 0..self.height {
                 if self.get_obstacle(x as i32, y as i32) == Obstacle::Platform {
@@ -235,14 +276,29 @@ impl ObstacleTable {
         }
     }
 
+    // a table with the same obstacles and surfaces, but with
+    // nobody on it and nothing seen yet
+    pub fn copy_layout(&self) -> ObstacleTable {
+        let mut copy = ObstacleTable::new(self.width, self.height);
+        copy.table = self.table.clone();
+        copy.surfaces = self.surfaces.clone();
+        copy.update_platforms();
+        copy.compute_unions();
+        copy
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         self.width = width;
         self.height = height;
         self.table.clear();
+        self.surfaces.clear();
+        self.reset_visibility();
         for x in 0..width {
             self.table.push(Vec::new());
+            self.surfaces.push(Vec::new());
             for _ in 0..height {
                 self.table[x as usize].push(Obstacle::Platform);
+                self.surfaces[x as usize].push(Surface::Concrete);
             }
         }
         self.regen_table();
@@ -252,7 +308,10 @@ impl ObstacleTable {
         for x in 0..self.width {
             for y in 0..self.height {
                 self.table[x as usize][y as usize] = Obstacle::Platform;
+                self.surfaces[x as usize][y as usize] = Surface::Concrete;
             }
         }
+        // nobody is standing on the new map yet
+        self.blocked.clear();
     }
 }
@@ -10,9 +10,14 @@ pub struct Player {
 
     pub n_falls: i32,
     pub n_delivered: u32,
+
+    // which of PlayerController::profiles the skater moves with
+    pub profile: usize,
+    // which of PlayerController::boards they ride, 0 is a stock board
+    pub board: usize,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PlayerType {
     Human,
     AI,
@@ -28,6 +33,8 @@ impl Player {
             recent_event: PlayerEvent::Wait,
             n_falls: 0,
             n_delivered: 0,
+            profile: 0,
+            board: 0,
         }
     }
 }
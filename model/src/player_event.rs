@@ -7,4 +7,22 @@ pub enum PlayerEvent {
     FallOver,
     GameOver(i32),
     Respawn,
+
+    // ran into the skater at (x, y), the turn loop resolves what happens to both
+    Collide(i32, i32),
+
+    // thrown off balance in a collision, but still standing
+    Bumped,
+
+    // fell over in a collision
+    KnockedOver,
+
+    // jumped over the tile between the last position and this one
+    Ollie,
+
+    // rolled on without slowing down much, leaning back
+    Manual,
+
+    // stopped without falling over
+    Bail,
 }
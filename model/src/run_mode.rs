@@ -0,0 +1,75 @@
+use super::run_stats::RunStats;
+
+// What the player has to do before the run ends
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunMode {
+    // deliver as much as possible before this much Player::time has passed
+    TimedShift(f32),
+
+    // deliver this many packages
+    Quota(u32),
+
+    // keep delivering until falling over this many times
+    Survival(u32),
+}
+
+// why a run ended
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunEnd {
+    ShiftOver,
+    QuotaMet,
+    OutOfFalls,
+}
+
+impl RunMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunMode::TimedShift(_) => "Timed Shift",
+            RunMode::Quota(_) => "Quota",
+            RunMode::Survival(_) => "Survival",
+        }
+    }
+
+    pub fn goal(&self) -> String {
+        match self {
+            RunMode::TimedShift(time) => {
+                format!("deliver as much as you can in {} turns", time.round())
+            }
+            RunMode::Quota(n) => format!("deliver {} packages", n),
+            RunMode::Survival(n) => format!("keep going until you fall {} times", n),
+        }
+    }
+
+    // Some once stats meet the end of the run
+    pub fn check(&self, stats: &RunStats) -> Option<RunEnd> {
+        match self {
+            RunMode::TimedShift(time) if stats.time >= *time => Some(RunEnd::ShiftOver),
+            RunMode::Quota(n) if stats.delivered >= *n => Some(RunEnd::QuotaMet),
+            RunMode::Survival(n) if stats.n_falls >= *n => Some(RunEnd::OutOfFalls),
+            _ => None,
+        }
+    }
+
+    // how far along the run is, for the HUD
+    pub fn status(&self, stats: &RunStats) -> String {
+        match self {
+            RunMode::TimedShift(time) => {
+                format!("{} turns left", (time - stats.time).max(0.0).ceil())
+            }
+            RunMode::Quota(n) => format!("{}/{} delivered", stats.delivered, n),
+            RunMode::Survival(n) => {
+                format!("{} falls left", n.saturating_sub(stats.n_falls))
+            }
+        }
+    }
+}
+
+impl RunEnd {
+    pub fn title(&self) -> &'static str {
+        match self {
+            RunEnd::ShiftOver => "Shift Over",
+            RunEnd::QuotaMet => "Quota Met",
+            RunEnd::OutOfFalls => "Out of Falls",
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::player::Player;
+use super::player_event::PlayerEvent;
+use util::vec_ops;
+
+// What happened over a whole run. Player::time and Player::n_falls are
+// reset during a run (on pickup and on respawn), so they are added up here
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunStats {
+    pub score: i32,
+    pub delivered: u32,
+
+    // sum of Player::time over the run
+    pub time: f32,
+    pub n_falls: u32,
+
+    // tiles skated, diagonals count as sqrt 2
+    pub distance: f32,
+
+    pub seed: u64,
+    pub map_size: (u32, u32),
+}
+
+impl RunStats {
+    pub fn new(seed: u64, map_size: (u32, u32)) -> Self {
+        RunStats {
+            score: 0,
+            delivered: 0,
+            time: 0.0,
+            n_falls: 0,
+            distance: 0.0,
+            seed,
+            map_size,
+        }
+    }
+}
+
+impl RunStats {
+    // adds up one move of the human, from before to after
+    pub fn record_move(&mut self, before: &Player, after: &Player) {
+        if after.time > before.time {
+            self.time += after.time - before.time;
+        }
+
+        self.distance += vec_ops::magnitude((
+            (after.x() - before.x()) as f32,
+            (after.y() - before.y()) as f32,
+        ));
+
+        // landing in a pit respawns without falling over first
+        if let PlayerEvent::FallOver | PlayerEvent::KnockedOver | PlayerEvent::Respawn =
+            after.recent_event
+        {
+            self.n_falls += 1;
+        }
+    }
+
+    pub fn record_fall(&mut self) {
+        self.n_falls += 1;
+    }
+
+    // time that passed outside of a move, like respawning
+    pub fn add_time(&mut self, time: f32) {
+        self.time += time.max(0.0);
+    }
+
+    // the run did anything worth keeping
+    pub fn is_empty(&self) -> bool {
+        self.time == 0.0 && self.delivered == 0
+    }
+}
@@ -10,4 +10,9 @@ pub enum ProcState {
     GotPackage(i32, i32),
     DeliveredPackage,
     LookMode,
+    MessageHistory,
+    HighScores,
+    Bindings,
+    Sandbox,
+    Shop,
 }
@@ -0,0 +1,15 @@
+// what the ground of a Platform is made of
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Surface {
+    // smooth, no effect
+    Concrete,
+
+    // loose stones that push the player's balance around
+    Gravel,
+
+    // slippery, pushes and turns get less grip
+    Wet,
+
+    // slopes upward toward (x, y)
+    Ramp(i32, i32),
+}
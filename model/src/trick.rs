@@ -0,0 +1,11 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Trick {
+    // hop over the next tile in the direction of travel
+    Ollie,
+
+    // roll on two wheels, keeping speed at the cost of balance
+    Manual,
+
+    // step off the board and stop without falling
+    Bail,
+}
@@ -2,8 +2,50 @@ use std::collections::HashSet;
 
 use rltk::Point;
 
+use crate::memory::TileMemory;
 use crate::obstacle_table::ObstacleTable;
 
 pub fn get_fov(center: (i32, i32), table: &ObstacleTable, radius: i32) -> HashSet<Point> {
     rltk::field_of_view_set(Point::new(center.0, center.1), radius, table)
 }
+
+// recomputes what can be seen from center at game time now (in turns) and
+// remembers it. Call after every turn, the renderer only reads the result
+pub fn update_visibility(table: &mut ObstacleTable, center: (i32, i32), radius: i32, now: f32) {
+    update_shared_visibility(table, &[center], radius, now);
+}
+
+// update_visibility for couriers sharing the map: everything any of the
+// viewers can see is visible. They all go by the one clock, so a tile's
+// age is the same whoever saw it. Memory fades from the first viewer's
+// point of view
+pub fn update_shared_visibility(
+    table: &mut ObstacleTable,
+    viewers: &[(i32, i32)],
+    radius: i32,
+    now: f32,
+) {
+    for v in table.visible.iter_mut() {
+        *v = false;
+    }
+
+    if let Some(center) = viewers.first() {
+        table.memory.set_viewer(now, *center);
+    }
+    for center in viewers.iter() {
+        let fov = get_fov(*center, table, radius);
+        for p in fov.iter() {
+            let idx = table.xy_flat(p.x, p.y);
+            table.visible[idx] = true;
+
+            let tile = TileMemory {
+                obstacle: table.get_obstacle(p.x, p.y),
+                surface: table.get_surface(p.x, p.y),
+                occupied: table.blocked.contains_key(&(p.x, p.y))
+                    && viewers.iter().all(|viewer| (p.x, p.y) != *viewer),
+                seen_at: now,
+            };
+            table.memory.remember((p.x, p.y), tile);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// What the player can see from where they are, and what they've explored
+
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::visibility;
+
+// a corridor split by a wall at x = 10
+fn table() -> ObstacleTable {
+    let mut table = ObstacleTable::new(21, 5);
+    for y in 0..5 {
+        table.set_obstacle((10, y), Obstacle::Wall);
+    }
+    table.update_platforms();
+    table
+}
+
+#[test]
+fn one_viewer_sees_their_side() {
+    let mut table = table();
+    visibility::update_visibility(&mut table, (2, 2), 16, 1.0);
+    assert!(table.is_visible(5, 2));
+    assert!(!table.is_visible(15, 2));
+}
+
+#[test]
+fn walls_are_seen_but_not_through() {
+    let mut table = table();
+    visibility::update_visibility(&mut table, (8, 2), 16, 1.0);
+    assert!(table.is_visible(10, 2));
+    assert!(table.is_revealed(10, 2));
+    assert!(!table.is_revealed(12, 2));
+}
+
+#[test]
+fn nothing_is_revealed_before_it_is_seen() {
+    let table = table();
+    for x in 0..21 {
+        assert!(!table.is_visible(x, 2));
+        assert!(!table.is_revealed(x, 2));
+    }
+}
+
+#[test]
+fn explored_tiles_stay_revealed_out_of_sight() {
+    let mut table = table();
+    visibility::update_visibility(&mut table, (2, 2), 4, 1.0);
+    assert!(table.is_visible(5, 2));
+
+    visibility::update_visibility(&mut table, (9, 2), 2, 2.0);
+    assert!(!table.is_visible(5, 2));
+    assert!(table.is_revealed(5, 2));
+    assert!(table.is_visible(9, 2));
+}
+
+#[test]
+fn a_new_run_forgets_what_was_explored() {
+    let mut table = table();
+    visibility::update_visibility(&mut table, (2, 2), 16, 1.0);
+    table.reset_visibility();
+    assert!(!table.is_visible(5, 2));
+    assert!(!table.is_revealed(5, 2));
+}
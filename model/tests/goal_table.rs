@@ -0,0 +1,39 @@
+use model::goal_table::GoalTable;
+use rltk::RGB;
+
+fn goals() -> GoalTable {
+    let mut goals = GoalTable::new();
+    goals.add_goal((2, 2), (0, RGB::named(rltk::CYAN)));
+    goals.add_goal((10, 4), (1, RGB::named(rltk::ORANGE)));
+    goals.add_goal((20, 20), (2, RGB::named(rltk::PINK)));
+    goals
+}
+
+#[test]
+fn nearest_job_is_the_closest_package() {
+    let goals = goals();
+    assert_eq!(goals.nearest_job((0, 0)), Some((2, 2)));
+    assert_eq!(goals.nearest_job((9, 5)), Some((10, 4)));
+    assert_eq!(goals.nearest_job((20, 20)), Some((20, 20)));
+}
+
+#[test]
+fn packages_picked_up_are_not_jobs() {
+    let mut goals = goals();
+    goals.picked_up.insert((2, 2));
+    assert_eq!(goals.nearest_job((0, 0)), Some((10, 4)));
+
+    goals.picked_up.insert((10, 4));
+    goals.picked_up.insert((20, 20));
+    assert_eq!(goals.nearest_job((0, 0)), None);
+    assert_eq!(GoalTable::new().nearest_job((0, 0)), None);
+}
+
+#[test]
+fn delivering_frees_the_recipient() {
+    let mut goals = goals();
+    assert!(goals.remove_goal_if_reached((10, 4)));
+    assert!(!goals.index_map.contains_key(&1));
+    assert_eq!(goals.count(), 2);
+    assert!(!goals.remove_goal_if_reached((10, 4)));
+}
@@ -0,0 +1,126 @@
+use model::high_scores::HighScores;
+use model::map_gen;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use model::run_stats::RunStats;
+use rltk::RandomNumberGenerator;
+
+fn run(score: i32, delivered: u32, time: f32) -> RunStats {
+    RunStats {
+        score,
+        delivered,
+        time,
+        ..RunStats::new(42, (80, 50))
+    }
+}
+
+#[test]
+fn best_runs_first() {
+    let mut scores = HighScores::new(10);
+    assert_eq!(scores.add(run(100, 1, 30.0)), Some(0));
+    assert_eq!(scores.add(run(300, 2, 60.0)), Some(0));
+    assert_eq!(scores.add(run(200, 2, 50.0)), Some(1));
+
+    let order: Vec<i32> = scores.runs.iter().map(|r| r.score).collect();
+    assert_eq!(order, vec![300, 200, 100]);
+    assert_eq!(scores.best().unwrap().score, 300);
+}
+
+#[test]
+fn ties_go_to_the_faster_run_then_the_older_one() {
+    let mut scores = HighScores::new(10);
+    scores.add(run(100, 1, 30.0));
+    assert_eq!(scores.add(run(100, 1, 20.0)), Some(0));
+    assert_eq!(scores.add(run(100, 1, 20.0)), Some(1));
+}
+
+#[test]
+fn only_keeps_the_best() {
+    let mut scores = HighScores::new(2);
+    scores.add(run(300, 3, 10.0));
+    scores.add(run(200, 2, 10.0));
+
+    assert_eq!(scores.add(run(100, 1, 10.0)), None);
+    assert_eq!(scores.add(run(250, 2, 10.0)), Some(1));
+    let order: Vec<i32> = scores.runs.iter().map(|r| r.score).collect();
+    assert_eq!(order, vec![300, 250]);
+}
+
+#[test]
+fn survives_a_round_trip() {
+    let mut scores = HighScores::new(10);
+    scores.add(RunStats {
+        score: 1234,
+        delivered: 5,
+        time: 101.25,
+        n_falls: 3,
+        distance: 87.5,
+        seed: u64::MAX,
+        map_size: (120, 60),
+    });
+    scores.add(run(7, 1, 2.5));
+
+    let loaded = HighScores::from_config(&scores.to_config(), 10);
+    assert_eq!(loaded.runs, scores.runs);
+}
+
+#[test]
+fn skips_lines_that_do_not_parse() {
+    let saved = "# comment\nrun 10 1 2 0 3 4 80 50\nrun nonsense\nrun 20 2 2 0 3 4 80\n\nrun 30 3 2 0 3 4 80 50\n";
+    let loaded = HighScores::from_config(saved, 10);
+    let order: Vec<i32> = loaded.runs.iter().map(|r| r.score).collect();
+    assert_eq!(order, vec![30, 10]);
+}
+
+#[test]
+fn stats_add_up_moves_and_falls() {
+    let mut stats = RunStats::new(1, (80, 50));
+    let mut before = Player::new(0, 0);
+    before.time = 2.0;
+
+    let mut after = before;
+    after.position = (3, 4);
+    after.time = 3.5;
+    after.recent_event = PlayerEvent::Move;
+    stats.record_move(&before, &after);
+
+    // time reset on pickup doesn't count backwards
+    let mut fell = after;
+    fell.time = 0.0;
+    fell.recent_event = PlayerEvent::FallOver;
+    stats.record_move(&after, &fell);
+
+    assert_eq!(stats.distance, 5.0);
+    assert_eq!(stats.time, 1.5);
+    assert_eq!(stats.n_falls, 1);
+    assert!(!stats.is_empty());
+}
+
+// the map a run was skated on, built the way the game starts a run
+fn map_of(run: &RunStats) -> ObstacleTable {
+    let mut rng = RandomNumberGenerator::seeded(run.seed);
+    let mut table = ObstacleTable::new(run.map_size.0, run.map_size.1);
+    map_gen::voronoi_mapgen(&mut table, &mut rng);
+    map_gen::tunnel_pockets(&mut table, &mut rng);
+    table.update_platforms();
+    map_gen::place_surfaces(&mut table, &mut rng);
+    table
+}
+
+#[test]
+fn a_saved_run_can_be_skated_again() {
+    let mut scores = HighScores::new(10);
+    scores.add(RunStats::new(987654321, (60, 40)));
+    let saved = HighScores::from_config(&scores.to_config(), 10);
+    let run = saved.best().unwrap();
+
+    let (first, again) = (map_of(&scores.runs[0]), map_of(run));
+    assert_eq!(first.platforms, again.platforms);
+    for y in 0..40 {
+        for x in 0..60 {
+            assert!(first.get_obstacle(x, y) == again.get_obstacle(x, y));
+            assert!(first.get_surface(x, y) == again.get_surface(x, y));
+        }
+    }
+}
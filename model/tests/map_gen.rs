@@ -0,0 +1,43 @@
+// Maps made from a seed, which every peer of a networked run has to build
+// the same way from the same one
+
+use model::map_gen;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::surface::Surface;
+use rltk::RandomNumberGenerator;
+
+fn generate(seed: u64) -> ObstacleTable {
+    let mut rng = RandomNumberGenerator::seeded(seed);
+    let mut table = ObstacleTable::new(40, 30);
+    map_gen::voronoi_mapgen(&mut table, &mut rng);
+    map_gen::tunnel_pockets(&mut table, &mut rng);
+    table.update_platforms();
+    map_gen::place_surfaces(&mut table, &mut rng);
+    table
+}
+
+fn tiles(table: &ObstacleTable) -> Vec<(Obstacle, Surface)> {
+    let mut tiles = Vec::new();
+    for y in 0..table.height() as i32 {
+        for x in 0..table.width() as i32 {
+            tiles.push((table.get_obstacle(x, y), table.get_surface(x, y)));
+        }
+    }
+    tiles
+}
+
+#[test]
+fn the_same_seed_makes_the_same_map() {
+    for seed in [1, 7, 12345] {
+        let first = generate(seed);
+        let second = generate(seed);
+        assert!(tiles(&first) == tiles(&second), "seed {}", seed);
+        assert_eq!(first.platforms, second.platforms);
+    }
+}
+
+#[test]
+fn different_seeds_make_different_maps() {
+    assert!(tiles(&generate(1)) != tiles(&generate(2)));
+}
@@ -0,0 +1,95 @@
+// Remembering tiles once they're out of sight, and forgetting them again
+
+use model::memory::{Memory, MemoryDecay, TileMemory};
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::surface::Surface;
+use model::visibility;
+
+fn seen_at(time: f32) -> TileMemory {
+    TileMemory {
+        obstacle: Obstacle::Platform,
+        surface: Surface::Gravel,
+        occupied: false,
+        seen_at: time,
+    }
+}
+
+#[test]
+fn decay_modes_are_read_from_config() {
+    assert_eq!(MemoryDecay::from_config("never", 3.0), Some(MemoryDecay::Never));
+    assert_eq!(MemoryDecay::from_config("time", 30.0), Some(MemoryDecay::Time(30.0)));
+    assert_eq!(MemoryDecay::from_config("distance", 12.0), Some(MemoryDecay::Distance(12)));
+    assert_eq!(MemoryDecay::from_config("sometimes", 1.0), None);
+}
+
+#[test]
+fn tiles_are_remembered_with_their_age() {
+    let mut memory = Memory::new(10, 10, MemoryDecay::Never);
+    assert!(!memory.is_remembered(3, 3));
+
+    memory.remember((3, 3), seen_at(2.0));
+    memory.set_viewer(7.5, (0, 0));
+
+    let (tile, age) = memory.last_seen(3, 3).unwrap();
+    assert!(tile.surface == Surface::Gravel);
+    assert_eq!(age, 5.5);
+    assert!(!memory.is_remembered(4, 3));
+}
+
+#[test]
+fn seeing_a_tile_again_replaces_the_old_sighting() {
+    let mut memory = Memory::new(10, 10, MemoryDecay::Never);
+    memory.remember((3, 3), seen_at(2.0));
+    memory.remember((3, 3), seen_at(6.0));
+    memory.set_viewer(7.0, (0, 0));
+    assert_eq!(memory.last_seen(3, 3).unwrap().1, 1.0);
+}
+
+#[test]
+fn time_decay_forgets_old_sightings() {
+    let mut memory = Memory::new(10, 10, MemoryDecay::Time(10.0));
+    memory.remember((3, 3), seen_at(0.0));
+
+    memory.set_viewer(10.0, (3, 3));
+    assert!(memory.is_remembered(3, 3));
+    memory.set_viewer(10.5, (3, 3));
+    assert!(!memory.is_remembered(3, 3));
+}
+
+#[test]
+fn distance_decay_forgets_far_tiles_until_the_viewer_is_back() {
+    let mut memory = Memory::new(20, 20, MemoryDecay::Distance(5));
+    memory.remember((3, 3), seen_at(0.0));
+
+    memory.set_viewer(100.0, (8, 8));
+    assert!(memory.is_remembered(3, 3));
+    memory.set_viewer(100.0, (9, 3));
+    assert!(!memory.is_remembered(3, 3));
+    memory.set_viewer(200.0, (4, 4));
+    assert!(memory.is_remembered(3, 3));
+}
+
+#[test]
+fn reset_forgets_everything() {
+    let mut memory = Memory::new(10, 10, MemoryDecay::Never);
+    memory.remember((3, 3), seen_at(0.0));
+    memory.reset(12, 12);
+    assert!(!memory.is_remembered(3, 3));
+    assert!(!memory.is_remembered(11, 11));
+}
+
+#[test]
+fn skaters_are_remembered_where_they_were_seen() {
+    let mut table = ObstacleTable::new(10, 3);
+    table.update_platforms();
+    table.blocked.insert((6, 1), Player::new(6, 1));
+    table.blocked.insert((2, 1), Player::new(2, 1));
+
+    // the viewer at (2, 1) isn't someone they saw
+    visibility::update_visibility(&mut table, (2, 1), 8, 1.0);
+    assert!(table.memory.last_seen(6, 1).unwrap().0.occupied);
+    assert!(!table.memory.last_seen(2, 1).unwrap().0.occupied);
+    assert!(!table.memory.last_seen(4, 1).unwrap().0.occupied);
+}
@@ -0,0 +1,36 @@
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
+
+fn stats(delivered: u32, time: f32, n_falls: u32) -> RunStats {
+    RunStats {
+        delivered,
+        time,
+        n_falls,
+        ..RunStats::new(0, (80, 50))
+    }
+}
+
+#[test]
+fn timed_shift_ends_when_time_runs_out() {
+    let mode = RunMode::TimedShift(100.0);
+    assert_eq!(mode.check(&stats(9, 99.5, 9)), None);
+    assert_eq!(mode.check(&stats(0, 100.0, 0)), Some(RunEnd::ShiftOver));
+    assert_eq!(mode.status(&stats(0, 99.5, 0)), "1 turns left");
+    assert_eq!(mode.status(&stats(0, 120.0, 0)), "0 turns left");
+}
+
+#[test]
+fn quota_ends_on_the_last_delivery() {
+    let mode = RunMode::Quota(3);
+    assert_eq!(mode.check(&stats(2, 1000.0, 9)), None);
+    assert_eq!(mode.check(&stats(3, 0.0, 0)), Some(RunEnd::QuotaMet));
+    assert_eq!(mode.status(&stats(2, 0.0, 0)), "2/3 delivered");
+}
+
+#[test]
+fn survival_ends_on_the_last_fall() {
+    let mode = RunMode::Survival(2);
+    assert_eq!(mode.check(&stats(9, 1000.0, 1)), None);
+    assert_eq!(mode.check(&stats(0, 0.0, 2)), Some(RunEnd::OutOfFalls));
+    assert_eq!(mode.status(&stats(0, 0.0, 1)), "1 falls left");
+}
@@ -0,0 +1,38 @@
+// Which way a ramp slopes for a skater crossing it
+
+use model::obstacle_table::ObstacleTable;
+use model::surface::Surface;
+use model::traversability::Traversability;
+
+// a ramp at (2, 2) sloping up to the east
+fn table() -> ObstacleTable {
+    let mut table = ObstacleTable::new(5, 5);
+    table.update_platforms();
+    table.set_surface((2, 2), Surface::Ramp(1, 0));
+    table
+}
+
+#[test]
+fn moving_with_the_slope_is_up_and_against_it_down() {
+    let table = table();
+    assert!(table.traversability((1, 2), (2, 2)) == Traversability::Up);
+    assert!(table.traversability((3, 2), (2, 2)) == Traversability::Down);
+    // diagonals count by how much of the move is along the slope
+    assert!(table.traversability((1, 1), (2, 2)) == Traversability::Up);
+    assert!(table.traversability((3, 3), (2, 2)) == Traversability::Down);
+}
+
+#[test]
+fn crossing_a_ramp_sideways_or_leaving_it_is_flat() {
+    let table = table();
+    assert!(table.traversability((2, 1), (2, 2)) == Traversability::Flat);
+    assert!(table.traversability((2, 2), (3, 2)) == Traversability::Flat);
+    assert!(table.traversability((0, 0), (1, 1)) == Traversability::Flat);
+}
+
+#[test]
+fn off_the_map_or_more_than_a_tile_away_is_no_way() {
+    let table = table();
+    assert!(table.traversability((4, 4), (5, 4)) == Traversability::No);
+    assert!(table.traversability((0, 2), (2, 2)) == Traversability::No);
+}
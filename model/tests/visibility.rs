@@ -0,0 +1,70 @@
+// Couriers sharing what they see of the map (see fog_of_war.rs for one)
+
+use model::memory::MemoryDecay;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::visibility;
+
+// a corridor split by a wall at x = 10
+fn table() -> ObstacleTable {
+    let mut table = ObstacleTable::new(21, 5);
+    for y in 0..5 {
+        table.set_obstacle((10, y), Obstacle::Wall);
+    }
+    table.update_platforms();
+    table
+}
+
+#[test]
+fn shared_visibility_is_what_anyone_sees() {
+    let mut table = table();
+    visibility::update_shared_visibility(&mut table, &[(2, 2), (18, 2)], 16, 1.0);
+    assert!(table.is_visible(5, 2));
+    assert!(table.is_visible(15, 2));
+
+    // seeing is redone every turn, so the second courier's side goes dark
+    visibility::update_shared_visibility(&mut table, &[(2, 2)], 16, 4.0);
+    assert!(!table.is_visible(15, 2));
+}
+
+#[test]
+fn shared_with_one_viewer_is_the_same_as_alone() {
+    let mut alone = table();
+    let mut shared = table();
+    visibility::update_visibility(&mut alone, (18, 1), 6, 2.0);
+    visibility::update_shared_visibility(&mut shared, &[(18, 1)], 6, 2.0);
+    assert_eq!(alone.visible, shared.visible);
+}
+
+// Player::time starts over when a package is picked up, the clock memory
+// goes by keeps running
+#[test]
+fn time_decay_forgets_tiles_seen_before_a_pickup() {
+    let mut table = table();
+    table.memory.decay = MemoryDecay::Time(3.0);
+    visibility::update_visibility(&mut table, (2, 2), 16, 1.0);
+
+    // picked up across the wall
+    visibility::update_visibility(&mut table, (18, 2), 16, 3.0);
+    assert_eq!(table.memory.last_seen(5, 2).unwrap().1, 2.0);
+
+    visibility::update_visibility(&mut table, (18, 2), 16, 6.0);
+    assert!(!table.memory.is_remembered(5, 2));
+    assert!(table.memory.is_remembered(15, 2));
+}
+
+#[test]
+fn tiles_age_the_same_whichever_courier_saw_them() {
+    let mut table = table();
+    table.memory.decay = MemoryDecay::Time(2.0);
+    visibility::update_shared_visibility(&mut table, &[(2, 2), (18, 2)], 16, 5.0);
+
+    // the second courier comes over to the first one's side
+    visibility::update_shared_visibility(&mut table, &[(2, 2), (8, 2)], 16, 6.0);
+    assert_eq!(table.memory.last_seen(15, 2).unwrap().1, 1.0);
+    assert_eq!(table.memory.last_seen(5, 2).unwrap().1, 0.0);
+
+    visibility::update_shared_visibility(&mut table, &[(2, 2), (8, 2)], 16, 8.0);
+    assert!(!table.memory.is_remembered(15, 2));
+    assert!(table.memory.is_remembered(5, 2));
+}
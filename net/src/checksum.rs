@@ -0,0 +1,47 @@
+// FNV-1a over the parts of a run that have to match. Peers compare these
+// after every turn to notice they have drifted apart, so it only has to
+// be the same everywhere, not hard to fool
+pub struct Checksum {
+    hash: u64,
+}
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl Checksum {
+    pub fn new() -> Self {
+        Checksum { hash: OFFSET_BASIS }
+    }
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Checksum::new()
+    }
+}
+
+impl Checksum {
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(PRIME);
+        }
+    }
+
+    pub fn add_u64(&mut self, value: u64) {
+        self.add_bytes(&value.to_le_bytes());
+    }
+
+    pub fn add_i32(&mut self, value: i32) {
+        self.add_bytes(&value.to_le_bytes());
+    }
+
+    // by its bits, so -0.0 and 0.0 differ but every peer agrees on them
+    pub fn add_f32(&mut self, value: f32) {
+        self.add_bytes(&value.to_bits().to_le_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,124 @@
+use crate::connection::{Connection, NetError};
+use crate::lockstep::Lockstep;
+use crate::protocol::{Command, Message, PROTOCOL_VERSION};
+
+// what happened during a poll
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientEvent {
+    // the run began, with this client playing courier
+    Started { courier: usize },
+    Turn { turn: u64, courier: usize },
+}
+
+// One courier's side of a networked run. Their command is sent when it's
+// their turn, and only played once it comes back from the server like
+// everyone else's, so every turn happens in the same order everywhere
+pub struct Client {
+    connection: Box<dyn Connection>,
+    courier: Option<usize>,
+    // turns played so far
+    turn: u64,
+    // sent a command for this turn, waiting for the server to play it
+    sent: bool,
+}
+
+impl Client {
+    pub fn join(mut connection: Box<dyn Connection>, name: &str) -> Result<Self, NetError> {
+        // a name is the rest of a line, so it has to stay on one
+        let mut name = name.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
+        if name.is_empty() {
+            name = "Courier".to_string();
+        }
+        connection.send(&Message::Join {
+            version: PROTOCOL_VERSION,
+            name,
+        })?;
+
+        Ok(Client {
+            connection,
+            courier: None,
+            turn: 0,
+            sent: false,
+        })
+    }
+}
+
+impl Client {
+    // which courier this client plays, None until the run starts
+    pub fn courier(&self) -> Option<usize> {
+        self.courier
+    }
+
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    // whether the run is waiting for this client's command
+    pub fn can_move<S: Lockstep>(&self, run: &S) -> bool {
+        !self.sent && self.courier.is_some() && run.turn_courier() == self.courier
+    }
+
+    pub fn send(&mut self, command: Command) -> Result<(), NetError> {
+        let courier = match self.courier {
+            None => return Ok(()),
+            Some(courier) => courier,
+        };
+        self.connection.send(&Message::Input {
+            turn: self.turn,
+            courier,
+            command,
+        })?;
+        self.sent = true;
+        Ok(())
+    }
+
+    // plays every turn that has arrived since the last poll on run
+    pub fn poll<S: Lockstep>(&mut self, run: &mut S) -> Result<Vec<ClientEvent>, NetError> {
+        let mut events = Vec::new();
+        while let Some(message) = self.connection.receive()? {
+            match message {
+                Message::MapSeed { setup, courier } => {
+                    run.start(&setup);
+                    self.courier = Some(courier);
+                    self.turn = 0;
+                    self.sent = false;
+                    events.push(ClientEvent::Started { courier });
+                }
+                Message::TurnResult {
+                    turn,
+                    courier,
+                    command,
+                    checksum,
+                } => {
+                    // a turn from the past was played already
+                    if turn < self.turn || self.courier.is_none() {
+                        continue;
+                    }
+                    // and one from the future means some were missed
+                    if turn > self.turn {
+                        return Err(NetError::Desync(self.turn));
+                    }
+
+                    run.apply(courier, command);
+                    self.turn += 1;
+                    if Some(courier) == self.courier {
+                        self.sent = false;
+                    }
+
+                    let ours = run.checksum();
+                    if ours != checksum {
+                        let _ = self.connection.send(&Message::Desync {
+                            turn,
+                            checksum: ours,
+                        });
+                        return Err(NetError::Desync(turn));
+                    }
+                    events.push(ClientEvent::Turn { turn, courier });
+                }
+                Message::Refused(reason) => return Err(NetError::Refused(reason)),
+                _ => {}
+            }
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::protocol::Message;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetError {
+    // the other end went away
+    Closed,
+    Io(String),
+    // the server wouldn't let us join, and why
+    Refused(String),
+    // our copy of the run stopped matching the server's after this turn
+    Desync(u64),
+}
+
+impl NetError {
+    pub fn description(&self) -> String {
+        match self {
+            NetError::Closed => "The connection was closed".to_string(),
+            NetError::Io(error) => format!("Network error: {}", error),
+            NetError::Refused(reason) => format!("The server refused: {}", reason),
+            NetError::Desync(turn) => format!("Out of step with the server after turn {}", turn),
+        }
+    }
+}
+
+// A way to swap messages with one peer. Neither call waits: the game
+// asks every frame, and the web build can't wait anyway
+pub trait Connection {
+    fn send(&mut self, message: &Message) -> Result<(), NetError>;
+
+    // the next message that has arrived, None while there isn't one.
+    // Lines that aren't messages are skipped
+    fn receive(&mut self) -> Result<Option<Message>, NetError>;
+}
+
+// Splits what arrives on a stream into lines, keeping the start of a
+// line until the rest of it comes
+pub struct LineBuffer {
+    bytes: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        LineBuffer { bytes: Vec::new() }
+    }
+}
+
+impl Default for LineBuffer {
+    fn default() -> Self {
+        LineBuffer::new()
+    }
+}
+
+impl LineBuffer {
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    // the next message out of the complete lines, skipping the rest
+    pub fn next_message(&mut self) -> Option<Message> {
+        while let Some(end) = self.bytes.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.bytes.drain(..=end).collect();
+            let message = std::str::from_utf8(&line).ok().and_then(Message::from_line);
+            if message.is_some() {
+                return message;
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,14 @@
+// Playing a run with couriers on other machines. A server keeps the copy of
+// the run everyone goes by, the couriers send it what they do on their turns
+// and every peer replays the same turns from the same seed (lockstep)
+pub mod checksum;
+pub mod client;
+pub mod connection;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod listener;
+pub mod lockstep;
+pub mod protocol;
+pub mod server;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tcp;
+pub mod web_socket;
@@ -0,0 +1,82 @@
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::connection::{Connection, NetError};
+use crate::tcp::{io_error, TcpConnection};
+use crate::web_socket::WebSocketConnection;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transport {
+    // native couriers
+    Tcp,
+    // couriers playing in a browser
+    WebSocket,
+}
+
+// a WebSocket handshake finished on its own thread
+type Handshake = Result<WebSocketConnection, NetError>;
+
+// Where a server waits for couriers to connect, one per transport
+pub struct Listener {
+    listener: TcpListener,
+    transport: Transport,
+
+    // a slow or silent browser mustn't hold up the server's loop, so
+    // each handshake runs on a thread and comes back through here
+    finished: Sender<Handshake>,
+    handshakes: Receiver<Handshake>,
+}
+
+impl Listener {
+    pub fn bind<A: ToSocketAddrs>(address: A, transport: Transport) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(address).map_err(io_error)?;
+        listener.set_nonblocking(true).map_err(io_error)?;
+        let (finished, handshakes) = mpsc::channel();
+        Ok(Listener {
+            listener,
+            transport,
+            finished,
+            handshakes,
+        })
+    }
+}
+
+impl Listener {
+    pub fn local_addr(&self) -> Result<SocketAddr, NetError> {
+        self.listener.local_addr().map_err(io_error)
+    }
+
+    // a courier who has just connected, None while nobody is waiting.
+    // Never waits on the courier, WebSocket couriers turn up here once
+    // their handshake is done
+    pub fn accept(&self) -> Result<Option<Box<dyn Connection>>, NetError> {
+        let stream = match self.listener.accept() {
+            Ok((stream, _)) => Some(stream),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => None,
+            Err(e) => return Err(io_error(e)),
+        };
+
+        if let Some(stream) = stream {
+            // accepted sockets can inherit non-blocking, the handshake waits
+            stream.set_nonblocking(false).map_err(io_error)?;
+            match self.transport {
+                Transport::Tcp => return Ok(Some(Box::new(TcpConnection::from_stream(stream)?))),
+                Transport::WebSocket => {
+                    let finished = self.finished.clone();
+                    thread::spawn(move || {
+                        // the listener may be gone by the time it's done
+                        let _ = finished.send(WebSocketConnection::accept(stream));
+                    });
+                }
+            }
+        }
+
+        match self.handshakes.try_recv() {
+            Ok(handshake) => Ok(Some(Box::new(handshake?))),
+            // the listener keeps a sender, so this is only ever empty
+            Err(_) => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::protocol::{Command, RunSetup};
+
+// A run the server and every client play in step. Anything that changes
+// it has to go through apply, so that peers given the same setup and the
+// same commands in the same order end up with the same checksum
+pub trait Lockstep {
+    // throws away the run so far and starts the one in setup
+    fn start(&mut self, setup: &RunSetup);
+
+    // the courier who has to send the next command, None once the run is over
+    fn turn_courier(&self) -> Option<usize>;
+
+    fn apply(&mut self, courier: usize, command: Command);
+
+    fn checksum(&self) -> u64;
+}
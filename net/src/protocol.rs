@@ -0,0 +1,172 @@
+use model::trick::Trick;
+
+// Raised whenever a message changes. A client has to speak the same
+// version as the server to join
+pub const PROTOCOL_VERSION: u32 = 1;
+
+// What a courier does with their turn. Everything else in a run follows
+// from the seed and these, so they are all the peers send each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    // a push in a direction, from 0 (none) to 1 (full strength)
+    Move((i32, i32), f32),
+    Trick(Trick),
+    // talk to the skaters next to them, which is how packages are delivered
+    Chat,
+}
+
+// what every peer starts the run with, indices are into the
+// modes and skaters each peer has loaded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunSetup {
+    pub seed: u64,
+    pub couriers: usize,
+    pub mode: usize,
+    pub skater: usize,
+}
+
+// One line of text each, the first word says which message it is:
+//   join <version> <name>                        client to server
+//   refused <reason>                             server to client
+//   seed <seed> <couriers> <mode> <skater> <you> server to client
+//   input <turn> <courier> <command>             client to server
+//   turn <turn> <courier> <checksum> <command>   server to every client
+//   desync <turn> <checksum>                     client to server
+// with commands "move <dx> <dy> <strength>", "trick <name>" and "chat"
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Join { version: u32, name: String },
+    // why the server won't take a join, the connection is closed after
+    Refused(String),
+    // the run to play and which courier in it is the client's
+    MapSeed { setup: RunSetup, courier: usize },
+    // what the courier does on turn, the number of turns played before it
+    Input { turn: u64, courier: usize, command: Command },
+    // a turn the server played, with the checksum of its run afterwards
+    TurnResult { turn: u64, courier: usize, command: Command, checksum: u64 },
+    // the client's run after turn doesn't match the server's checksum
+    Desync { turn: u64, checksum: u64 },
+}
+
+impl Command {
+    pub fn to_words(&self) -> String {
+        match self {
+            Command::Move((dx, dy), strength) => format!("move {} {} {}", dx, dy, strength),
+            Command::Trick(trick) => format!("trick {}", trick_name(*trick)),
+            Command::Chat => "chat".to_string(),
+        }
+    }
+
+    pub fn from_words(words: &[&str]) -> Option<Command> {
+        match words {
+            ["move", dx, dy, strength] => {
+                let direction = (dx.parse::<i32>().ok()?, dy.parse::<i32>().ok()?);
+                if direction.0.abs() > 1 || direction.1.abs() > 1 {
+                    return None;
+                }
+                let strength = strength.parse::<f32>().ok()?;
+                if !(0.0..=1.0).contains(&strength) {
+                    return None;
+                }
+                Some(Command::Move(direction, strength))
+            }
+            ["trick", name] => trick_from_name(name).map(Command::Trick),
+            ["chat"] => Some(Command::Chat),
+            _ => None,
+        }
+    }
+}
+
+impl Message {
+    pub fn to_line(&self) -> String {
+        match self {
+            Message::Join { version, name } => format!("join {} {}", version, name),
+            Message::Refused(reason) => format!("refused {}", reason),
+            Message::MapSeed { setup, courier } => format!(
+                "seed {} {} {} {} {}",
+                setup.seed, setup.couriers, setup.mode, setup.skater, courier
+            ),
+            Message::Input {
+                turn,
+                courier,
+                command,
+            } => format!("input {} {} {}", turn, courier, command.to_words()),
+            Message::TurnResult {
+                turn,
+                courier,
+                command,
+                checksum,
+            } => format!(
+                "turn {} {} {} {}",
+                turn,
+                courier,
+                checksum,
+                command.to_words()
+            ),
+            Message::Desync { turn, checksum } => format!("desync {} {}", turn, checksum),
+        }
+    }
+
+    // None for lines that aren't a message of this version
+    pub fn from_line(line: &str) -> Option<Message> {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        if words[0] == "join" && words.len() > 2 {
+            Some(Message::Join {
+                version: words[1].parse().ok()?,
+                name: words[2..].join(" "),
+            })
+        } else if words[0] == "refused" {
+            Some(Message::Refused(words[1..].join(" ")))
+        } else if words[0] == "seed" && words.len() == 6 {
+            let setup = RunSetup {
+                seed: words[1].parse().ok()?,
+                couriers: words[2].parse().ok()?,
+                mode: words[3].parse().ok()?,
+                skater: words[4].parse().ok()?,
+            };
+            let courier = words[5].parse().ok()?;
+            if courier >= setup.couriers {
+                return None;
+            }
+            Some(Message::MapSeed { setup, courier })
+        } else if words[0] == "input" && words.len() > 3 {
+            Some(Message::Input {
+                turn: words[1].parse().ok()?,
+                courier: words[2].parse().ok()?,
+                command: Command::from_words(&words[3..])?,
+            })
+        } else if words[0] == "turn" && words.len() > 4 {
+            Some(Message::TurnResult {
+                turn: words[1].parse().ok()?,
+                courier: words[2].parse().ok()?,
+                checksum: words[3].parse().ok()?,
+                command: Command::from_words(&words[4..])?,
+            })
+        } else if words[0] == "desync" && words.len() == 3 {
+            Some(Message::Desync {
+                turn: words[1].parse().ok()?,
+                checksum: words[2].parse().ok()?,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+fn trick_name(trick: Trick) -> &'static str {
+    match trick {
+        Trick::Ollie => "ollie",
+        Trick::Manual => "manual",
+        Trick::Bail => "bail",
+    }
+}
+
+fn trick_from_name(name: &str) -> Option<Trick> {
+    [Trick::Ollie, Trick::Manual, Trick::Bail]
+        .into_iter()
+        .find(|trick| trick_name(*trick) == name)
+}
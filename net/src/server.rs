@@ -0,0 +1,228 @@
+use crate::connection::Connection;
+use crate::lockstep::Lockstep;
+use crate::protocol::{Command, Message, RunSetup, PROTOCOL_VERSION};
+
+// what happened during a poll, for the host to show
+#[derive(Clone, Debug, PartialEq)]
+pub enum ServerEvent {
+    Joined { name: String, courier: usize },
+    Refused { name: String, reason: String },
+    Started,
+    Turn { turn: u64, courier: usize },
+    Desync { courier: usize, turn: u64 },
+    // a connection closed, with the courier it played if it had joined
+    Left { name: String, courier: Option<usize> },
+    RunOver,
+}
+
+struct Peer {
+    connection: Box<dyn Connection>,
+    name: String,
+    courier: Option<usize>,
+    // dropped at the end of the poll
+    gone: bool,
+}
+
+// Hosts one run. Couriers join until there are as many as the setup asks
+// for, then everyone starts on the same seed. From then on the server
+// takes each turn from the courier whose turn it is, plays it on its own
+// copy and sends it on to everyone with the checksum of the result
+pub struct Server<S: Lockstep> {
+    pub run: S,
+    setup: RunSetup,
+    peers: Vec<Peer>,
+    // couriers handed out so far, in the order they joined
+    joined: usize,
+    // turns played, which is the number the next input has to carry
+    turn: u64,
+    started: bool,
+    over: bool,
+}
+
+impl<S: Lockstep> Server<S> {
+    pub fn new(run: S, setup: RunSetup) -> Self {
+        Server {
+            run,
+            setup,
+            peers: Vec::new(),
+            joined: 0,
+            turn: 0,
+            started: false,
+            over: false,
+        }
+    }
+}
+
+impl<S: Lockstep> Server<S> {
+    pub fn setup(&self) -> &RunSetup {
+        &self.setup
+    }
+
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    // couriers with a player still connected
+    pub fn connected(&self) -> usize {
+        self.peers.iter().filter(|p| p.courier.is_some()).count()
+    }
+
+    // a new connection, which has to send a join before anything else
+    pub fn add_connection(&mut self, connection: Box<dyn Connection>) {
+        self.peers.push(Peer {
+            connection,
+            name: String::new(),
+            courier: None,
+            gone: false,
+        });
+    }
+
+    // handles everything that has arrived since the last poll
+    pub fn poll(&mut self) -> Vec<ServerEvent> {
+        let mut events = Vec::new();
+
+        for index in 0..self.peers.len() {
+            while !self.peers[index].gone {
+                match self.peers[index].connection.receive() {
+                    Ok(Some(message)) => self.handle(index, message, &mut events),
+                    Ok(None) => break,
+                    Err(_) => self.peers[index].gone = true,
+                }
+            }
+        }
+
+        for peer in self.peers.iter().filter(|p| p.gone && !p.name.is_empty()) {
+            events.push(ServerEvent::Left {
+                name: peer.name.clone(),
+                courier: peer.courier,
+            });
+        }
+        self.peers.retain(|p| !p.gone);
+
+        if !self.started && self.joined == self.setup.couriers {
+            self.start(&mut events);
+        }
+        self.wait_for_absent(&mut events);
+
+        events
+    }
+
+    fn handle(&mut self, index: usize, message: Message, events: &mut Vec<ServerEvent>) {
+        let courier = self.peers[index].courier;
+        match message {
+            Message::Join { version, name } if courier.is_none() => {
+                let refusal = if version != PROTOCOL_VERSION {
+                    Some(format!("the server speaks version {}", PROTOCOL_VERSION))
+                } else if self.started || self.joined >= self.setup.couriers {
+                    Some("the run is full".to_string())
+                } else {
+                    None
+                };
+
+                let peer = &mut self.peers[index];
+                match refusal {
+                    Some(reason) => {
+                        let _ = peer.connection.send(&Message::Refused(reason.clone()));
+                        peer.gone = true;
+                        events.push(ServerEvent::Refused { name, reason });
+                    }
+                    None => {
+                        peer.name = name.clone();
+                        peer.courier = Some(self.joined);
+                        events.push(ServerEvent::Joined {
+                            name,
+                            courier: self.joined,
+                        });
+                        self.joined += 1;
+                    }
+                }
+            }
+            // inputs for turns that have been played or aren't theirs are dropped
+            Message::Input {
+                turn,
+                courier: moved,
+                command,
+            } if self.started
+                && !self.over
+                && turn == self.turn
+                && courier == Some(moved)
+                && self.run.turn_courier() == Some(moved) =>
+            {
+                self.play(moved, command, events);
+            }
+            Message::Desync { turn, .. } => {
+                if let Some(courier) = courier {
+                    events.push(ServerEvent::Desync { courier, turn });
+                }
+                // their copy is no good any more, they wait from now on
+                self.peers[index].gone = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, events: &mut Vec<ServerEvent>) {
+        self.run.start(&self.setup);
+        self.started = true;
+        for peer in self.peers.iter_mut() {
+            if let Some(courier) = peer.courier {
+                let message = Message::MapSeed {
+                    setup: self.setup,
+                    courier,
+                };
+                if peer.connection.send(&message).is_err() {
+                    peer.gone = true;
+                }
+            }
+        }
+        events.push(ServerEvent::Started);
+    }
+
+    fn play(&mut self, courier: usize, command: Command, events: &mut Vec<ServerEvent>) {
+        self.run.apply(courier, command);
+        let result = Message::TurnResult {
+            turn: self.turn,
+            courier,
+            command,
+            checksum: self.run.checksum(),
+        };
+        for peer in self.peers.iter_mut().filter(|p| p.courier.is_some()) {
+            if peer.connection.send(&result).is_err() {
+                peer.gone = true;
+            }
+        }
+        events.push(ServerEvent::Turn {
+            turn: self.turn,
+            courier,
+        });
+        self.turn += 1;
+
+        if self.run.turn_courier().is_none() {
+            self.over = true;
+            events.push(ServerEvent::RunOver);
+        }
+    }
+
+    // couriers whose player left wait on their turns, so the others can
+    // go on. With nobody left there's nobody to wait for
+    fn wait_for_absent(&mut self, events: &mut Vec<ServerEvent>) {
+        while self.started && !self.over && self.peers.iter().any(|p| p.courier.is_some()) {
+            let courier = match self.run.turn_courier() {
+                None => break,
+                Some(courier) => courier,
+            };
+            if self.peers.iter().any(|p| p.courier == Some(courier)) {
+                break;
+            }
+            self.play(courier, Command::Move((0, 0), 0.0), events);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::connection::{Connection, LineBuffer, NetError};
+use crate::protocol::Message;
+
+// Messages as lines of text over a TCP stream, for native builds
+pub struct TcpConnection {
+    stream: TcpStream,
+    incoming: LineBuffer,
+    // what the socket didn't take yet
+    outgoing: Vec<u8>,
+}
+
+impl TcpConnection {
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<Self, NetError> {
+        let stream = TcpStream::connect(address).map_err(io_error)?;
+        TcpConnection::from_stream(stream)
+    }
+
+    pub fn from_stream(stream: TcpStream) -> Result<Self, NetError> {
+        stream.set_nonblocking(true).map_err(io_error)?;
+        // turns are a line each, don't hold them back
+        stream.set_nodelay(true).map_err(io_error)?;
+        Ok(TcpConnection {
+            stream,
+            incoming: LineBuffer::new(),
+            outgoing: Vec::new(),
+        })
+    }
+}
+
+impl TcpConnection {
+    fn flush(&mut self) -> Result<(), NetError> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(NetError::Closed),
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(io_error(e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Connection for TcpConnection {
+    fn send(&mut self, message: &Message) -> Result<(), NetError> {
+        self.outgoing.extend_from_slice(message.to_line().as_bytes());
+        self.outgoing.push(b'\n');
+        self.flush()
+    }
+
+    fn receive(&mut self) -> Result<Option<Message>, NetError> {
+        self.flush()?;
+        if let Some(message) = self.incoming.next_message() {
+            return Ok(Some(message));
+        }
+
+        let mut buffer = [0u8; 1024];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err(NetError::Closed),
+                Ok(n) => {
+                    self.incoming.push(&buffer[..n]);
+                    if let Some(message) = self.incoming.next_message() {
+                        return Ok(Some(message));
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(None),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(io_error(e)),
+            }
+        }
+    }
+}
+
+pub fn io_error(error: std::io::Error) -> NetError {
+    NetError::Io(error.to_string())
+}
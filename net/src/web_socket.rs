@@ -0,0 +1,220 @@
+// Messages as WebSocket text frames, one message each. The web build can
+// only reach the server this way, native builds use it to host web
+// couriers (see listener.rs) and to test it
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::io::ErrorKind;
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    use tungstenite::client::IntoClientRequest;
+    use tungstenite::{Error, WebSocket};
+
+    use crate::connection::{Connection, NetError};
+    use crate::protocol::Message;
+    use crate::tcp::io_error;
+
+    // how long a peer gets to finish the opening handshake
+    const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub struct WebSocketConnection {
+        socket: WebSocket<TcpStream>,
+    }
+
+    impl WebSocketConnection {
+        // url is like ws://localhost:7879
+        pub fn connect(url: &str) -> Result<Self, NetError> {
+            let request = url.into_client_request().map_err(ws_error)?;
+            let host = request.uri().host().unwrap_or("localhost").to_string();
+            let port = request.uri().port_u16().unwrap_or(80);
+            let stream = TcpStream::connect((host.as_str(), port)).map_err(io_error)?;
+            stream
+                .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+                .map_err(io_error)?;
+
+            let (socket, _) = tungstenite::client(request, stream)
+                .map_err(|e| NetError::Io(e.to_string()))?;
+            WebSocketConnection::ready(socket)
+        }
+
+        // the server's side of a connection that has just been accepted.
+        // Waits for the handshake, Listener runs it off the server's loop
+        pub fn accept(stream: TcpStream) -> Result<Self, NetError> {
+            stream
+                .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+                .map_err(io_error)?;
+            let socket = tungstenite::accept(stream).map_err(|e| NetError::Io(e.to_string()))?;
+            WebSocketConnection::ready(socket)
+        }
+
+        fn ready(socket: WebSocket<TcpStream>) -> Result<Self, NetError> {
+            socket.get_ref().set_read_timeout(None).map_err(io_error)?;
+            socket.get_ref().set_nonblocking(true).map_err(io_error)?;
+            socket.get_ref().set_nodelay(true).map_err(io_error)?;
+            Ok(WebSocketConnection { socket })
+        }
+    }
+
+    impl Connection for WebSocketConnection {
+        fn send(&mut self, message: &Message) -> Result<(), NetError> {
+            let frame = tungstenite::Message::Text(message.to_line());
+            match self.socket.send(frame) {
+                Ok(()) => Ok(()),
+                // queued, it goes out with the next send or receive
+                Err(Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => Ok(()),
+                Err(e) => Err(ws_error(e)),
+            }
+        }
+
+        fn receive(&mut self) -> Result<Option<Message>, NetError> {
+            loop {
+                match self.socket.read() {
+                    Ok(tungstenite::Message::Text(line)) => {
+                        if let Some(message) = Message::from_line(&line) {
+                            return Ok(Some(message));
+                        }
+                    }
+                    Ok(tungstenite::Message::Close(_)) => return Err(NetError::Closed),
+                    // pings are answered by tungstenite
+                    Ok(_) => {}
+                    Err(Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => {
+                        return match self.socket.flush() {
+                            Ok(()) => Ok(None),
+                            Err(Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => Ok(None),
+                            Err(e) => Err(ws_error(e)),
+                        };
+                    }
+                    Err(e) => return Err(ws_error(e)),
+                }
+            }
+        }
+    }
+
+    fn ws_error(error: Error) -> NetError {
+        match error {
+            Error::ConnectionClosed | Error::AlreadyClosed => NetError::Closed,
+            error => NetError::Io(error.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::{Event, MessageEvent, WebSocket};
+
+    use crate::connection::{Connection, NetError};
+    use crate::protocol::Message;
+
+    // filled in by the browser's callbacks between frames
+    struct Shared {
+        incoming: VecDeque<String>,
+        open: bool,
+        closed: bool,
+    }
+
+    pub struct WebSocketConnection {
+        socket: WebSocket,
+        shared: Rc<RefCell<Shared>>,
+        // sent before the socket opened
+        outgoing: Vec<String>,
+        // kept alive for as long as the socket can call them
+        _on_message: Closure<dyn FnMut(MessageEvent)>,
+        _on_open: Closure<dyn FnMut(Event)>,
+        _on_close: Closure<dyn FnMut(Event)>,
+    }
+
+    impl WebSocketConnection {
+        // url is like ws://localhost:7879
+        pub fn connect(url: &str) -> Result<Self, NetError> {
+            let socket = WebSocket::new(url).map_err(|e| NetError::Io(format!("{:?}", e)))?;
+            let shared = Rc::new(RefCell::new(Shared {
+                incoming: VecDeque::new(),
+                open: false,
+                closed: false,
+            }));
+
+            let on_message = {
+                let shared = shared.clone();
+                Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                    if let Some(line) = event.data().as_string() {
+                        shared.borrow_mut().incoming.push_back(line);
+                    }
+                })
+            };
+            let on_open = {
+                let shared = shared.clone();
+                Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+                    shared.borrow_mut().open = true;
+                })
+            };
+            let on_close = {
+                let shared = shared.clone();
+                Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+                    shared.borrow_mut().closed = true;
+                })
+            };
+            socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+            socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+            socket.set_onerror(Some(on_close.as_ref().unchecked_ref()));
+
+            Ok(WebSocketConnection {
+                socket,
+                shared,
+                outgoing: Vec::new(),
+                _on_message: on_message,
+                _on_open: on_open,
+                _on_close: on_close,
+            })
+        }
+    }
+
+    impl WebSocketConnection {
+        fn flush(&mut self) -> Result<(), NetError> {
+            if !self.shared.borrow().open {
+                return Ok(());
+            }
+            for line in self.outgoing.drain(..) {
+                self.socket
+                    .send_with_str(&line)
+                    .map_err(|e| NetError::Io(format!("{:?}", e)))?;
+            }
+            Ok(())
+        }
+    }
+
+    impl Connection for WebSocketConnection {
+        fn send(&mut self, message: &Message) -> Result<(), NetError> {
+            if self.shared.borrow().closed {
+                return Err(NetError::Closed);
+            }
+            self.outgoing.push(message.to_line());
+            self.flush()
+        }
+
+        fn receive(&mut self) -> Result<Option<Message>, NetError> {
+            self.flush()?;
+            loop {
+                let line = self.shared.borrow_mut().incoming.pop_front();
+                match line {
+                    Some(line) => {
+                        if let Some(message) = Message::from_line(&line) {
+                            return Ok(Some(message));
+                        }
+                    }
+                    None if self.shared.borrow().closed => return Err(NetError::Closed),
+                    None => return Ok(None),
+                }
+            }
+        }
+    }
+}
+
+pub use backend::WebSocketConnection;
@@ -0,0 +1,401 @@
+// A server and its clients talking over real sockets on this machine,
+// each with their own copy of a small run of couriers skating a seeded map
+
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use controller::player_controller::PlayerController;
+use model::map_gen;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use net::checksum::Checksum;
+use net::client::{Client, ClientEvent};
+use net::connection::{Connection, NetError};
+use net::listener::{Listener, Transport};
+use net::lockstep::Lockstep;
+use net::protocol::{Command, Message, RunSetup, PROTOCOL_VERSION};
+use net::server::{Server, ServerEvent};
+use net::tcp::TcpConnection;
+use net::web_socket::WebSocketConnection;
+use rltk::RandomNumberGenerator;
+
+// turns in a run before it's over
+const RUN_TURNS: u64 = 12;
+
+struct Run {
+    controller: PlayerController,
+    table: ObstacleTable,
+    couriers: Vec<Player>,
+    next: usize,
+    turns_left: u64,
+    // this copy plays every turn a little differently
+    drifts: bool,
+}
+
+impl Run {
+    fn new(drifts: bool) -> Self {
+        rltk::embedding::EMBED.lock().add_resource(
+            "raws/model.txt".to_string(),
+            include_bytes!("../../game/src/raws/raws/model.txt"),
+        );
+        Run {
+            controller: PlayerController::new(),
+            table: ObstacleTable::new(30, 20),
+            couriers: Vec::new(),
+            next: 0,
+            turns_left: 0,
+            drifts,
+        }
+    }
+}
+
+impl Lockstep for Run {
+    fn start(&mut self, setup: &RunSetup) {
+        let mut rng = RandomNumberGenerator::seeded(setup.seed);
+        self.table = ObstacleTable::new(30, 20);
+        map_gen::voronoi_mapgen(&mut self.table, &mut rng);
+        self.table.update_platforms();
+        self.couriers = (0..setup.couriers)
+            .map(|_| {
+                let (x, y) = self.table.platforms[rng.range(0, self.table.platforms.len())];
+                Player::new(x, y)
+            })
+            .collect();
+        self.next = 0;
+        self.turns_left = RUN_TURNS;
+    }
+
+    fn turn_courier(&self) -> Option<usize> {
+        match self.turns_left {
+            0 => None,
+            _ => Some(self.next),
+        }
+    }
+
+    fn apply(&mut self, courier: usize, command: Command) {
+        if self.turn_courier() != Some(courier) {
+            return;
+        }
+        let player = self.couriers[courier];
+        let mut moved = match command {
+            Command::Move(direction, strength) => {
+                self.controller
+                    .move_player_analog(&self.table, &player, direction, strength)
+            }
+            Command::Trick(trick) => self.controller.perform_trick(&self.table, &player, trick),
+            Command::Chat => player,
+        };
+        if self.drifts {
+            moved.time += 1.0;
+        }
+        self.couriers[courier] = moved;
+        self.next = (self.next + 1) % self.couriers.len();
+        self.turns_left -= 1;
+    }
+
+    fn checksum(&self) -> u64 {
+        let mut sum = Checksum::new();
+        for player in self.couriers.iter() {
+            sum.add_i32(player.x());
+            sum.add_i32(player.y());
+            sum.add_f32(player.speed.0);
+            sum.add_f32(player.speed.1);
+            sum.add_f32(player.time);
+        }
+        sum.add_u64(self.turns_left);
+        sum.finish()
+    }
+}
+
+struct Host {
+    server: Server<Run>,
+    tcp: Listener,
+    web: Listener,
+    events: Vec<ServerEvent>,
+}
+
+impl Host {
+    fn new(couriers: usize) -> Self {
+        let setup = RunSetup {
+            seed: 99,
+            couriers,
+            mode: 0,
+            skater: 0,
+        };
+        Host {
+            server: Server::new(Run::new(false), setup),
+            tcp: Listener::bind("127.0.0.1:0", Transport::Tcp).unwrap(),
+            web: Listener::bind("127.0.0.1:0", Transport::WebSocket).unwrap(),
+            events: Vec::new(),
+        }
+    }
+
+    fn join_tcp(&mut self) -> Box<dyn Connection> {
+        let connection = TcpConnection::connect(self.tcp.local_addr().unwrap()).unwrap();
+        self.accept(Transport::Tcp);
+        Box::new(connection)
+    }
+
+    // the handshake waits for the server, so the client connects on its own thread
+    fn join_web_socket(&mut self) -> Box<dyn Connection> {
+        let url = format!("ws://{}", self.web.local_addr().unwrap());
+        let client = thread::spawn(move || WebSocketConnection::connect(&url).unwrap());
+        self.accept(Transport::WebSocket);
+        Box::new(client.join().unwrap())
+    }
+
+    fn accept(&mut self, transport: Transport) {
+        let listener = match transport {
+            Transport::Tcp => &self.tcp,
+            Transport::WebSocket => &self.web,
+        };
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            if let Some(connection) = listener.accept().unwrap() {
+                return self.server.add_connection(connection);
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        panic!("nobody connected");
+    }
+
+    fn poll(&mut self) {
+        let events = self.server.poll();
+        self.events.extend(events);
+    }
+}
+
+// a client with its own copy of the run
+struct Courier {
+    client: Client,
+    run: Run,
+    events: Vec<ClientEvent>,
+    error: Option<NetError>,
+}
+
+impl Courier {
+    fn join(connection: Box<dyn Connection>, name: &str, drifts: bool) -> Self {
+        Courier {
+            client: Client::join(connection, name).unwrap(),
+            run: Run::new(drifts),
+            events: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn poll(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+        match self.client.poll(&mut self.run) {
+            Ok(events) => self.events.extend(events),
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    // on their turn pushes east, then gently south, then chats
+    fn play(&mut self) {
+        if self.error.is_none() && self.client.can_move(&self.run) {
+            let command = match self.client.turn() % 3 {
+                0 => Command::Move((1, 0), 1.0),
+                1 => Command::Move((0, 1), 0.5),
+                _ => Command::Chat,
+            };
+            self.client.send(command).unwrap();
+        }
+    }
+}
+
+// polls everyone until done says so, failing after a few seconds
+fn pump(host: &mut Host, players: &mut [Courier], done: &dyn Fn(&Host, &[Courier]) -> bool) {
+    let start = Instant::now();
+    while !done(host, players) {
+        assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+        host.poll();
+        for player in players.iter_mut() {
+            player.poll();
+            player.play();
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn couriers_on_both_transports_stay_in_step() {
+    let mut host = Host::new(2);
+    let tcp = host.join_tcp();
+    let web = host.join_web_socket();
+    let mut players = [
+        Courier::join(tcp, "First", false),
+        Courier::join(web, "Second", false),
+    ];
+
+    pump(&mut host, &mut players, &|host, _| host.server.is_over());
+    // the last turn still has to reach them
+    pump(&mut host, &mut players, &|_, players| {
+        players.iter().all(|p| p.client.turn() == RUN_TURNS)
+    });
+
+    assert!(host.events.contains(&ServerEvent::Started));
+    assert!(host.events.contains(&ServerEvent::RunOver));
+    assert_eq!(host.server.turn(), RUN_TURNS);
+    for (courier, player) in players.iter().enumerate() {
+        assert_eq!(player.error, None);
+        assert_eq!(player.client.courier(), Some(courier));
+        assert_eq!(player.events[0], ClientEvent::Started { courier });
+        assert_eq!(player.run.checksum(), host.server.run.checksum());
+        for (ours, theirs) in player.run.couriers.iter().zip(host.server.run.couriers.iter()) {
+            assert_eq!(ours.xy(), theirs.xy());
+        }
+    }
+}
+
+#[test]
+fn a_silent_browser_holds_up_nobody() {
+    let mut host = Host::new(1);
+    let _silent = TcpStream::connect(host.web.local_addr().unwrap()).unwrap();
+    let start = Instant::now();
+    assert!(host.web.accept().unwrap().is_none());
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    let mut players = [Courier::join(host.join_web_socket(), "First", false)];
+    pump(&mut host, &mut players, &|host, _| host.server.is_started());
+    assert_eq!(players[0].client.courier(), Some(0));
+}
+
+#[test]
+fn joins_in_another_version_or_past_the_couriers_wanted_are_refused() {
+    let mut host = Host::new(1);
+    let mut old = host.join_tcp();
+    old.send(&Message::Join {
+        version: PROTOCOL_VERSION + 1,
+        name: "Old".to_string(),
+    })
+    .unwrap();
+    let mut players = [Courier::join(host.join_tcp(), "First", false)];
+    let mut late = Courier::join(host.join_tcp(), "Late", false);
+
+    pump(&mut host, &mut players, &|host, _| host.server.is_started());
+    let start = Instant::now();
+    while late.error.is_none() {
+        assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+        host.poll();
+        late.poll();
+    }
+
+    assert_eq!(
+        late.error,
+        Some(NetError::Refused("the run is full".to_string()))
+    );
+    let refused: Vec<&str> = host
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            ServerEvent::Refused { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(refused, vec!["Old", "Late"]);
+}
+
+#[test]
+fn inputs_out_of_turn_are_dropped() {
+    let mut host = Host::new(2);
+    let first = host.join_tcp();
+    let mut second = host.join_tcp();
+    let mut players = [Courier::join(first, "First", false)];
+    second
+        .send(&Message::Join {
+            version: PROTOCOL_VERSION,
+            name: "Second".to_string(),
+        })
+        .unwrap();
+    pump(&mut host, &mut players, &|host, _| host.server.turn() == 1);
+
+    // an old turn, someone else's courier and a turn to come
+    let inputs = [(0, 1), (1, 0), (2, 1)];
+    for (turn, courier) in inputs {
+        second
+            .send(&Message::Input {
+                turn,
+                courier,
+                command: Command::Chat,
+            })
+            .unwrap();
+    }
+    second
+        .send(&Message::Input {
+            turn: 1,
+            courier: 1,
+            command: Command::Move((-1, 0), 1.0),
+        })
+        .unwrap();
+    pump(&mut host, &mut players, &|host, _| host.server.turn() >= 3);
+    for _ in 0..20 {
+        host.poll();
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let played: Vec<(u64, usize)> = host
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            ServerEvent::Turn { turn, courier } => Some((*turn, *courier)),
+            _ => None,
+        })
+        .collect();
+    // courier 1 moved once, and the run waits for them again after the first courier
+    assert_eq!(played, vec![(0, 0), (1, 1), (2, 0)]);
+    assert_eq!(players[0].run.checksum(), host.server.run.checksum());
+}
+
+#[test]
+fn a_copy_that_drifts_is_caught_and_dropped() {
+    let mut host = Host::new(2);
+    let first = host.join_tcp();
+    let second = host.join_tcp();
+    let mut players = [
+        Courier::join(first, "First", false),
+        Courier::join(second, "Drifter", true),
+    ];
+
+    pump(&mut host, &mut players, &|host, _| {
+        host.events
+            .iter()
+            .any(|event| matches!(event, ServerEvent::Desync { .. }))
+    });
+
+    assert_eq!(players[1].error, Some(NetError::Desync(0)));
+    assert!(host
+        .events
+        .contains(&ServerEvent::Desync { courier: 1, turn: 0 }));
+    assert_eq!(players[0].error, None);
+}
+
+#[test]
+fn couriers_whose_player_left_wait_on_their_turns() {
+    let mut host = Host::new(2);
+    let first = host.join_tcp();
+    let second = host.join_tcp();
+    let mut players = [
+        Courier::join(first, "First", false),
+        Courier::join(second, "Leaver", false),
+    ];
+    pump(&mut host, &mut players, &|host, _| host.server.is_started());
+
+    let [first, leaver] = players;
+    drop(leaver);
+    let mut players = [first];
+    pump(&mut host, &mut players, &|host, _| host.server.is_over());
+
+    assert!(host.events.contains(&ServerEvent::Left {
+        name: "Leaver".to_string(),
+        courier: Some(1),
+    }));
+    assert_eq!(host.server.turn(), RUN_TURNS);
+    pump(&mut host, &mut players, &|_, players| {
+        players[0].client.turn() == RUN_TURNS
+    });
+    assert_eq!(players[0].run.checksum(), host.server.run.checksum());
+}
@@ -0,0 +1,122 @@
+// The lines peers send each other, and the checksums they compare
+
+use model::trick::Trick;
+use net::checksum::Checksum;
+use net::connection::LineBuffer;
+use net::protocol::{Command, Message, RunSetup, PROTOCOL_VERSION};
+
+fn setup() -> RunSetup {
+    RunSetup {
+        seed: 18446744073709551557,
+        couriers: 3,
+        mode: 1,
+        skater: 2,
+    }
+}
+
+#[test]
+fn every_message_survives_a_round_trip() {
+    let messages = [
+        Message::Join {
+            version: PROTOCOL_VERSION,
+            name: "Two Words".to_string(),
+        },
+        Message::Refused("the run is full".to_string()),
+        Message::MapSeed {
+            setup: setup(),
+            courier: 2,
+        },
+        Message::Input {
+            turn: 4,
+            courier: 1,
+            command: Command::Move((-1, 1), 0.35),
+        },
+        Message::Input {
+            turn: 5,
+            courier: 0,
+            command: Command::Chat,
+        },
+        Message::TurnResult {
+            turn: 6,
+            courier: 2,
+            command: Command::Trick(Trick::Manual),
+            checksum: u64::MAX,
+        },
+        Message::Desync {
+            turn: 7,
+            checksum: 12,
+        },
+    ];
+    for message in messages {
+        assert_eq!(Message::from_line(&message.to_line()), Some(message));
+    }
+}
+
+#[test]
+fn lines_that_are_not_messages_are_rejected() {
+    let lines = [
+        "",
+        "hello",
+        "join",
+        "join x Name",
+        "seed 1 2 0 0",
+        // not one of the run's couriers
+        "seed 1 2 0 0 2",
+        "input 1 0 move 2 0 1",
+        "input 1 0 move 1 0 1.5",
+        "input 1 0 move 1 0 -0.5",
+        "input 1 0 trick kickflip",
+        "input -1 0 chat",
+        "turn 1 0 chat",
+        "desync 1",
+    ];
+    for line in lines {
+        assert_eq!(Message::from_line(line), None, "{:?}", line);
+    }
+}
+
+#[test]
+fn line_buffer_waits_for_whole_lines() {
+    let mut buffer = LineBuffer::new();
+    buffer.push(b"input 3 1 ch");
+    assert_eq!(buffer.next_message(), None);
+
+    buffer.push(b"at\nnot a message\ndesync 3 9\n");
+    assert_eq!(
+        buffer.next_message(),
+        Some(Message::Input {
+            turn: 3,
+            courier: 1,
+            command: Command::Chat
+        })
+    );
+    assert_eq!(
+        buffer.next_message(),
+        Some(Message::Desync {
+            turn: 3,
+            checksum: 9
+        })
+    );
+    assert_eq!(buffer.next_message(), None);
+}
+
+#[test]
+fn checksums_depend_on_every_value_and_its_order() {
+    let sum = |values: &[i32]| {
+        let mut checksum = Checksum::new();
+        for value in values {
+            checksum.add_i32(*value);
+        }
+        checksum.finish()
+    };
+    assert_eq!(sum(&[1, 2, 3]), sum(&[1, 2, 3]));
+    assert_ne!(sum(&[1, 2, 3]), sum(&[3, 2, 1]));
+    assert_ne!(sum(&[1, 2, 3]), sum(&[1, 2, 4]));
+
+    // floats go by their bits, so even a sign that compares equal counts
+    let mut a = Checksum::new();
+    a.add_f32(0.0);
+    let mut b = Checksum::new();
+    b.add_f32(-0.0);
+    assert_ne!(a.finish(), b.finish());
+}
@@ -1,103 +1,182 @@
+use std::collections::HashMap;
+
+// identifies an entry in a Heap so its key can be changed or the entry removed
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HeapHandle(u64);
+
+// A binary min-heap. Entries with equal keys come out in the order they were
+// inserted, so anything ordered by it (like turn order) is deterministic.
 pub struct Heap<K, T>
 where
-    K: Clone,
-    K: Copy,
     K: Ord,
-    T: Clone,
-    T: Copy,
 {
-    heap: Vec<(K, T)>,
+    // (key, insertion order, handle, value)
+    heap: Vec<(K, u64, HeapHandle, T)>,
+
+    // handle -> index into heap
+    positions: HashMap<HeapHandle, usize>,
+    next_seq: u64,
 }
 
 impl<K, T> Heap<K, T>
 where
-    K: Clone,
-    K: Copy,
     K: Ord,
-    T: Clone,
-    T: Copy,
 {
     pub fn new() -> Self {
-        Heap { heap: Vec::new() }
+        Heap {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<K, T> Default for Heap<K, T>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Heap::new()
     }
 }
 
 impl<K, T> Heap<K, T>
 where
-    K: Clone,
-    K: Copy,
     K: Ord,
-    T: Clone,
-    T: Copy,
 {
-    pub fn empty(&self) -> bool {
-        return self.heap.len() < 2;
+    pub fn len(&self) -> usize {
+        self.heap.len()
     }
 
-    pub fn insert(&mut self, key: K, val: T) {
-        if self.heap.len() < 2 {
-            if self.heap.len() == 1 {
-                self.heap.remove(0);
-            }
-            self.heap.push((key, val));
-            self.heap.push((key, val));
-        } else {
-            self.heap.push((key, val));
-            let mut index = self.heap.len() - 1;
-            while self.heap[parent(index)].0 > key && index > 1 {
-                self.swap(parent(index), index);
-                index = parent(index);
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    pub fn contains(&self, handle: HeapHandle) -> bool {
+        self.positions.contains_key(&handle)
+    }
+
+    pub fn insert(&mut self, key: K, val: T) -> HeapHandle {
+        let handle = HeapHandle(self.next_seq);
+        self.heap.push((key, self.next_seq, handle, val));
+        self.next_seq += 1;
+
+        let index = self.heap.len() - 1;
+        self.positions.insert(handle, index);
+        self.sift_up(index);
+
+        handle
+    }
+
+    // the smallest key and its value
+    pub fn peek(&self) -> Option<(&K, &T)> {
+        self.heap.first().map(|entry| (&entry.0, &entry.3))
+    }
+
+    pub fn get(&self, handle: HeapHandle) -> Option<(&K, &T)> {
+        let index = *self.positions.get(&handle)?;
+        Some((&self.heap[index].0, &self.heap[index].3))
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        self.extract_min_with_key().map(|(_, val)| val)
+    }
+
+    pub fn extract_min_with_key(&mut self) -> Option<(K, T)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        self.remove_at(0)
+    }
+
+    // lowers the key of an entry. Returns false (and leaves the heap alone)
+    // if the entry is gone or key is larger than its current key
+    pub fn decrease_key(&mut self, handle: HeapHandle, key: K) -> bool {
+        match self.positions.get(&handle) {
+            None => false,
+            Some(&index) => {
+                if key > self.heap[index].0 {
+                    return false;
+                }
+                self.heap[index].0 = key;
+                self.sift_up(index);
+                true
             }
         }
     }
 
-    pub fn extract_min(&mut self) -> T {
-        let l = 1;
-        let r = self.heap.len() - 1;
-        self.swap(l, r);
+    pub fn remove(&mut self, handle: HeapHandle) -> Option<(K, T)> {
+        let index = *self.positions.get(&handle)?;
+        self.remove_at(index)
+    }
 
-        let min = self.heap.remove(r);
+    fn remove_at(&mut self, index: usize) -> Option<(K, T)> {
+        let last = self.heap.len() - 1;
+        self.swap(index, last);
 
-        let mut index = 1;
-        while index < self.heap.len() / 2 {
-            if self.heap[index].0 <= self.heap[left(index)].0
-                || self.heap[index].0 <= self.heap[right(index)].0
-            {
-                break;
+        let (key, _, handle, val) = self.heap.pop()?;
+        self.positions.remove(&handle);
+
+        if index < self.heap.len() {
+            // the entry moved into index may belong above or below it
+            self.sift_up(index);
+            self.sift_down(index);
+        }
+
+        Some((key, val))
+    }
+
+    // true if the entry at l comes out before the entry at r
+    fn before(&self, l: usize, r: usize) -> bool {
+        (&self.heap[l].0, self.heap[l].1) < (&self.heap[r].0, self.heap[r].1)
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 && self.before(index, parent(index)) {
+            self.swap(index, parent(index));
+            index = parent(index);
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut smallest = index;
+            if left(index) < self.heap.len() && self.before(left(index), smallest) {
+                smallest = left(index);
+            }
+            if right(index) < self.heap.len() && self.before(right(index), smallest) {
+                smallest = right(index);
             }
 
-            let l = index;
-            let r: usize;
-            if right(index) >= self.heap.len() {
-                r = left(index);
-            } else if self.heap[left(index)].0 < self.heap[right(index)].0 {
-                r = left(index);
-            } else {
-                r = right(index);
+            if smallest == index {
+                break;
             }
 
-            self.swap(l, r);
-            index = r;
+            self.swap(index, smallest);
+            index = smallest;
         }
-
-        min.1
     }
 
     fn swap(&mut self, l: usize, r: usize) {
-        let temp = self.heap[l];
-        self.heap[l] = self.heap[r];
-        self.heap[r] = temp;
+        self.heap.swap(l, r);
+        self.positions.insert(self.heap[l].2, l);
+        self.positions.insert(self.heap[r].2, r);
     }
 }
 
 fn parent(index: usize) -> usize {
-    index / 2
+    (index - 1) / 2
 }
 
 fn left(index: usize) -> usize {
-    index * 2
+    index * 2 + 1
 }
 
 fn right(index: usize) -> usize {
-    index * 2 + 1
+    index * 2 + 2
 }
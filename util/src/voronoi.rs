@@ -4,9 +4,13 @@ use rltk::RandomNumberGenerator;
 
 use crate::vec_ops;
 
-pub fn voronoi_seeds(n: usize, width: u32, height: u32) -> HashSet<(i32, i32)> {
+pub fn voronoi_seeds(
+    n: usize,
+    width: u32,
+    height: u32,
+    rng: &mut RandomNumberGenerator,
+) -> HashSet<(i32, i32)> {
     let mut seeds = HashSet::new();
-    let mut rng = RandomNumberGenerator::new();
 
     if n >= width as usize * height as usize {
         return seeds;
@@ -26,11 +30,15 @@ pub fn voronoi_membership(
     width: u32,
     height: u32,
 ) -> HashMap<(i32, i32), (i32, i32)> {
-    let mut voronoi_dist = vec![((0, 0), 0.0f32); seeds.len()];
+    // in a fixed order, so ties go the same way every time
+    let mut points: Vec<(i32, i32)> = seeds.iter().copied().collect();
+    points.sort();
+
+    let mut voronoi_dist = vec![((0, 0), 0.0f32); points.len()];
     let mut voronoi_membership = HashMap::new();
     for x in 0..width {
         for y in 0..height {
-            for (seed, point) in seeds.iter().enumerate() {
+            for (seed, point) in points.iter().enumerate() {
                 let dist =
                     vec_ops::magnitude((x as f32 - point.0 as f32, y as f32 - point.1 as f32));
                 voronoi_dist[seed] = (*point, dist);
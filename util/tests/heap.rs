@@ -0,0 +1,121 @@
+use proptest::prelude::*;
+use util::heap::Heap;
+
+// drains the heap, returning (key, value) pairs in the order they came out
+fn drain(heap: &mut Heap<i32, usize>) -> Vec<(i32, usize)> {
+    let mut out = Vec::new();
+    while let Some(entry) = heap.extract_min_with_key() {
+        out.push(entry);
+    }
+    out
+}
+
+#[test]
+fn empty_heap() {
+    let mut heap: Heap<i32, ()> = Heap::new();
+    assert!(heap.is_empty());
+    assert_eq!(heap.len(), 0);
+    assert!(heap.peek().is_none());
+    assert!(heap.extract_min().is_none());
+}
+
+#[test]
+fn single_entry() {
+    let mut heap = Heap::new();
+    heap.insert(3, "a");
+    assert!(!heap.is_empty());
+    assert_eq!(heap.len(), 1);
+    assert_eq!(heap.peek(), Some((&3, &"a")));
+    assert_eq!(heap.extract_min(), Some("a"));
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn equal_keys_in_insertion_order() {
+    let mut heap = Heap::new();
+    for val in 0..10 {
+        heap.insert(1, val);
+    }
+    heap.insert(0, 10);
+    assert_eq!(heap.extract_min(), Some(10));
+    for val in 0..10 {
+        assert_eq!(heap.extract_min(), Some(val));
+    }
+}
+
+#[test]
+fn decrease_key_moves_entry_forward() {
+    let mut heap = Heap::new();
+    heap.insert(5, 'a');
+    let b = heap.insert(10, 'b');
+    heap.insert(7, 'c');
+
+    assert!(heap.decrease_key(b, 1));
+    assert!(!heap.decrease_key(b, 2));
+    assert_eq!(heap.get(b), Some((&1, &'b')));
+    assert_eq!(heap.extract_min(), Some('b'));
+    assert!(!heap.contains(b));
+    assert!(!heap.decrease_key(b, 0));
+}
+
+proptest! {
+    #[test]
+    fn extracts_in_stable_sorted_order(keys in prop::collection::vec(-50i32..50, 0..200)) {
+        let mut heap = Heap::new();
+        for (val, key) in keys.iter().enumerate() {
+            heap.insert(*key, val);
+        }
+        prop_assert_eq!(heap.len(), keys.len());
+
+        let mut expected: Vec<(i32, usize)> =
+            keys.iter().enumerate().map(|(val, key)| (*key, val)).collect();
+        expected.sort();
+
+        prop_assert_eq!(drain(&mut heap), expected);
+    }
+
+    #[test]
+    fn peek_is_always_the_minimum(keys in prop::collection::vec(any::<i32>(), 1..100)) {
+        let mut heap = Heap::new();
+        for (val, key) in keys.iter().enumerate() {
+            heap.insert(*key, val);
+            let min = keys[..=val].iter().min().unwrap();
+            prop_assert_eq!(heap.peek().map(|(k, _)| *k), Some(*min));
+        }
+    }
+
+    #[test]
+    fn decrease_and_remove_keep_order(
+        keys in prop::collection::vec(0i32..100, 1..100),
+        ops in prop::collection::vec((any::<prop::sample::Index>(), 0i32..100, any::<bool>()), 0..50),
+    ) {
+        let mut heap = Heap::new();
+        let mut handles = Vec::new();
+        let mut model: Vec<Option<(i32, usize)>> = Vec::new();
+        for (val, key) in keys.iter().enumerate() {
+            handles.push(heap.insert(*key, val));
+            model.push(Some((*key, val)));
+        }
+
+        for (index, key, remove) in ops.iter() {
+            let target = index.index(handles.len());
+            if *remove {
+                let removed = heap.remove(handles[target]);
+                prop_assert_eq!(removed, model[target]);
+                model[target] = None;
+            } else if let Some((old, val)) = model[target] {
+                let lowered = heap.decrease_key(handles[target], *key);
+                prop_assert_eq!(lowered, *key <= old);
+                if lowered {
+                    model[target] = Some((*key, val));
+                }
+            }
+        }
+
+        // ties stay in insertion order, which is the value here
+        let mut expected: Vec<(i32, usize)> = model.iter().flatten().cloned().collect();
+        expected.sort();
+
+        prop_assert_eq!(drain(&mut heap), expected);
+    }
+}
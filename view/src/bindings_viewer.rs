@@ -0,0 +1,127 @@
+use super::canvas::Canvas;
+use controller::bindings::{Action, Bindings};
+use rltk::RGB;
+
+// what the next key press does on the rebinding screen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Capture {
+    // becomes the only key of the selected action
+    Replace,
+    // is added to the keys of the selected action
+    Add,
+}
+
+// Lists every action with its keys and lets the player pick one to change
+pub struct BindingsViewer {
+    selected: usize,
+    pub capture: Option<Capture>,
+
+    // whose keys are shown, out of how many couriers are playing
+    pub courier: usize,
+    pub couriers: usize,
+
+    // result of the last change, like a conflict
+    pub message: Option<(String, RGB)>,
+}
+
+impl BindingsViewer {
+    pub fn new() -> Self {
+        BindingsViewer {
+            selected: 0,
+            capture: None,
+            courier: 0,
+            couriers: 1,
+            message: None,
+        }
+    }
+}
+
+impl Default for BindingsViewer {
+    fn default() -> Self {
+        BindingsViewer::new()
+    }
+}
+
+impl BindingsViewer {
+    pub fn selected(&self) -> Action {
+        Action::ALL[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % Action::ALL.len();
+    }
+
+    // the next courier's keys, back to the first after the last
+    pub fn next_courier(&mut self) {
+        self.courier = (self.courier + 1) % self.couriers.max(1);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + Action::ALL.len() - 1) % Action::ALL.len();
+    }
+
+    pub fn draw(&self, ctx: &mut dyn Canvas, bindings: &Bindings, _width: u32, height: u32) {
+        let black = RGB::named(rltk::BLACK);
+        let title = if self.couriers > 1 {
+            format!("Key Bindings: Courier {} (Tab for the next)", self.courier + 1)
+        } else {
+            "Key Bindings".to_string()
+        };
+        ctx.print_color_centered(0, RGB::named(rltk::YELLOW), black, &title);
+
+        let mut sc_y = 1;
+        let mut section = "";
+        for (index, action) in Action::ALL.iter().enumerate() {
+            if action.section() != section {
+                section = action.section();
+                sc_y += 1;
+                ctx.print_color(1, sc_y, RGB::named(rltk::GRAY), black, section);
+                sc_y += 1;
+            }
+
+            let (fg, bg) = if index == self.selected {
+                (black, RGB::named(rltk::WHITE))
+            } else {
+                (RGB::named(rltk::WHITE), black)
+            };
+            let line = format!(
+                "{:<39} {}",
+                action.description(),
+                bindings.describe(*action)
+            );
+            ctx.print_color(3, sc_y, fg, bg, &line);
+            sc_y += 1;
+        }
+
+        // left over from a bindings file that binds a key twice
+        for (key, first, second) in bindings.conflicts() {
+            sc_y += 1;
+            ctx.print_color(
+                1,
+                sc_y,
+                RGB::named(rltk::ORANGE),
+                black,
+                &format!(
+                    "{} is bound to both {} and {}",
+                    controller::bindings::key_label(key),
+                    first.description(),
+                    second.description()
+                ),
+            );
+        }
+
+        if let Some((message, color)) = &self.message {
+            ctx.print_color(1, height as i32 - 3, *color, black, message);
+        }
+
+        let footer = match self.capture {
+            Some(_) => format!(
+                "Press a key for {}, Esc to cancel",
+                self.selected().description()
+            ),
+            None => "Enter replace, A add a key, Backspace clear, R defaults, Esc to Return"
+                .to_string(),
+        };
+        ctx.print(1, height as i32 - 1, &footer);
+    }
+}
@@ -0,0 +1,101 @@
+// Decides which part of the ObstacleTable the main view shows.
+// The camera only moves once the player (pushed ahead in the direction
+// they are moving) leaves a box around its center, so the view doesn't
+// shake with every step. Maps smaller than the view are centered in it.
+pub struct Camera {
+    // map coordinates at the center of the view
+    center: (i32, i32),
+
+    // manual offset, cleared when the player moves
+    pan: (i32, i32),
+
+    // half size of the box the player can move in without moving the camera
+    pub dead_zone: (i32, i32),
+
+    // tiles to look ahead per unit of speed
+    pub look_ahead: f32,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Camera {
+            center: (0, 0),
+            pan: (0, 0),
+            dead_zone: (6, 4),
+            look_ahead: 4.0,
+        }
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera::new()
+    }
+}
+
+impl Camera {
+    // moves the center as little as possible to keep the
+    // point ahead of target inside the dead zone
+    pub fn follow(&mut self, target: (i32, i32), speed: (f32, f32)) {
+        let ahead = (
+            target.0 + (speed.0 * self.look_ahead).round() as i32,
+            target.1 + (speed.1 * self.look_ahead).round() as i32,
+        );
+
+        self.center.0 = follow_axis(self.center.0, ahead.0, self.dead_zone.0);
+        self.center.1 = follow_axis(self.center.1, ahead.1, self.dead_zone.1);
+    }
+
+    // jump straight to target, e.g. after a respawn
+    pub fn snap_to(&mut self, target: (i32, i32)) {
+        self.center = target;
+        self.pan = (0, 0);
+    }
+
+    // moves the view by (dx, dy), no further than the edges of a map of map_size
+    pub fn pan(&mut self, dx: i32, dy: i32, map_size: (u32, u32)) {
+        self.pan.0 = pan_axis(self.center.0, self.pan.0 + dx, map_size.0);
+        self.pan.1 = pan_axis(self.center.1, self.pan.1 + dy, map_size.1);
+    }
+
+    pub fn reset_pan(&mut self) {
+        self.pan = (0, 0);
+    }
+
+    pub fn is_panned(&self) -> bool {
+        self.pan != (0, 0)
+    }
+
+    // map coordinates of the top left tile of a view of view_size
+    // on a map of map_size. May be negative when the map is smaller than the view
+    pub fn top_left(&self, view_size: (u32, u32), map_size: (u32, u32)) -> (i32, i32) {
+        (
+            top_left_axis(self.center.0 + self.pan.0, view_size.0, map_size.0),
+            top_left_axis(self.center.1 + self.pan.1, view_size.1, map_size.1),
+        )
+    }
+}
+
+fn follow_axis(center: i32, target: i32, dead_zone: i32) -> i32 {
+    if target > center + dead_zone {
+        target - dead_zone
+    } else if target < center - dead_zone {
+        target + dead_zone
+    } else {
+        center
+    }
+}
+
+fn pan_axis(center: i32, pan: i32, map: u32) -> i32 {
+    (center + pan).clamp(0, (map as i32 - 1).max(0)) - center
+}
+
+fn top_left_axis(center: i32, view: u32, map: u32) -> i32 {
+    let (view, map) = (view as i32, map as i32);
+    if map <= view {
+        // center the whole map
+        -(view - map) / 2
+    } else {
+        (center - view / 2).clamp(0, map - view)
+    }
+}
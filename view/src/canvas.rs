@@ -0,0 +1,98 @@
+use rltk::{FontCharType, RGB};
+
+// Everything the viewers draw with. Implemented for rltk's context, for a plain
+// terminal (see terminal_canvas) and for an in-memory grid (see grid_canvas).
+// Glyphs are codepage 437 like rltk's.
+pub trait Canvas {
+    // size in characters
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+
+    // blank every cell to a space, white on black
+    fn cls(&mut self);
+
+    // draw one glyph, cells outside the canvas are ignored
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: FontCharType);
+
+    fn print_color(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.set(x + i as i32, y, fg, bg, rltk::to_cp437(c));
+        }
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        self.print_color(x, y, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), text);
+    }
+
+    // centered on the whole width, the same way rltk does it
+    fn print_color_centered(&mut self, y: i32, fg: RGB, bg: RGB, text: &str) {
+        let x = (self.width() as i32 / 2) - (text.len() as i32 / 2);
+        self.print_color(x, y, fg, bg, text);
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        self.print_color_centered(y, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), text);
+    }
+
+    // a single lined box with corners at (x, y) and (x + width, y + height),
+    // the inside is cleared
+    fn draw_box(&mut self, x: i32, y: i32, width: i32, height: i32, fg: RGB, bg: RGB) {
+        for by in y..y + height {
+            for bx in x..x + width {
+                self.set(bx, by, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), 32);
+            }
+        }
+
+        self.set(x, y, fg, bg, rltk::to_cp437('┌'));
+        self.set(x + width, y, fg, bg, rltk::to_cp437('┐'));
+        self.set(x, y + height, fg, bg, rltk::to_cp437('└'));
+        self.set(x + width, y + height, fg, bg, rltk::to_cp437('┘'));
+        for bx in x + 1..x + width {
+            self.set(bx, y, fg, bg, rltk::to_cp437('─'));
+            self.set(bx, y + height, fg, bg, rltk::to_cp437('─'));
+        }
+        for by in y + 1..y + height {
+            self.set(x, by, fg, bg, rltk::to_cp437('│'));
+            self.set(x + width, by, fg, bg, rltk::to_cp437('│'));
+        }
+    }
+}
+
+// rltk draws these itself
+impl Canvas for rltk::Rltk {
+    fn width(&self) -> u32 {
+        self.get_char_size().0
+    }
+
+    fn height(&self) -> u32 {
+        self.get_char_size().1
+    }
+
+    fn cls(&mut self) {
+        rltk::Rltk::cls(self);
+    }
+
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: FontCharType) {
+        rltk::Rltk::set(self, x, y, fg, bg, glyph);
+    }
+
+    fn print_color(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, text: &str) {
+        rltk::Rltk::print_color(self, x, y, fg, bg, text);
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        rltk::Rltk::print(self, x, y, text);
+    }
+
+    fn print_color_centered(&mut self, y: i32, fg: RGB, bg: RGB, text: &str) {
+        rltk::Rltk::print_color_centered(self, y, fg, bg, text);
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        rltk::Rltk::print_centered(self, y, text);
+    }
+
+    fn draw_box(&mut self, x: i32, y: i32, width: i32, height: i32, fg: RGB, bg: RGB) {
+        rltk::Rltk::draw_box(self, x, y, width, height, fg, bg);
+    }
+}
@@ -0,0 +1,49 @@
+use controller::ai_controller::AIController;
+use controller::bindings::Bindings;
+use controller::player_controller::PlayerController;
+use controller::sandbox::Sandbox;
+use controller::shop::{Item, Progress};
+
+use model::goal_table::GoalTable;
+use model::high_scores::HighScores;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
+use model::state::ProcState;
+
+// The part of a run the map views are drawn from
+pub struct Scene<'a> {
+    pub table: &'a ObstacleTable,
+    pub goals: &'a GoalTable,
+    pub opponents: &'a [AIController],
+    pub controller: &'a PlayerController,
+}
+
+// Everything the game hands ViewManager to draw one frame
+pub struct Frame<'a> {
+    pub state: ProcState,
+    pub scene: Scene<'a>,
+
+    // the courier the screen is about, their money and where the
+    // package they carry was
+    pub player: &'a Player,
+    pub score: i32,
+    pub package: Option<(i32, i32)>,
+    // every courier as (skater, money) and whose turn it is.
+    // More than one splits the screen
+    pub couriers: &'a [(Player, i32)],
+    pub active: usize,
+
+    pub mode: &'a RunMode,
+    pub last_run: &'a RunStats,
+    pub run_end: Option<RunEnd>,
+    pub standings: &'a [(usize, RunStats)],
+    pub high_scores: &'a HighScores,
+    pub new_high_score: Option<usize>,
+
+    pub bindings: &'a Bindings,
+    pub sandbox: &'a Sandbox,
+    pub shop_items: &'a [Item],
+    pub progress: &'a Progress,
+}
@@ -1,37 +1,100 @@
-use model::obstacle_table::ObstacleTable;
-use model::player::Player;
-use model::player_event::PlayerEvent;
+use super::canvas::Canvas;
+use super::high_scores_viewer;
+use model::high_scores::HighScores;
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
 use rltk::RGB;
 
+// results of the run that just ended, and where it placed. With more
+// than one courier, standings is everyone's run (courier index, run),
+// best first, and run is the winner's
 pub fn game_over_screen(
-    ctx: &mut rltk::Rltk,
-    obs_table: &ObstacleTable,
-    player: &Player,
-    _width: u32,
+    ctx: &mut dyn Canvas,
+    (run, standings): (&RunStats, &[(usize, RunStats)]),
+    mode: &RunMode,
+    end: Option<RunEnd>,
+    (high_scores, new_high_score): (&HighScores, Option<usize>),
+    width: u32,
     height: u32,
 ) {
+    let mut sc_y = 2;
+
+    let (title, color) = match end {
+        Some(RunEnd::OutOfFalls) => (RunEnd::OutOfFalls.title(), RGB::named(rltk::ORANGE)),
+        Some(end) => (end.title(), RGB::named(rltk::GREEN)),
+        None => ("Run Over", RGB::named(rltk::GRAY)),
+    };
+    ctx.print_color_centered(sc_y, color, RGB::named(rltk::BLACK), title);
+    sc_y += 1;
+
     ctx.print_color_centered(
-        (height as i32 / 2) - 1,
-        RGB::named(rltk::GREEN),
+        sc_y,
+        RGB::named(rltk::GRAY),
         RGB::named(rltk::BLACK),
-        "All Packages Delivered",
+        &format!("{}: {}", mode.name(), mode.goal()),
     );
+    sc_y += 2;
+
+    let lines = [
+        ("Packages delivered", format!("{}", run.delivered)),
+        ("Money earned", format!("${}", run.score)),
+        ("Time", format!("{:.1} turns", run.time)),
+        ("Falls", format!("{}", run.n_falls)),
+        ("Distance skated", format!("{:.1} tiles", run.distance)),
+        (
+            "Map",
+            format!("{}x{}, seed {}", run.map_size.0, run.map_size.1, run.seed),
+        ),
+    ];
+    let left = width as i32 / 4;
+    for (name, value) in lines.iter() {
+        ctx.print(left, sc_y, name);
+        ctx.print(width as i32 / 2, sc_y, value);
+        sc_y += 1;
+    }
+    sc_y += 1;
 
-    let mut info = String::new();
-    if let PlayerEvent::GameOver(time) = player.recent_event {
-        info.push_str(&format!(
-            "Score: {}",
-            (((obs_table.width() as f32 * obs_table.height() as f32) / time as f32)
-                * player.n_delivered as f32)
-                .round() as i32
-        ));
-    } else {
-        info.push_str(&format!("You delivered {} packages", player.n_delivered));
+    if standings.len() > 1 {
+        for (place, (courier, run)) in standings.iter().enumerate() {
+            let color = if place == 0 {
+                RGB::named(rltk::YELLOW)
+            } else {
+                RGB::named(rltk::WHITE)
+            };
+            let line = format!(
+                "{}. Courier {:<3} ${:<6} {} delivered, {} falls",
+                place + 1,
+                courier + 1,
+                run.score,
+                run.delivered,
+                run.n_falls
+            );
+            ctx.print_color(left, sc_y, color, RGB::named(rltk::BLACK), &line);
+            sc_y += 1;
+        }
+        sc_y += 1;
     }
-    ctx.print_centered(height as i32 / 2, &info);
 
-    info.clear();
+    if let Some(rank) = new_high_score {
+        ctx.print_color_centered(
+            sc_y,
+            RGB::named(rltk::YELLOW),
+            RGB::named(rltk::BLACK),
+            &format!("New high score, number {}!", rank + 1),
+        );
+    }
+    sc_y += 2;
+
+    high_scores_viewer::draw_high_scores(
+        ctx,
+        sc_y,
+        high_scores,
+        new_high_score,
+        (height as i32 - sc_y - 3).max(0) as usize,
+    );
 
-    info.push_str("Press R to restart. Press Esc to exit.");
-    ctx.print_centered((height as i32 / 2) + 1, &info);
+    ctx.print_centered(
+        height as i32 - 2,
+        "Press R to play again, S for the shop. Press Esc for the menu.",
+    );
 }
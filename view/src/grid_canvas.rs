@@ -0,0 +1,95 @@
+use rltk::{FontCharType, RGB};
+
+use super::canvas::Canvas;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cell {
+    pub glyph: FontCharType,
+    pub fg: RGB,
+    pub bg: RGB,
+}
+
+impl Cell {
+    pub fn blank() -> Self {
+        Cell {
+            glyph: 32,
+            fg: RGB::named(rltk::WHITE),
+            bg: RGB::named(rltk::BLACK),
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        rltk::to_char(self.glyph as u8)
+    }
+}
+
+// Draws into memory instead of a window, so what a viewer
+// draws can be read back (used by the view tests)
+pub struct GridCanvas {
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+}
+
+impl GridCanvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        GridCanvas {
+            width,
+            height,
+            cells: vec![Cell::blank(); width as usize * height as usize],
+        }
+    }
+}
+
+impl GridCanvas {
+    pub fn cell(&self, x: i32, y: i32) -> Option<&Cell> {
+        self.idx(x, y).map(|idx| &self.cells[idx])
+    }
+
+    // one row as text, trailing spaces included
+    pub fn row(&self, y: i32) -> String {
+        (0..self.width as i32)
+            .map(|x| self.cells[self.idx(x, y).unwrap()].to_char())
+            .collect()
+    }
+
+    // the whole grid as text, one line per row with trailing spaces removed
+    pub fn text(&self) -> String {
+        let mut s = String::new();
+        for y in 0..self.height as i32 {
+            s.push_str(self.row(y).trim_end());
+            s.push('\n');
+        }
+        s
+    }
+
+    fn idx(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            None
+        } else {
+            Some(y as usize * self.width as usize + x as usize)
+        }
+    }
+}
+
+impl Canvas for GridCanvas {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn cls(&mut self) {
+        for cell in self.cells.iter_mut() {
+            *cell = Cell::blank();
+        }
+    }
+
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: FontCharType) {
+        if let Some(idx) = self.idx(x, y) {
+            self.cells[idx] = Cell { glyph, fg, bg };
+        }
+    }
+}
@@ -1,56 +1,51 @@
+use super::canvas::Canvas;
+use super::theme::Theme;
+use controller::bindings::{Action, Bindings};
 use rltk::RGB;
 
-pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
-    let mut left_col = Vec::new();
-    let mut right_col = Vec::new();
-
-    left_col.push(String::from("Movement"));
-    right_col.push(String::from(""));
-
-    left_col.push(String::from("Up"));
-    right_col.push(String::from("W or K"));
-
-    left_col.push(String::from("Left"));
-    right_col.push(String::from("A or H"));
-
-    left_col.push(String::from("Down"));
-    right_col.push(String::from("S or J"));
-
-    left_col.push(String::from("Right"));
-    right_col.push(String::from("D or L"));
-
-    left_col.push(String::from("NorthEast"));
-    right_col.push(String::from("E or U"));
-
-    left_col.push(String::from("NorthWest"));
-    right_col.push(String::from("Q or Y"));
-
-    left_col.push(String::from("SouthWest"));
-    right_col.push(String::from("Z or B"));
-
-    left_col.push(String::from("SouthEast"));
-    right_col.push(String::from("C or N"));
-
-    left_col.push(String::from("Wait (don't increase speed or turn)"));
-    right_col.push(String::from("1 or Period"));
-
-    left_col.push(String::from("Messages"));
-    right_col.push(String::from(""));
-
-    left_col.push(String::from("Help Message"));
-    right_col.push(String::from("Semicolon"));
-
-    left_col.push(String::from("Get Information or Give Package"));
-    right_col.push(String::from("G"));
-
-    left_col.push(String::from("Game Functions"));
-    right_col.push(String::from(""));
-
-    left_col.push(String::from("New Game"));
-    right_col.push(String::from("5"));
+pub fn help_screen(
+    ctx: &mut dyn Canvas,
+    theme: &Theme,
+    bindings: &Bindings,
+    width: u32,
+    _height: u32,
+) {
+    let mut left_col: Vec<String> = Vec::new();
+    let mut right_col: Vec<String> = Vec::new();
+
+    // generated from the bindings so it always shows the keys in use
+    let mut section = "";
+    for action in Action::ALL.iter() {
+        if action.section() != section {
+            // moves that aren't bound to a single key
+            if section == "Movement" {
+                left_col.push(String::from("Push at the Other Strength"));
+                right_col.push(String::from("Shift + Movement"));
+                left_col.push(String::from("Push with the Mouse"));
+                right_col.push(String::from("Click a tile next to you"));
+            }
+            section = action.section();
+            left_col.push(String::from(section));
+            right_col.push(String::from(""));
+        }
 
-    left_col.push(String::from("Menu"));
-    right_col.push(String::from("Esc"));
+        // the look directions share a row
+        let label = match action {
+            Action::Pan(..) => "Look Around",
+            _ => action.description(),
+        };
+        let keys = bindings.describe(*action);
+
+        if left_col.last().map(|l| l.as_str()) == Some(label) {
+            if let Some(last) = right_col.last_mut() {
+                last.push(' ');
+                last.push_str(&keys);
+            }
+        } else {
+            left_col.push(String::from(label));
+            right_col.push(keys);
+        }
+    }
 
     //left_col.push(String::from("Exit Game"));
     //right_col.push(String::from("Ctrl+C"));
@@ -59,22 +54,34 @@ pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
     let mut sc_y = 0;
 
     while col < left_col.len() && col < right_col.len() {
+        // rows without a key are section headers, underlined
+        // and set apart from the section before
+        let header = right_col[col].is_empty();
+        if header && col > 0 {
+            sc_y += 1;
+        }
+
         ctx.print(1, sc_y, &left_col[col]);
         ctx.print(width as i32 / 2, sc_y, &right_col[col]);
         sc_y += 1;
-        for x in 0..width {
-            ctx.set(
-                x,
-                sc_y,
-                RGB::named(rltk::DARKGRAY),
-                RGB::named(rltk::BLACK),
-                rltk::to_cp437('─'),
-            );
+
+        if header {
+            for x in 0..width as i32 {
+                ctx.set(
+                    x,
+                    sc_y,
+                    RGB::named(rltk::DARKGRAY),
+                    RGB::named(rltk::BLACK),
+                    rltk::to_cp437('─'),
+                );
+            }
+            sc_y += 1;
         }
-        sc_y += 1;
         col += 1;
     }
 
+    sc_y += 1;
+
     ctx.print_color(
         1,
         sc_y,
@@ -88,13 +95,25 @@ pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
     ctx.print_color(
         1,
         sc_y,
-        RGB::from_u8(0, 255, 0),
+        theme.fall,
         RGB::named(rltk::BLACK),
         "Fall Over or Game Over",
     );
 
     sc_y += 2;
 
+    if theme.danger_glyphs {
+        ctx.print_color(
+            1,
+            sc_y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            "Next to you: ? risky move, ! very risky move, X fall over",
+        );
+
+        sc_y += 2;
+    }
+
     let title = "Balance Gradient: ";
     let ds = "Decrease Balance, ";
     let is = "Increase Balance";
@@ -110,7 +129,7 @@ pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
     ctx.print_color(
         title.chars().count() as i32 + 1,
         sc_y,
-        RGB::from_u8(0, 0, 255),
+        theme.balance_high,
         RGB::named(rltk::BLACK),
         ds,
     );
@@ -118,7 +137,7 @@ pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
     ctx.print_color(
         title.chars().count() as i32 + ds.chars().count() as i32 + 2,
         sc_y,
-        RGB::from_u8(255, 0, 0),
+        theme.balance_low,
         RGB::named(rltk::BLACK),
         is,
     );
@@ -140,10 +159,13 @@ pub fn help_screen(ctx: &mut rltk::Rltk, width: u32, _height: u32) {
         sc_y,
         RGB::named(rltk::GRAY),
         RGB::named(rltk::BLACK),
-        "Press G when near another skater to talk to them",
+        &format!(
+            "Press {} when near another skater to talk to them",
+            bindings.describe(Action::Chat)
+        ),
     );
 
     sc_y += 2;
 
-    ctx.print(1, sc_y, "Esc to Return");
+    ctx.print(1, sc_y, "Esc to Return, B to change keys");
 }
@@ -0,0 +1,68 @@
+use super::canvas::Canvas;
+use model::high_scores::HighScores;
+use rltk::RGB;
+
+pub fn high_scores_screen(
+    ctx: &mut dyn Canvas,
+    scores: &HighScores,
+    highlight: Option<usize>,
+    _width: u32,
+    height: u32,
+) {
+    ctx.print_color_centered(1, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), "High Scores");
+
+    draw_high_scores(ctx, 3, scores, highlight, height.saturating_sub(6) as usize);
+
+    ctx.print(1, height as i32 - 2, "Esc to Return");
+}
+
+// one row per run starting at tly, the highlighted one (the run just
+// finished) in yellow. Draws at most rows runs under the header
+pub fn draw_high_scores(
+    ctx: &mut dyn Canvas,
+    tly: i32,
+    scores: &HighScores,
+    highlight: Option<usize>,
+    rows: usize,
+) {
+    ctx.print_color(
+        1,
+        tly,
+        RGB::named(rltk::GRAY),
+        RGB::named(rltk::BLACK),
+        &format!(
+            "{:>3} {:>7} {:>9} {:>7} {:>5} {:>8} {:>7}  {}",
+            "#", "Score", "Delivered", "Time", "Falls", "Distance", "Map", "Seed"
+        ),
+    );
+
+    if scores.runs.is_empty() {
+        ctx.print(1, tly + 2, "No runs yet");
+        return;
+    }
+
+    for (rank, run) in scores.runs.iter().take(rows).enumerate() {
+        let color = if highlight == Some(rank) {
+            RGB::named(rltk::YELLOW)
+        } else {
+            RGB::named(rltk::WHITE)
+        };
+        ctx.print_color(
+            1,
+            tly + 1 + rank as i32,
+            color,
+            RGB::named(rltk::BLACK),
+            &format!(
+                "{:>3} {:>7} {:>9} {:>7.1} {:>5} {:>8.1} {:>7}  {}",
+                rank + 1,
+                format!("${}", run.score),
+                run.delivered,
+                run.time,
+                run.n_falls,
+                run.distance,
+                format!("{}x{}", run.map_size.0, run.map_size.1),
+                run.seed
+            ),
+        );
+    }
+}
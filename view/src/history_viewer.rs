@@ -0,0 +1,143 @@
+use super::canvas::Canvas;
+use super::message_log::{self, MessageKind, MessageLog};
+use rltk::RGB;
+
+// Full screen list of every message of the run, newest at the bottom.
+// Remembers how far back the player scrolled and which kind of
+// messages they want to see
+pub struct HistoryViewer {
+    // lines scrolled up from the newest message
+    scroll: usize,
+    pub filter: Option<MessageKind>,
+
+    // rows shown when last drawn
+    page: usize,
+}
+
+impl HistoryViewer {
+    pub fn new() -> Self {
+        HistoryViewer {
+            scroll: 0,
+            filter: None,
+            page: 1,
+        }
+    }
+}
+
+impl Default for HistoryViewer {
+    fn default() -> Self {
+        HistoryViewer::new()
+    }
+}
+
+impl HistoryViewer {
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll += lines;
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub fn page_up(&mut self) {
+        self.scroll_up(self.page);
+    }
+
+    pub fn page_down(&mut self) {
+        self.scroll_down(self.page);
+    }
+
+    // back to the newest message
+    pub fn scroll_to_end(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    // 0 shows everything, 1.. the kinds in MessageKind::ALL
+    pub fn set_filter(&mut self, index: usize) {
+        self.filter = match index {
+            0 => None,
+            i => MessageKind::ALL.get(i - 1).copied(),
+        };
+        self.scroll = 0;
+    }
+
+    pub fn draw(&mut self, ctx: &mut dyn Canvas, log: &MessageLog, width: u32, height: u32) {
+        let black = RGB::named(rltk::BLACK);
+
+        ctx.print_color_centered(0, RGB::named(rltk::YELLOW), black, "Message History");
+
+        // filters, the selected one highlighted
+        let mut x = 1;
+        let all = std::iter::once((None, "All", RGB::named(rltk::WHITE))).chain(
+            MessageKind::ALL
+                .iter()
+                .map(|k| (Some(*k), k.name(), k.color())),
+        );
+        for (i, (kind, name, color)) in all.enumerate() {
+            let label = format!("{} {}", i, name);
+            let (fg, bg) = if kind == self.filter {
+                (black, color)
+            } else {
+                (color, black)
+            };
+            ctx.print_color(x, 1, fg, bg, &label);
+            x += label.chars().count() as i32 + 2;
+        }
+
+        let mut lines: Vec<(String, RGB)> = Vec::new();
+        for message in log.filtered(self.filter) {
+            for line in message_log::wrap(
+                &message.display(),
+                (width as usize).saturating_sub(2).max(1),
+            ) {
+                lines.push((line, message.color));
+            }
+        }
+
+        // rows between the header and the footer
+        let rows = height.saturating_sub(5) as usize;
+        self.page = rows.max(1);
+        let max_scroll = lines.len().saturating_sub(rows);
+        self.scroll = self.scroll.min(max_scroll);
+        let end = lines.len() - self.scroll;
+        let start = end.saturating_sub(rows);
+
+        if lines.is_empty() {
+            ctx.print_color(1, 3, RGB::named(rltk::GRAY), black, "No messages");
+        }
+        for (row, (line, color)) in lines[start..end].iter().enumerate() {
+            ctx.print_color(1, 3 + row as i32, *color, black, line);
+        }
+
+        if start > 0 {
+            let above = format!("{} more above", start);
+            ctx.print_color(
+                width as i32 - above.chars().count() as i32 - 1,
+                2,
+                RGB::named(rltk::GRAY),
+                black,
+                &above,
+            );
+        }
+        if self.scroll > 0 {
+            let below = format!("{} more below", self.scroll);
+            ctx.print_color(
+                width as i32 - below.chars().count() as i32 - 1,
+                height as i32 - 2,
+                RGB::named(rltk::GRAY),
+                black,
+                &below,
+            );
+        }
+
+        ctx.print(
+            1,
+            height as i32 - 1,
+            "Up/Down scroll, PgUp/PgDn page, 0-5 filter, Esc to Return",
+        );
+    }
+}
@@ -1,6 +1,20 @@
+pub mod camera;
+pub mod canvas;
+pub mod frame;
+pub mod grid_canvas;
+#[cfg(feature = "terminal")]
+pub mod terminal_canvas;
+
+pub mod bindings_viewer;
 pub mod gameover_viewer;
 pub mod help_viewer;
+pub mod high_scores_viewer;
+pub mod history_viewer;
 pub mod main_menu_viewer;
 pub mod main_viewer;
+pub mod message_log;
 pub mod options_viewer;
+pub mod sandbox_viewer;
+pub mod shop_viewer;
+pub mod theme;
 pub mod view_manager;
@@ -1,6 +1,31 @@
-use rltk::RGB;
+use super::canvas::Canvas;
+use model::run_mode::RunMode;
+use rltk::{VirtualKeyCode, RGB};
 
-pub fn main_menu_screen(ctx: &mut rltk::Rltk, width: u32, height: u32) {
+// the key each row asks for, in the order the rows are drawn,
+// so clicking a row can stand in for pressing its key
+const ROW_KEYS: [Option<VirtualKeyCode>; 10] = [
+    None,
+    Some(VirtualKeyCode::Escape),
+    Some(VirtualKeyCode::Return),
+    Some(VirtualKeyCode::M),
+    Some(VirtualKeyCode::C),
+    Some(VirtualKeyCode::N),
+    Some(VirtualKeyCode::S),
+    Some(VirtualKeyCode::B),
+    Some(VirtualKeyCode::P),
+    Some(VirtualKeyCode::T),
+];
+
+pub fn main_menu_screen(
+    ctx: &mut dyn Canvas,
+    theme_name: &str,
+    mode: &RunMode,
+    skater: &str,
+    couriers: usize,
+    width: u32,
+    height: u32,
+) {
     let mut left_col = Vec::new();
     let mut right_col = Vec::new();
 
@@ -13,6 +38,31 @@ pub fn main_menu_screen(ctx: &mut rltk::Rltk, width: u32, height: u32) {
     left_col.push(("Play", RGB::named(rltk::CYAN)));
     right_col.push("Press Enter");
 
+    let mode = format!("Mode: {}", mode.name());
+    left_col.push((mode.as_str(), RGB::named(rltk::ORANGE)));
+    right_col.push("Press M");
+
+    let skater = format!("Skater: {}", skater);
+    left_col.push((skater.as_str(), RGB::named(rltk::YELLOW)));
+    right_col.push("Press C");
+
+    let couriers = format!("Couriers: {}", couriers);
+    left_col.push((couriers.as_str(), RGB::named(rltk::CYAN)));
+    right_col.push("Press N");
+
+    left_col.push(("High Scores", RGB::named(rltk::MAGENTA)));
+    right_col.push("Press S");
+
+    left_col.push(("Key Bindings", RGB::named(rltk::GREEN)));
+    right_col.push("Press B");
+
+    left_col.push(("Physics Sandbox", RGB::named(rltk::CYAN)));
+    right_col.push("Press P");
+
+    let theme = format!("Theme: {}", theme_name);
+    left_col.push((theme.as_str(), RGB::named(rltk::WHITE)));
+    right_col.push("Press T");
+
     //left_col.push(("Set Level", Color::Magenta));
     //right_col.push("Press 2");
 
@@ -52,3 +102,12 @@ pub fn main_menu_screen(ctx: &mut rltk::Rltk, width: u32, height: u32) {
         sc_y += height as i32 / left_col.len() as i32;
     }
 }
+
+// the key of the menu row drawn at screen row y, if there is one
+pub fn key_at(y: i32, height: u32) -> Option<VirtualKeyCode> {
+    let row_height = height as i32 / ROW_KEYS.len() as i32;
+    if y < 0 || row_height == 0 || y % row_height != 0 {
+        return None;
+    }
+    ROW_KEYS.get((y / row_height) as usize).copied().flatten()
+}
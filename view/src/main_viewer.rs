@@ -1,6 +1,10 @@
+use super::camera::Camera;
+use super::canvas::Canvas;
+use super::frame::Scene;
+use super::message_log::{self, MessageKind, MessageLog};
+use super::theme::{self, Theme};
 use controller::ai_controller::AIController;
-use controller::player_controller::PlayerController;
-use model::visibility;
+use controller::player_controller::DIRECTIONS;
 use rltk::{Bresenham, FontCharType, Point, RGB};
 
 use std::collections::HashMap;
@@ -8,26 +12,85 @@ use std::collections::HashMap;
 use util::vec_ops;
 
 use model::goal_table::GoalTable;
+use model::memory::MemoryDecay;
 use model::obstacle::Obstacle;
 use model::obstacle_table::ObstacleTable;
 use model::player::Player;
 use model::player_event::PlayerEvent;
+use model::surface::Surface;
 use model::traversability::Traversability;
 
 pub struct MainViewer {
     symbol_map: HashMap<Obstacle, FontCharType>,
-    message_log: Vec<(String, RGB)>,
-    log_length: usize,
+    surface_map: HashMap<Surface, FontCharType>,
+    pub message_log: MessageLog,
     _max_message_length: u32,
+
+    // debug option, draws the whole map and every skater
+    pub reveal_all: bool,
+
+    pub theme: Theme,
+
+    // how far along the run is, shown next to the money
+    pub run_status: String,
+
+    // how hard movement keys push, from 0 to 1
+    pub push_strength: f32,
+
+    pub camera: Camera,
+    // one per courier when the screen is split between them
+    pub split_cameras: Vec<Camera>,
+    // couriers playing on other machines, drawn with the player
+    pub other_couriers: Vec<(Player, RGB)>,
+    pub show_minimap: bool,
+
+    // where the player is headed and where each move would land
+    pub show_trajectory: bool,
+    pub trajectory_steps: usize,
+
+    // what is under the mouse, shown under the map
+    pub hover: Option<String>,
+
+    // where the map view was last drawn on screen and the map top left
+    // it showed, to find the tile under the mouse
+    table_view: Option<(ScreenArea, (i32, i32))>,
+}
+
+// top left and size of part of the screen
+type ScreenArea = ((i32, i32), (u32, u32));
+
+// width of the minimap inside its border
+const MINIMAP_WIDTH: u32 = 20;
+
+// each human courier is drawn in their own color
+pub fn courier_color(index: usize) -> RGB {
+    match index % 4 {
+        0 => RGB::named(rltk::WHITE),
+        1 => RGB::named(rltk::CYAN),
+        2 => RGB::named(rltk::ORANGE),
+        _ => RGB::named(rltk::PINK),
+    }
 }
 
 impl MainViewer {
     pub fn new(log_length: usize) -> Self {
         let mut gv = MainViewer {
             symbol_map: HashMap::new(),
-            message_log: Vec::new(),
-            log_length,
+            surface_map: HashMap::new(),
+            message_log: MessageLog::new(log_length),
             _max_message_length: 16,
+            reveal_all: false,
+            theme: Theme::default(),
+            run_status: String::new(),
+            push_strength: 1.0,
+            camera: Camera::new(),
+            split_cameras: Vec::new(),
+            other_couriers: Vec::new(),
+            show_minimap: true,
+            show_trajectory: false,
+            trajectory_steps: 6,
+            hover: None,
+            table_view: None,
         };
 
         gv.symbol_map.insert(Obstacle::Pit, rltk::to_cp437('x'));
@@ -71,6 +134,22 @@ impl MainViewer {
         gv.symbol_map
             .insert(Obstacle::Rail(-1, -1), rltk::to_cp437('u'));
 
+        // platforms are drawn by what their surface is made of
+        gv.surface_map
+            .insert(Surface::Concrete, rltk::to_cp437('.'));
+        gv.surface_map.insert(Surface::Gravel, rltk::to_cp437(':'));
+        gv.surface_map.insert(Surface::Wet, rltk::to_cp437('~'));
+
+        // ramps point uphill
+        gv.surface_map
+            .insert(Surface::Ramp(0, -1), rltk::to_cp437('↑'));
+        gv.surface_map
+            .insert(Surface::Ramp(0, 1), rltk::to_cp437('↓'));
+        gv.surface_map
+            .insert(Surface::Ramp(-1, 0), rltk::to_cp437('←'));
+        gv.surface_map
+            .insert(Surface::Ramp(1, 0), rltk::to_cp437('→'));
+
         gv
     }
 
@@ -92,22 +171,21 @@ impl MainViewer {
 }
 
 impl MainViewer {
+    // the map around player with their money and gauges. package is
+    // where the one they carry was picked up
     pub fn draw_layout(
-        &self,
-        ctx: &mut rltk::Rltk,
-        table: &mut ObstacleTable,
-        goals: &GoalTable,
-        player: &Player,
-        ai: &Vec<AIController>,
-        controller: &PlayerController,
-        max_speed: f32,
-        fallover_threshold: f32,
+        &mut self,
+        ctx: &mut dyn Canvas,
+        scene: &Scene,
+        (player, score, package): (&Player, i32, Option<(i32, i32)>),
         width: u32,
         height: u32,
-        score: i32,
     ) {
+        let table = scene.table;
+        let physics = scene.controller.physics_for(player);
         let speed_width = 8;
         let speed_tlx = width - speed_width - 1;
+        let balance_tlx = speed_tlx - speed_width - 1;
         let msg_log_height = speed_width as i32;
         let table_view_width = width;
 
@@ -116,36 +194,84 @@ impl MainViewer {
 
         self.draw_table(
             ctx,
-            0,
-            1,
-            table,
-            goals,
+            (0, 1),
+            scene,
             player,
-            ai,
-            controller,
             table_view_width as u32,
             table_view_height as u32,
-            fallover_threshold,
         );
+        let view_size = (table_view_width, table_view_height as u32);
+        self.table_view = Some((
+            ((0, 1), view_size),
+            self.camera.top_left(view_size, (table.width(), table.height())),
+        ));
+
+        if let Some(hover) = &self.hover {
+            ctx.print_color(
+                1,
+                table_view_height + 1,
+                RGB::named(rltk::GRAY),
+                RGB::named(rltk::BLACK),
+                hover,
+            );
+        }
+
+        if self.show_trajectory {
+            self.draw_trajectory(
+                ctx,
+                (0, 1),
+                (table_view_width, table_view_height as u32),
+                scene,
+                player,
+            );
+        }
+
+        // the log ends where the gauges start
         self.draw_msg_log(
             ctx,
             0,
             msg_log_tl_y as i32,
-            width - 1,
+            balance_tlx - 1,
             msg_log_height as u32,
         );
 
+        self.draw_balance(
+            ctx,
+            balance_tlx as i32,
+            msg_log_tl_y,
+            player,
+            physics.fallover_threshold,
+            speed_width,
+        );
+        ctx.print(balance_tlx as i32 + 1, msg_log_tl_y, "Balance");
+
         self.draw_speed(
             ctx,
             speed_tlx as i32,
             msg_log_tl_y,
             player,
-            max_speed,
+            physics.max_speed,
             speed_width as u32,
         );
+        ctx.print(speed_tlx as i32 + 1, msg_log_tl_y, "Speed");
+
+        if self.show_minimap {
+            self.draw_minimap(
+                ctx,
+                (width as i32 - MINIMAP_WIDTH as i32 - 2, 1),
+                table,
+                scene.goals,
+                (player, package),
+                scene.opponents,
+            );
+        }
 
         let mut s = String::new();
         s.push_str(&format!("${}, ", score));
+        if !self.run_status.is_empty() {
+            s.push_str(&format!("{}, ", self.run_status));
+        }
+        s.push_str(&format!("Push {}%, ", (self.push_strength * 100.0).round()));
         s.push_str("Help: press Esc");
 
         ctx.print_color(
@@ -155,76 +281,265 @@ impl MainViewer {
             RGB::named(rltk::BLACK),
             &s,
         );
+
+        if let Some((name, color)) = MainViewer::trick_feedback(player.recent_event) {
+            ctx.print_color(
+                s.chars().count() as i32 + 2,
+                0,
+                color,
+                RGB::named(rltk::BLACK),
+                name,
+            );
+        }
     }
 
-    // return a Screen of dimensions width x height that maps a width x height section
-    // of the ObstacleTable centered on the player (any ObstacleTable coordinates that are out of bounds
-    // are clamped out and the screen doesn't draw anything there)
-    pub fn draw_table(
-        &self,
-        ctx: &mut rltk::Rltk,
-        sc_tlx: i32,
-        sc_tly: i32,
-        table: &mut ObstacleTable,
-        goals: &GoalTable,
-        player: &Player,
-        ai: &Vec<AIController>,
-        controller: &PlayerController,
+    // draw_layout for two to four couriers sharing the screen: a view of
+    // the map around each of them, side by side or two by two, above the
+    // message log and the gauges of whoever's turn it is.
+    // couriers are (skater, money), active is whose turn it is
+    pub fn draw_split_layout(
+        &mut self,
+        ctx: &mut dyn Canvas,
+        scene: &Scene,
+        couriers: &[(Player, i32)],
+        active: usize,
         width: u32,
         height: u32,
-        fallover_threshold: f32,
     ) {
-        // set to true to reveal the map and all entities
-        let testing = true;
-
-        // compute ObstacleTable coordinates
-        let middle = player.xy();
-        let mut tl_x = (middle.0 - (width / 2) as i32).clamp(0, table.width() as i32 - 1);
-        let mut tl_y = (middle.1 - (height / 2) as i32).clamp(0, table.height() as i32 - 1);
-
-        let mut br_x = (middle.0 + (width as i32 / 2)).clamp(0, table.width() as i32 - 1);
-        let mut br_y = (middle.1 + (height as i32 / 2)).clamp(0, table.height() as i32 - 1);
-
-        if br_x == table.width() as i32 - 1 {
-            tl_x -= (middle.0 + width as i32 / 2) - br_x;
-            tl_x = tl_x.clamp(0, table.width() as i32 - 1);
-        } else if tl_x == 0 {
-            br_x += (middle.0 + width as i32 / 2) - tl_x;
-            br_x = br_x.clamp(0, table.width() as i32 - 1);
+        let (table, controller) = (scene.table, scene.controller);
+        let speed_width = 8;
+        let speed_tlx = width - speed_width - 1;
+        let balance_tlx = speed_tlx - speed_width - 1;
+        let msg_log_height = speed_width as i32;
+        let views_height = height as i32 - msg_log_height - 3;
+        let msg_log_tl_y = height as i32 - msg_log_height - 1;
+
+        let rows = if couriers.len() > 2 { 2 } else { 1 };
+        let view_width = (width as i32 - 1) / 2;
+        let cell_height = views_height / rows;
+
+        for (index, (player, score)) in couriers.iter().enumerate() {
+            let tlx = (index as i32 % 2) * (view_width + 1);
+            let tly = 1 + (index as i32 / 2) * cell_height;
+            let color = courier_color(index);
+
+            // whose turn it is stands out
+            let header = format!(
+                " Courier {}: ${}, {} delivered ",
+                index + 1,
+                score,
+                player.n_delivered
+            );
+            let (fg, bg) = if index == active {
+                (RGB::named(rltk::BLACK), color)
+            } else {
+                (color, RGB::named(rltk::BLACK))
+            };
+            ctx.print_color(tlx, tly, fg, bg, &header);
+
+            let others: Vec<(Player, RGB)> = couriers
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(other, (p, _))| (*p, courier_color(other)))
+                .collect();
+            let view_size = (view_width as u32, (cell_height - 1).max(0) as u32);
+            let camera = &self.split_cameras[index];
+            let area = ((tlx, tly + 1), view_size);
+            self.draw_view(ctx, camera, area, scene, (player, color), &others);
+
+            if index == active {
+                if self.show_trajectory {
+                    self.draw_trajectory_from(ctx, camera, area, scene, player);
+                }
+                self.table_view = Some((
+                    area,
+                    camera.top_left(view_size, (table.width(), table.height())),
+                ));
+            }
         }
 
-        if br_y == table.height() as i32 - 1 {
-            tl_y -= (middle.1 + height as i32 / 2) - br_y;
-            tl_y = tl_y.clamp(0, table.height() as i32 - 1)
-        } else if tl_y == 0 {
-            br_y += (middle.1 + height as i32 / 2) - tl_y;
-            br_y = br_y.clamp(0, table.height() as i32 - 1);
+        for sc_y in 1..views_height + 1 {
+            ctx.set(
+                view_width,
+                sc_y,
+                RGB::named(rltk::GRAY),
+                RGB::named(rltk::BLACK),
+                rltk::to_cp437('│'),
+            );
         }
 
-        // screen coords
-        let mut sc_x = sc_tlx;
-        let mut sc_y = sc_tly;
-
-        // put this somewhere else
-        let visible = visibility::get_fov(player.xy(), table, 16);
-        for p in visible.iter() {
-            let idx = table.xy_flat(p.x, p.y) as usize;
-            table.revealed[idx] = true;
-            table.memory.push((p.x, p.y));
-            if table.memory.len() > table.memory_size {
-                let notvis = table.memory.remove(0);
-                let idx = table.xy_flat(notvis.0, notvis.1) as usize;
-                table.revealed[idx] = false;
-            }
+        if let Some(hover) = &self.hover {
+            ctx.print_color(
+                1,
+                views_height + 1,
+                RGB::named(rltk::GRAY),
+                RGB::named(rltk::BLACK),
+                hover,
+            );
+        }
+
+        self.draw_msg_log(ctx, 0, msg_log_tl_y, balance_tlx - 1, msg_log_height as u32);
+
+        let (player, _) = &couriers[active];
+        let physics = controller.physics_for(player);
+        self.draw_balance(
+            ctx,
+            balance_tlx as i32,
+            msg_log_tl_y,
+            player,
+            physics.fallover_threshold,
+            speed_width,
+        );
+        ctx.print(balance_tlx as i32 + 1, msg_log_tl_y, "Balance");
+        self.draw_speed(
+            ctx,
+            speed_tlx as i32,
+            msg_log_tl_y,
+            player,
+            physics.max_speed,
+            speed_width,
+        );
+        ctx.print(speed_tlx as i32 + 1, msg_log_tl_y, "Speed");
+
+        let mut s = String::new();
+        if !self.run_status.is_empty() {
+            s.push_str(&format!("{}, ", self.run_status));
+        }
+        s.push_str(&format!("Push {}%, ", (self.push_strength * 100.0).round()));
+        s.push_str(&format!("Courier {}'s turn, ", active + 1));
+        s.push_str("Help: press Esc");
+        ctx.print_color(
+            0,
+            0,
+            RGB::named(rltk::ALICEBLUE),
+            RGB::named(rltk::BLACK),
+            &s,
+        );
+
+        if let Some((name, color)) = MainViewer::trick_feedback(player.recent_event) {
+            ctx.print_color(
+                s.chars().count() as i32 + 2,
+                0,
+                color,
+                RGB::named(rltk::BLACK),
+                name,
+            );
+        }
+    }
+
+    // the camera of courier index's view when the screen is split,
+    // set up like the main camera the first time it's needed
+    pub fn split_camera(&mut self, index: usize) -> &mut Camera {
+        while self.split_cameras.len() <= index {
+            let mut camera = Camera::new();
+            camera.dead_zone = self.camera.dead_zone;
+            camera.look_ahead = self.camera.look_ahead;
+            self.split_cameras.push(camera);
+        }
+        &mut self.split_cameras[index]
+    }
+
+    // the map tile drawn at screen cell (sc_x, sc_y) by the last draw_layout,
+    // None when the cell is outside the map view
+    pub fn tile_at(&self, (sc_x, sc_y): (i32, i32)) -> Option<(i32, i32)> {
+        let (((sc_tlx, sc_tly), (width, height)), (tl_x, tl_y)) = self.table_view?;
+        let (sx, sy) = (sc_x - sc_tlx, sc_y - sc_tly);
+        if sx < 0 || sy < 0 || sx >= width as i32 || sy >= height as i32 {
+            return None;
+        }
+        Some((tl_x + sx, tl_y + sy))
+    }
+
+    // the name and color the player is drawn with after a trick
+    pub fn trick_feedback(event: PlayerEvent) -> Option<(&'static str, RGB)> {
+        match event {
+            PlayerEvent::Ollie => Some(("Ollie!", RGB::named(rltk::YELLOW))),
+            PlayerEvent::Manual => Some(("Manual", RGB::named(rltk::MAGENTA))),
+            PlayerEvent::Bail => Some(("Bailed", RGB::named(rltk::GRAY))),
+            _ => None,
         }
+    }
+
+    // draws the width x height section of the ObstacleTable the camera
+    // is looking at, with its top left corner at sc_tl. Parts of the
+    // view outside the table are left blank
+    pub fn draw_table(
+        &self,
+        ctx: &mut dyn Canvas,
+        sc_tl: (i32, i32),
+        scene: &Scene,
+        player: &Player,
+        width: u32,
+        height: u32,
+    ) {
+        self.draw_view(
+            ctx,
+            &self.camera,
+            (sc_tl, (width, height)),
+            scene,
+            (player, RGB::named(rltk::WHITE)),
+            &self.other_couriers,
+        );
+    }
+
+    // draw_table through camera, with the other couriers on the map too
+    fn draw_view(
+        &self,
+        ctx: &mut dyn Canvas,
+        camera: &Camera,
+        ((sc_tlx, sc_tly), (width, height)): ScreenArea,
+        scene: &Scene,
+        (player, player_color): (&Player, RGB),
+        others: &[(Player, RGB)],
+    ) {
+        let Scene { table, goals, opponents: ai, controller } = *scene;
+        let fallover_threshold = controller.physics_for(player).fallover_threshold;
+        let (tl_x, tl_y) = camera.top_left((width, height), (table.width(), table.height()));
+
+        for sx in 0..width as i32 {
+            for sy in 0..height as i32 {
+                let (x, y) = (tl_x + sx, tl_y + sy);
+                if x < 0 || y < 0 || x >= table.width() as i32 || y >= table.height() as i32 {
+                    continue;
+                }
+
+                // screen coords
+                let (sc_x, sc_y) = (sc_tlx + sx, sc_tly + sy);
 
-        for x in tl_x..=br_x {
-            for y in tl_y..=br_y {
-                if testing || table.revealed[table.xy_flat(x, y)] || (player.x() == x && player.y() == y) || visible.contains(&Point::new(x, y)) {
+                let in_view = self.reveal_all
+                    || table.is_visible(x, y)
+                    || (player.x() == x && player.y() == y);
+
+                if !in_view {
+                    // drawn as it was last seen, fading as it is forgotten
+                    if let Some((seen, age)) = table.memory.last_seen(x, y) {
+                        let symbol = match seen.obstacle {
+                            Obstacle::Platform => self.surface_map[&seen.surface],
+                            obstacle_type => self.symbol_map[&obstacle_type],
+                        };
+                        let fade = match table.memory.decay {
+                            MemoryDecay::Time(turns) if turns > 0.0 => {
+                                1.0 - 0.5 * (age / turns).min(1.0)
+                            }
+                            _ => 1.0,
+                        };
+                        ctx.set(
+                            sc_x,
+                            sc_y,
+                            theme::scaled(self.theme.remembered, fade),
+                            RGB::named(rltk::BLACK),
+                            symbol,
+                        );
+                    }
+                } else {
                     let obstacle_type = table.get_obstacle(x, y);
 
                     let t = table.traversability((player.x(), player.y()), (x, y));
-                    let symbol = self.symbol_map[&obstacle_type];
+                    let mut symbol = match obstacle_type {
+                        Obstacle::Platform => self.surface_map[&table.get_surface(x, y)],
+                        _ => self.symbol_map[&obstacle_type],
+                    };
 
                     let mov = controller.move_player_vel(
                         table,
@@ -236,42 +551,51 @@ impl MainViewer {
                         x as f32 - player.x() as f32,
                         y as f32 - player.y() as f32,
                     ));
-                    let inv_dist: f32;
-                    if dist.round() as i32 == 0 {
-                        inv_dist = 1.0;
-                    } else {
-                        inv_dist = 6.0 / (1.0 + dist);
-                    }
-                    match mov.recent_event {
-                        PlayerEvent::FallOver | PlayerEvent::GameOver(_) => {
-                            ctx.set(
-                                sc_x,
-                                sc_y,
-                                RGB::from_f32(0.0, inv_dist, 0.0),
-                                RGB::named(rltk::BLACK),
-                                symbol,
-                            );
-                        }
-                        _ => {
-                            ctx.set(
-                                sc_x,
-                                sc_y,
-                                RGB::from_f32(
-                                    (1.0 - balance_amount) * inv_dist,
-                                    0.0,
-                                    balance_amount * inv_dist,
-                                ),
-                                RGB::named(rltk::BLACK),
-                                symbol,
-                            );
+                    let inv_dist = self.theme.fade(dist);
+                    let falls = matches!(mov.recent_event, PlayerEvent::FallOver | PlayerEvent::GameOver(_));
+
+                    // spell out the danger of the moves the player can make next
+                    if self.theme.danger_glyphs
+                        && dist.round() as i32 == 1
+                        && obstacle_type == Obstacle::Platform
+                    {
+                        if let Some(glyph) = danger_glyph(falls, balance_amount) {
+                            symbol = rltk::to_cp437(glyph);
                         }
                     }
 
+                    if falls {
+                        ctx.set(
+                            sc_x,
+                            sc_y,
+                            theme::scaled(self.theme.fall, inv_dist),
+                            RGB::named(rltk::BLACK),
+                            symbol,
+                        );
+                    } else {
+                        ctx.set(
+                            sc_x,
+                            sc_y,
+                            theme::scaled(self.theme.balance_color(balance_amount), inv_dist),
+                            RGB::named(rltk::BLACK),
+                            symbol,
+                        );
+                    }
+
                     for goal in goals.goals.keys() {
                         if x == goal.0 && y == goal.1 && !goals.picked_up.contains(&(x, y)) {
-                            if visible.contains(&Point::new(x, y)) {
-                                match t {
-                                    Traversability::No => {
+                            match t {
+                                Traversability::No => {
+                                    ctx.set(
+                                        sc_x,
+                                        sc_y,
+                                        RGB::from_f32(inv_dist, inv_dist, inv_dist),
+                                        RGB::named(rltk::BLACK),
+                                        rltk::to_cp437('$'),
+                                    );
+                                }
+                                _ => match mov.recent_event {
+                                    PlayerEvent::FallOver | PlayerEvent::GameOver(_) => {
                                         ctx.set(
                                             sc_x,
                                             sc_y,
@@ -280,27 +604,16 @@ impl MainViewer {
                                             rltk::to_cp437('$'),
                                         );
                                     }
-                                    _ => match mov.recent_event {
-                                        PlayerEvent::FallOver | PlayerEvent::GameOver(_) => {
-                                            ctx.set(
-                                                sc_x,
-                                                sc_y,
-                                                RGB::from_f32(inv_dist, inv_dist, inv_dist),
-                                                RGB::named(rltk::BLACK),
-                                                rltk::to_cp437('$'),
-                                            );
-                                        }
-                                        _ => {
-                                            ctx.set(
-                                                sc_x,
-                                                sc_y,
-                                                RGB::from_f32(1.0, 0.0, 0.0),
-                                                RGB::named(rltk::WHITE),
-                                                rltk::to_cp437('$'),
-                                            );
-                                        }
-                                    },
-                                }
+                                    _ => {
+                                        ctx.set(
+                                            sc_x,
+                                            sc_y,
+                                            RGB::from_f32(1.0, 0.0, 0.0),
+                                            RGB::named(rltk::WHITE),
+                                            rltk::to_cp437('$'),
+                                        );
+                                    }
+                                },
                             }
 
                             break;
@@ -311,19 +624,22 @@ impl MainViewer {
                         Obstacle::Pit => {}
                         _ => {
                             for p in ai.iter().enumerate() {
-                                let mut color = RGB::from_f32(inv_dist, inv_dist * 0.5, 0.0);
+                                let mut color = theme::scaled(self.theme.skater, inv_dist);
+                                let mut glyph = '☻';
                                 if let Some(key) = goals.index_map.get(&p.0) {
                                     if let Some((_idx, c)) = goals.goals.get(key) {
                                         color = *c;
+                                        if self.theme.recipient_letters {
+                                            glyph = self.theme.shirt_letter(*c).unwrap_or(glyph);
+                                        }
                                     }
                                 }
                                 if x == p.1.player.x()
                                     && y == p.1.player.y()
                                     && table.blocked.contains_key(&(x, y))
-                                    && (testing || visible.contains(&Point::new(x, y)))
                                 {
                                     match p.1.player.recent_event {
-                                        PlayerEvent::FallOver => {
+                                        PlayerEvent::FallOver | PlayerEvent::KnockedOver => {
                                             ctx.set(
                                                 sc_x,
                                                 sc_y,
@@ -338,17 +654,34 @@ impl MainViewer {
                                                 sc_y,
                                                 color,
                                                 RGB::named(rltk::BLACK),
-                                                rltk::to_cp437('☻'),
+                                                rltk::to_cp437(glyph),
                                             );
                                         }
                                     }
                                 }
                             }
 
+                            for (other, color) in others.iter() {
+                                if x != other.x() || y != other.y() {
+                                    continue;
+                                }
+                                let glyph = match other.recent_event {
+                                    PlayerEvent::FallOver | PlayerEvent::KnockedOver => '!',
+                                    _ => '☺',
+                                };
+                                ctx.set(
+                                    sc_x,
+                                    sc_y,
+                                    *color,
+                                    RGB::named(rltk::BLACK),
+                                    rltk::to_cp437(glyph),
+                                );
+                            }
+
                             // draw player last so it is on top
                             if x == player.x() && y == player.y() {
                                 match player.recent_event {
-                                    PlayerEvent::FallOver => {
+                                    PlayerEvent::FallOver | PlayerEvent::KnockedOver => {
                                         ctx.set(
                                             sc_x,
                                             sc_y,
@@ -357,11 +690,15 @@ impl MainViewer {
                                             rltk::to_cp437('!'),
                                         );
                                     }
-                                    _ => {
+                                    event => {
+                                        let color = match MainViewer::trick_feedback(event) {
+                                            Some((_, c)) => c,
+                                            None => player_color,
+                                        };
                                         ctx.set(
                                             sc_x,
                                             sc_y,
-                                            RGB::named(rltk::WHITE),
+                                            color,
                                             RGB::named(rltk::BLACK),
                                             rltk::to_cp437('☺'),
                                         );
@@ -371,20 +708,188 @@ impl MainViewer {
                         }
                     }
                 }
+            }
+        }
+    }
+
+    // dots where the player will be if they keep waiting, and a marker on the
+    // tile each move would take them to: green if they stay up, red if they fall
+    pub fn draw_trajectory(
+        &self,
+        ctx: &mut dyn Canvas,
+        sc_tl: (i32, i32),
+        view_size: (u32, u32),
+        scene: &Scene,
+        player: &Player,
+    ) {
+        self.draw_trajectory_from(ctx, &self.camera, (sc_tl, view_size), scene, player);
+    }
+
+    fn draw_trajectory_from(
+        &self,
+        ctx: &mut dyn Canvas,
+        camera: &Camera,
+        ((sc_tlx, sc_tly), view_size): ScreenArea,
+        scene: &Scene,
+        player: &Player,
+    ) {
+        let (table, controller) = (scene.table, scene.controller);
+        let (tl_x, tl_y) = camera.top_left(view_size, (table.width(), table.height()));
+        let mark = |ctx: &mut dyn Canvas, (x, y): (i32, i32), color: RGB, glyph: char| {
+            let (sx, sy) = (x - tl_x, y - tl_y);
+            if (x, y) != player.xy()
+                && sx >= 0
+                && sy >= 0
+                && sx < view_size.0 as i32
+                && sy < view_size.1 as i32
+            {
+                ctx.set(sc_tlx + sx, sc_tly + sy, color, RGB::named(rltk::BLACK), rltk::to_cp437(glyph));
+            }
+        };
+
+        // a fall leaves the player where they are, so it is marked
+        // on the tile they were headed for instead
+        let fall_at = |from: &Player, landing: &Player, push: (f32, f32)| {
+            if landing.xy() == from.xy() {
+                let (dx, dy) = vec_ops::discrete_jmp((from.speed.0 + push.0, from.speed.1 + push.1));
+                (from.x() + dx, from.y() + dy)
+            } else {
+                landing.xy()
+            }
+        };
+
+        let mut last = *player;
+        for step in controller.predict_coast(table, player, self.trajectory_steps) {
+            match step.recent_event {
+                PlayerEvent::FallOver | PlayerEvent::Respawn => {
+                    mark(ctx, fall_at(&last, &step, (0.0, 0.0)), RGB::named(rltk::RED), '!');
+                }
+                _ => mark(ctx, step.xy(), RGB::named(rltk::CYAN), '·'),
+            }
+            last = step;
+        }
+
+        let mut landings: Vec<((i32, i32), bool)> = Vec::new();
+        for direction in DIRECTIONS {
+            let landing = controller.move_player(table, player, direction);
+            match landing.recent_event {
+                PlayerEvent::FallOver | PlayerEvent::KnockedOver | PlayerEvent::Respawn => {
+                    let push = controller.get_inst_velocity(direction);
+                    landings.push((fall_at(player, &landing, push), true));
+                }
+                _ => landings.push((landing.xy(), false)),
+            }
+        }
+
+        // falls drawn last so they are never hidden
+        landings.sort_by_key(|(_, falls)| *falls);
+        for (at, falls) in landings {
+            if falls {
+                mark(ctx, at, RGB::named(rltk::RED), 'x');
+            } else {
+                mark(ctx, at, RGB::named(rltk::GREEN), '+');
+            }
+        }
+    }
+
+    // the explored part of the whole table at reduced scale, with the package,
+    // the recipient once the package is picked up, and the player
+    pub fn draw_minimap(
+        &self,
+        ctx: &mut dyn Canvas,
+        (tlx, tly): (i32, i32),
+        table: &ObstacleTable,
+        goals: &GoalTable,
+        (player, package): (&Player, Option<(i32, i32)>),
+        ai: &[AIController],
+    ) {
+        let scale = table.width().div_ceil(MINIMAP_WIDTH).max(1) as i32;
+        let map_width = (table.width() as i32 + scale - 1) / scale;
+        let map_height = (table.height() as i32 + scale - 1) / scale;
+
+        ctx.draw_box(
+            tlx,
+            tly,
+            map_width + 1,
+            map_height + 1,
+            RGB::named(rltk::GRAY),
+            RGB::named(rltk::BLACK),
+        );
+
+        let known = |x: i32, y: i32| -> Option<Obstacle> {
+            if self.reveal_all || table.is_visible(x, y) {
+                Some(table.get_obstacle(x, y))
+            } else {
+                table.memory.last_seen(x, y).map(|(seen, _)| seen.obstacle)
+            }
+        };
+
+        // each cell stands for a scale x scale block of tiles
+        let to_cell = |(x, y): (i32, i32)| (tlx + 1 + x / scale, tly + 1 + y / scale);
+
+        for my in 0..map_height {
+            for mx in 0..map_width {
+                let mut any_known = false;
+                let mut any_platform = false;
+                for x in mx * scale..((mx + 1) * scale).min(table.width() as i32) {
+                    for y in my * scale..((my + 1) * scale).min(table.height() as i32) {
+                        if let Some(obstacle) = known(x, y) {
+                            any_known = true;
+                            any_platform |= obstacle == Obstacle::Platform;
+                        }
+                    }
+                }
 
-                sc_y += 1;
+                if any_known {
+                    let glyph = if any_platform { '.' } else { '#' };
+                    ctx.set(
+                        tlx + 1 + mx,
+                        tly + 1 + my,
+                        RGB::named(rltk::DIMGRAY),
+                        RGB::named(rltk::BLACK),
+                        rltk::to_cp437(glyph),
+                    );
+                }
             }
+        }
 
-            sc_y = sc_tly;
-            sc_x += 1;
+        for (pos, (idx, color)) in goals.goals.iter() {
+            if package == Some(*pos) {
+                // the recipient of player's package, while in sight
+                if let Some(recipient) = ai.get(*idx) {
+                    let at = recipient.player.xy();
+                    if self.reveal_all || table.is_visible(at.0, at.1) {
+                        let (sc_x, sc_y) = to_cell(at);
+                        ctx.set(sc_x, sc_y, *color, RGB::named(rltk::BLACK), rltk::to_cp437('☺'));
+                    }
+                }
+            } else if !goals.picked_up.contains(pos) && known(pos.0, pos.1).is_some() {
+                let (sc_x, sc_y) = to_cell(*pos);
+                ctx.set(
+                    sc_x,
+                    sc_y,
+                    RGB::named(rltk::YELLOW),
+                    RGB::named(rltk::BLACK),
+                    rltk::to_cp437('$'),
+                );
+            }
         }
+
+        let (sc_x, sc_y) = to_cell(player.xy());
+        ctx.set(
+            sc_x,
+            sc_y,
+            RGB::named(rltk::WHITE),
+            RGB::named(rltk::BLACK),
+            rltk::to_cp437('@'),
+        );
     }
 
     // returns a Screen which visualizes the direction of the Player's
     // Balance vector, and their closeness to falling over (the nearness of the indicator to the border)
     pub fn draw_balance(
         &self,
-        ctx: &mut rltk::Rltk,
+        ctx: &mut dyn Canvas,
         tlx: i32,
         tly: i32,
         player: &Player,
@@ -393,8 +898,7 @@ impl MainViewer {
     ) {
         self.draw_vector(
             ctx,
-            tlx,
-            tly,
+            (tlx, tly),
             player.balance,
             fallover_threshold,
             size,
@@ -404,7 +908,7 @@ impl MainViewer {
 
     pub fn draw_speed(
         &self,
-        ctx: &mut rltk::Rltk,
+        ctx: &mut dyn Canvas,
         tlx: i32,
         tly: i32,
         player: &Player,
@@ -413,8 +917,7 @@ impl MainViewer {
     ) {
         self.draw_vector(
             ctx,
-            tlx,
-            tly,
+            (tlx, tly),
             player.speed,
             max_speed,
             size,
@@ -424,23 +927,21 @@ impl MainViewer {
 
     pub fn draw_vector(
         &self,
-        ctx: &mut rltk::Rltk,
-        tlx: i32,
-        tly: i32,
+        ctx: &mut dyn Canvas,
+        (tlx, tly): (i32, i32),
         v: (f32, f32),
         max: f32,
         size: u32,
         color: RGB,
     ) {
         // draw border
-        ctx.draw_box(tlx, tly, size, size, color, RGB::named(rltk::BLACK));
+        ctx.draw_box(tlx, tly, size as i32, size as i32, color, RGB::named(rltk::BLACK));
 
-        // compute position of vector inside the rect
-        // is p_x correct?
-        let p_x = (((v.0 / max) * (size as f32)).round() as i32 + (size as i32 / 2))
-            .clamp(0, size as i32);
-        let p_y = (((v.1 / max) * (size as f32)).round() as i32 + (size as i32 / 2))
-            .clamp(0, size as i32);
+        // compute position of vector inside the rect,
+        // a vector of length max reaches the border
+        let half = size as f32 / 2.0;
+        let p_x = (((v.0 / max) * half).round() as i32 + (size as i32 / 2)).clamp(0, size as i32);
+        let p_y = (((v.1 / max) * half).round() as i32 + (size as i32 / 2)).clamp(0, size as i32);
 
         // indicate speed with this symbol
         let lines = Bresenham::new(
@@ -472,41 +973,52 @@ impl MainViewer {
         );
     }
 
-    pub fn draw_msg_log(&self, ctx: &mut rltk::Rltk, tlx: i32, tly: i32, width: u32, height: u32) {
+    pub fn draw_msg_log(&self, ctx: &mut dyn Canvas, tlx: i32, tly: i32, width: u32, height: u32) {
         ctx.draw_box(
             tlx,
             tly,
-            width,
-            height,
+            width as i32,
+            height as i32,
             RGB::named(rltk::WHITE),
             RGB::named(rltk::BLACK),
         );
 
-        let mut l_index = (self.message_log.len() as i32 - 1) as i32;
+        // newest message on top, long ones wrapped to fit inside the box
         let mut scr_y = tly + 1;
-
-        while scr_y < tly + height as i32 - 2 && l_index >= 0 {
-            ctx.print_color(
-                tlx + 1,
-                scr_y,
-                self.message_log[l_index as usize].1,
-                RGB::named(rltk::BLACK),
-                &self.message_log[l_index as usize].0,
-            );
-
-            scr_y += 1;
-            l_index -= 1;
+        for message in self.message_log.iter().rev() {
+            let text = message.display();
+            for line in message_log::wrap(&text, (width as usize).saturating_sub(1).max(1)) {
+                if scr_y >= tly + height as i32 {
+                    return;
+                }
+                ctx.print_color(tlx + 1, scr_y, message.color, RGB::named(rltk::BLACK), &line);
+                scr_y += 1;
+            }
         }
     }
 
-    pub fn add_string(&mut self, s: String, c: RGB) {
-        self.message_log.push((s, c));
-        if self.message_log.len() > self.log_length {
-            self.message_log.remove(0);
-        }
+    pub fn add_message(&mut self, kind: MessageKind, s: String) {
+        self.message_log.add(kind, s);
+    }
+
+    pub fn add_colored_message(&mut self, kind: MessageKind, s: String, c: RGB) {
+        self.message_log.add_colored(kind, s, c);
     }
 
     pub fn clear_log(&mut self) {
         self.message_log.clear();
     }
 }
+
+// the glyph a neighbouring tile is marked with when danger_glyphs is on
+fn danger_glyph(falls: bool, balance_amount: f32) -> Option<char> {
+    if falls {
+        Some('X')
+    } else if balance_amount >= 0.66 {
+        Some('!')
+    } else if balance_amount >= 0.33 {
+        Some('?')
+    } else {
+        None
+    }
+}
@@ -0,0 +1,166 @@
+use rltk::RGB;
+
+// What a message is about, so the history can be filtered
+// and each kind drawn in its own color
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageKind {
+    // directions from other skaters and look mode
+    Hint,
+    // picking up and handing over packages
+    Delivery,
+    // falls, bails and collisions
+    Fall,
+    // small talk that doesn't help
+    Chat,
+    // tricks and everything else
+    System,
+}
+
+impl MessageKind {
+    pub const ALL: [MessageKind; 5] = [
+        MessageKind::Hint,
+        MessageKind::Delivery,
+        MessageKind::Fall,
+        MessageKind::Chat,
+        MessageKind::System,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageKind::Hint => "Hint",
+            MessageKind::Delivery => "Delivery",
+            MessageKind::Fall => "Fall",
+            MessageKind::Chat => "Chat",
+            MessageKind::System => "System",
+        }
+    }
+
+    pub fn color(&self) -> RGB {
+        match self {
+            MessageKind::Hint => RGB::named(rltk::GREEN),
+            MessageKind::Delivery => RGB::named(rltk::CYAN),
+            MessageKind::Fall => RGB::named(rltk::ORANGE),
+            MessageKind::Chat => RGB::named(rltk::DARKGRAY),
+            MessageKind::System => RGB::named(rltk::YELLOW),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub text: String,
+    pub kind: MessageKind,
+    pub color: RGB,
+
+    // how many times in a row it was added
+    pub count: u32,
+}
+
+impl Message {
+    // the text with how often it was repeated
+    pub fn display(&self) -> String {
+        if self.count > 1 {
+            format!("{} (x{})", self.text, self.count)
+        } else {
+            String::clone(&self.text)
+        }
+    }
+}
+
+// Every message of the current run, oldest first. The same message added
+// again right after itself only bumps its counter
+pub struct MessageLog {
+    messages: Vec<Message>,
+    capacity: usize,
+}
+
+impl MessageLog {
+    pub fn new(capacity: usize) -> Self {
+        MessageLog {
+            messages: Vec::new(),
+            capacity,
+        }
+    }
+}
+
+impl MessageLog {
+    pub fn add(&mut self, kind: MessageKind, text: String) {
+        self.add_colored(kind, text, kind.color());
+    }
+
+    // for messages that need another color than their kind's,
+    // like the shirt color of a recipient
+    pub fn add_colored(&mut self, kind: MessageKind, text: String, color: RGB) {
+        if let Some(last) = self.messages.last_mut() {
+            if last.kind == kind && last.text == text && last.color == color {
+                last.count += 1;
+                return;
+            }
+        }
+
+        self.messages.push(Message {
+            text,
+            kind,
+            color,
+            count: 1,
+        });
+        if self.messages.len() > self.capacity {
+            self.messages.remove(0);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Message> {
+        self.messages.iter()
+    }
+
+    // messages of kind, or all of them
+    pub fn filtered(&self, kind: Option<MessageKind>) -> impl DoubleEndedIterator<Item = &Message> {
+        self.messages
+            .iter()
+            .filter(move |m| kind.is_none() || kind == Some(m.kind))
+    }
+}
+
+// splits text into lines of at most width characters, breaking
+// between words where it can
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        // longer than a whole line, cut it
+        while word.len() > width {
+            let rest = word.split_off(width);
+            lines.push(word.into_iter().collect());
+            word = rest;
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,185 @@
+use super::canvas::Canvas;
+use super::frame::Scene;
+use super::main_viewer::MainViewer;
+use controller::player_controller::PlayerController;
+use controller::physics::Tunable;
+use controller::sandbox::Sandbox;
+use model::goal_table::GoalTable;
+use model::player::Player;
+use model::player_event::PlayerEvent;
+use rltk::RGB;
+use util::vec_ops;
+
+// width of the column of values right of the map
+const PANEL_WIDTH: u32 = 30;
+
+// rows each trace is plotted in
+const TRACE_HEIGHT: u32 = 6;
+
+// Draws the physics sandbox: the map, the values being tuned and the
+// speed and balance of the last moves
+pub struct SandboxViewer {
+    // draws the map, with everything revealed
+    pub table_view: MainViewer,
+}
+
+impl SandboxViewer {
+    pub fn new() -> Self {
+        let mut table_view = MainViewer::new(0);
+        table_view.reveal_all = true;
+        SandboxViewer { table_view }
+    }
+}
+
+impl Default for SandboxViewer {
+    fn default() -> Self {
+        SandboxViewer::new()
+    }
+}
+
+impl SandboxViewer {
+    pub fn draw(
+        &mut self,
+        ctx: &mut dyn Canvas,
+        sandbox: &Sandbox,
+        controller: &PlayerController,
+        width: u32,
+        height: u32,
+    ) {
+        let black = RGB::named(rltk::BLACK);
+        let physics = &controller.physics;
+
+        let traces_tly = height as i32 - 2 - 2 * (TRACE_HEIGHT as i32 + 1);
+        let table_width = width - PANEL_WIDTH - 1;
+        let table_height = (traces_tly - 1).max(0) as u32;
+
+        let map = if sandbox.run_map { "Run map" } else { "Empty map" };
+        ctx.print_color(
+            0,
+            0,
+            RGB::named(rltk::YELLOW),
+            black,
+            &format!("Physics Sandbox: {}, {}", controller.integrator().name(), map),
+        );
+
+        let player = &sandbox.player;
+        let scene = Scene {
+            table: &sandbox.table,
+            goals: &GoalTable::new(),
+            opponents: &[],
+            controller,
+        };
+        self.table_view
+            .draw_table(ctx, (0, 1), &scene, player, table_width, table_height);
+
+        // the values as they would be written in model.txt
+        let panel_x = table_width as i32 + 1;
+        for (index, tunable) in Tunable::ALL.iter().enumerate() {
+            let (fg, bg) = if *tunable == sandbox.selected() {
+                (black, RGB::named(rltk::WHITE))
+            } else {
+                (RGB::named(rltk::WHITE), black)
+            };
+            let value = match tunable {
+                Tunable::Substeps => format!("{}", physics.substeps),
+                _ => format!("{:.2}", tunable.get(physics)),
+            };
+            let line = format!("{:<22} {:>6}", tunable.name(), value);
+            ctx.print_color(panel_x, index as i32 + 1, fg, bg, &line);
+        }
+        let sc_y = Tunable::ALL.len() as i32 + 1;
+        ctx.print_color(
+            panel_x,
+            sc_y,
+            RGB::named(rltk::GRAY),
+            black,
+            &format!("{:<22} {:>6.2}", "speed_damp", physics.speed_damp),
+        );
+
+        let gauge_size = 8;
+        let gauges_y = sc_y + 2;
+        self.table_view.draw_balance(
+            ctx,
+            panel_x,
+            gauges_y,
+            player,
+            physics.fallover_threshold,
+            gauge_size,
+        );
+        ctx.print(panel_x + 1, gauges_y, "Balance");
+        self.table_view.draw_speed(
+            ctx,
+            panel_x + gauge_size as i32 + 2,
+            gauges_y,
+            player,
+            physics.max_speed,
+            gauge_size,
+        );
+        ctx.print(panel_x + gauge_size as i32 + 3, gauges_y, "Speed");
+
+        let columns = (width as usize - 2).min(sandbox.trace_length());
+        let skip = sandbox.trace().len().saturating_sub(columns);
+        let moves: Vec<&Player> = sandbox.trace().iter().skip(skip).collect();
+
+        ctx.print_color(
+            1,
+            traces_tly,
+            RGB::named(rltk::CYAN),
+            black,
+            &format!("Speed, last {} moves (max {:.2})", columns, physics.max_speed),
+        );
+        let speeds: Vec<(f32, bool)> = moves
+            .iter()
+            .map(|p| (vec_ops::magnitude(p.speed) / physics.max_speed, false))
+            .collect();
+        draw_trace(ctx, 1, traces_tly + 1, &speeds, RGB::named(rltk::CYAN));
+
+        let balance_tly = traces_tly + TRACE_HEIGHT as i32 + 1;
+        ctx.print_color(
+            1,
+            balance_tly,
+            RGB::named(rltk::BLUE),
+            black,
+            &format!("Balance (falls at {:.2})", physics.fallover_threshold),
+        );
+        let balances: Vec<(f32, bool)> = moves
+            .iter()
+            .map(|p| {
+                let fell = matches!(
+                    p.recent_event,
+                    PlayerEvent::FallOver | PlayerEvent::Respawn
+                );
+                (vec_ops::magnitude(p.balance) / physics.fallover_threshold, fell)
+            })
+            .collect();
+        draw_trace(ctx, 1, balance_tly + 1, &balances, RGB::named(rltk::BLUE));
+
+        ctx.print(
+            1,
+            height as i32 - 1,
+            "↑↓ choose, ←→ change, R reset, I physics, O map, Backspace defaults, Esc",
+        );
+    }
+}
+
+// one column per value, filled from the bottom in proportion to the value
+// (1 fills the column). Columns marked true are drawn as a red X
+fn draw_trace(ctx: &mut dyn Canvas, tlx: i32, tly: i32, values: &[(f32, bool)], color: RGB) {
+    let black = RGB::named(rltk::BLACK);
+    let bottom = tly + TRACE_HEIGHT as i32 - 1;
+    for (index, (value, marked)) in values.iter().enumerate() {
+        let sc_x = tlx + index as i32;
+        if *marked {
+            ctx.set(sc_x, bottom, RGB::named(rltk::RED), black, rltk::to_cp437('X'));
+            continue;
+        }
+
+        let filled = (value.clamp(0.0, 1.0) * TRACE_HEIGHT as f32).round() as i32;
+        for row in 0..filled {
+            ctx.set(sc_x, bottom - row, color, black, rltk::to_cp437('█'));
+        }
+        if filled == 0 {
+            ctx.set(sc_x, bottom, RGB::named(rltk::GRAY), black, rltk::to_cp437('_'));
+        }
+    }
+}
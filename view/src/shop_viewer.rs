@@ -0,0 +1,126 @@
+use super::canvas::Canvas;
+use controller::shop::{Item, ItemKind, Progress};
+use rltk::RGB;
+
+// Lists what's for sale between runs and what the board has so far
+pub struct ShopViewer {
+    selected: usize,
+
+    // result of the last purchase
+    pub message: Option<(String, RGB)>,
+}
+
+impl ShopViewer {
+    pub fn new() -> Self {
+        ShopViewer {
+            selected: 0,
+            message: None,
+        }
+    }
+}
+
+impl Default for ShopViewer {
+    fn default() -> Self {
+        ShopViewer::new()
+    }
+}
+
+impl ShopViewer {
+    // index into the items, which may be fewer than last time it was drawn
+    pub fn selected(&self, n_items: usize) -> Option<usize> {
+        if n_items == 0 {
+            None
+        } else {
+            Some(self.selected.min(n_items - 1))
+        }
+    }
+
+    pub fn select_next(&mut self, n_items: usize) {
+        if n_items > 0 {
+            self.selected = (self.selected + 1) % n_items;
+        }
+    }
+
+    pub fn select_previous(&mut self, n_items: usize) {
+        if n_items > 0 {
+            self.selected = (self.selected + n_items - 1) % n_items;
+        }
+    }
+
+    pub fn draw(
+        &self,
+        ctx: &mut dyn Canvas,
+        items: &[Item],
+        progress: &Progress,
+        _width: u32,
+        height: u32,
+    ) {
+        let black = RGB::named(rltk::BLACK);
+        ctx.print_color_centered(0, RGB::named(rltk::YELLOW), black, "Board Shop");
+        ctx.print_color(
+            1,
+            2,
+            RGB::named(rltk::GREEN),
+            black,
+            &format!("Money: ${}", progress.money),
+        );
+
+        let mut sc_y = 3;
+        let mut section = "";
+        let selected = self.selected(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let (title, status, price) = match item.kind {
+                ItemKind::Upgrade(levels) => {
+                    let level = progress.level(&item.id);
+                    let price = if level >= levels {
+                        "-".to_string()
+                    } else {
+                        format!("${}", progress.price(item))
+                    };
+                    ("Upgrades", format!("level {}/{}", level, levels), price)
+                }
+                ItemKind::Consumable => {
+                    let status = if progress.is_stocked(&item.id) {
+                        "next run"
+                    } else {
+                        ""
+                    };
+                    ("For one run", status.to_string(), format!("${}", item.price))
+                }
+            };
+            if title != section {
+                section = title;
+                sc_y += 1;
+                ctx.print_color(1, sc_y, RGB::named(rltk::GRAY), black, section);
+                sc_y += 1;
+            }
+
+            let (fg, bg) = if Some(index) == selected {
+                (black, RGB::named(rltk::WHITE))
+            } else {
+                (RGB::named(rltk::WHITE), black)
+            };
+            let line = format!("{:<14} {:<10} {:>5}  {}", item.name, status, price, item.about);
+            ctx.print_color(3, sc_y, fg, bg, &line);
+            sc_y += 1;
+        }
+
+        // the changes to model.txt the next run is skated with
+        sc_y += 1;
+        ctx.print_color(1, sc_y, RGB::named(rltk::GRAY), black, "Your board");
+        sc_y += 1;
+        let board = progress.board(items);
+        if board.changes().is_empty() {
+            ctx.print(3, sc_y, "Stock, nothing added yet");
+        }
+        for (tunable, change) in board.changes() {
+            ctx.print(3, sc_y, &format!("{:<19} {:>+6.2}", tunable.name(), change));
+            sc_y += 1;
+        }
+
+        if let Some((message, color)) = &self.message {
+            ctx.print_color(1, height as i32 - 3, *color, black, message);
+        }
+        ctx.print(1, height as i32 - 1, "↑↓ choose, Enter buy, Esc to Return");
+    }
+}
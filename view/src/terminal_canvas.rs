@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use crossterm::cursor::MoveTo;
+use crossterm::queue;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use rltk::{FontCharType, RGB};
+
+use super::canvas::Canvas;
+use super::grid_canvas::{Cell, GridCanvas};
+
+// Draws to a plain terminal with crossterm, e.g. to play over SSH.
+// Frames are drawn into memory and present writes only the cells
+// that changed since the last frame.
+pub struct TerminalCanvas {
+    frame: GridCanvas,
+    shown: Vec<Option<Cell>>,
+}
+
+impl TerminalCanvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        TerminalCanvas {
+            frame: GridCanvas::new(width, height),
+            shown: vec![None; width as usize * height as usize],
+        }
+    }
+}
+
+impl TerminalCanvas {
+    // forget what is on the terminal so the next present redraws everything
+    pub fn invalidate(&mut self) {
+        for cell in self.shown.iter_mut() {
+            *cell = None;
+        }
+    }
+
+    pub fn present<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let width = self.frame.width() as i32;
+        for y in 0..self.frame.height() as i32 {
+            for x in 0..width {
+                let idx = (y * width + x) as usize;
+                let cell = *self.frame.cell(x, y).unwrap();
+                if self.shown[idx] == Some(cell) {
+                    continue;
+                }
+
+                queue!(
+                    out,
+                    MoveTo(x as u16, y as u16),
+                    SetForegroundColor(to_color(cell.fg)),
+                    SetBackgroundColor(to_color(cell.bg)),
+                    Print(cell.to_char())
+                )?;
+                self.shown[idx] = Some(cell);
+            }
+        }
+
+        queue!(out, ResetColor)?;
+        out.flush()
+    }
+}
+
+impl Canvas for TerminalCanvas {
+    fn width(&self) -> u32 {
+        self.frame.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.frame.height()
+    }
+
+    fn cls(&mut self) {
+        self.frame.cls();
+    }
+
+    fn set(&mut self, x: i32, y: i32, fg: RGB, bg: RGB, glyph: FontCharType) {
+        self.frame.set(x, y, fg, bg, glyph);
+    }
+}
+
+fn to_color(c: RGB) -> Color {
+    Color::Rgb {
+        r: (c.r.clamp(0.0, 1.0) * 255.0) as u8,
+        g: (c.g.clamp(0.0, 1.0) * 255.0) as u8,
+        b: (c.b.clamp(0.0, 1.0) * 255.0) as u8,
+    }
+}
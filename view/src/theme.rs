@@ -0,0 +1,191 @@
+use rltk::RGB;
+
+// Colors and markers the main view draws with. Themes are read from
+// raws/themes.txt and picked from the main menu, so the game can be
+// played without telling red from green or cyan from teal.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub name: String,
+
+    // tiles are colored from balance_low to balance_high by how
+    // much balance moving there would cost
+    pub balance_low: RGB,
+    pub balance_high: RGB,
+
+    // tiles that moving to would make the player fall over
+    pub fall: RGB,
+
+    // tiles that are remembered but not in view
+    pub remembered: RGB,
+
+    // skaters that are not waiting for a package
+    pub skater: RGB,
+
+    // shirt colors of package recipients
+    pub shirts: Vec<RGB>,
+
+    // fade tiles with distance from the player
+    pub distance_fade: bool,
+
+    // mark the tiles around the player with how dangerous moving there is
+    pub danger_glyphs: bool,
+
+    // draw recipients with the letter of their shirt instead of a face
+    pub recipient_letters: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "Default".to_string(),
+            balance_low: RGB::named(rltk::RED),
+            balance_high: RGB::named(rltk::BLUE),
+            fall: RGB::named(rltk::GREEN),
+            remembered: RGB::from_f32(0.4, 0.4, 0.4),
+            skater: RGB::from_f32(1.0, 0.5, 0.0),
+            shirts: vec![
+                RGB::named(rltk::CYAN),
+                RGB::named(rltk::MAGENTA),
+                RGB::named(rltk::AQUAMARINE),
+                RGB::named(rltk::FORESTGREEN),
+                RGB::named(rltk::TEAL),
+                RGB::named(rltk::BURLYWOOD),
+                RGB::named(rltk::BROWN1),
+                RGB::named(rltk::GRAY),
+            ],
+            distance_fade: true,
+            danger_glyphs: false,
+            recipient_letters: false,
+        }
+    }
+}
+
+impl Theme {
+    // the letter recipients wearing this shirt are drawn with
+    pub fn shirt_letter(&self, shirt: RGB) -> Option<char> {
+        self.shirts
+            .iter()
+            .position(|c| *c == shirt)
+            .map(|i| (b'A' + i as u8) as char)
+    }
+
+    // color between balance_low and balance_high, amount in 0..1
+    pub fn balance_color(&self, amount: f32) -> RGB {
+        let t = amount.clamp(0.0, 1.0);
+        RGB::from_f32(
+            self.balance_low.r + (self.balance_high.r - self.balance_low.r) * t,
+            self.balance_low.g + (self.balance_high.g - self.balance_low.g) * t,
+            self.balance_low.b + (self.balance_high.b - self.balance_low.b) * t,
+        )
+    }
+
+    // how bright a tile at dist from the player is drawn,
+    // above 1.0 close to the player
+    pub fn fade(&self, dist: f32) -> f32 {
+        if !self.distance_fade || dist.round() as i32 == 0 {
+            1.0
+        } else {
+            6.0 / (1.0 + dist)
+        }
+    }
+}
+
+// dim c by amount, 1.0 leaves it alone
+pub fn scaled(c: RGB, amount: f32) -> RGB {
+    RGB::from_f32(c.r * amount, c.g * amount, c.b * amount)
+}
+
+pub fn load_themes() -> Vec<Theme> {
+    match rltk::embedding::EMBED
+        .lock()
+        .get_resource("raws/themes.txt".to_string())
+    {
+        None => vec![Theme::default()],
+        Some(raw_data) => {
+            let raw_string = std::str::from_utf8(raw_data)
+                .expect("Unable to convert to a valid UTF-8 string.");
+            themes_from_config(raw_string)
+        }
+    }
+}
+
+// Every "theme <name>" line starts a new theme based on the default one,
+// the lines after it change its fields. Colors are "r g b" in 0..255
+pub fn themes_from_config(raw_string: &str) -> Vec<Theme> {
+    let mut themes: Vec<Theme> = Vec::new();
+
+    for line in raw_string.lines() {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        if words.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if words[0] == "theme" {
+            themes.push(Theme {
+                name: words[1..].join(" "),
+                shirts: Vec::new(),
+                ..Theme::default()
+            });
+            continue;
+        }
+
+        let theme = match themes.last_mut() {
+            None => continue,
+            Some(theme) => theme,
+        };
+
+        if words[0] == "balance_low" {
+            if let Some(c) = parse_color(&words) {
+                theme.balance_low = c;
+            }
+        } else if words[0] == "balance_high" {
+            if let Some(c) = parse_color(&words) {
+                theme.balance_high = c;
+            }
+        } else if words[0] == "fall" {
+            if let Some(c) = parse_color(&words) {
+                theme.fall = c;
+            }
+        } else if words[0] == "remembered" {
+            if let Some(c) = parse_color(&words) {
+                theme.remembered = c;
+            }
+        } else if words[0] == "skater" {
+            if let Some(c) = parse_color(&words) {
+                theme.skater = c;
+            }
+        } else if words[0] == "shirt" {
+            if let Some(c) = parse_color(&words) {
+                theme.shirts.push(c);
+            }
+        } else if words[0] == "distance_fade" {
+            theme.distance_fade = words.get(1) == Some(&"1");
+        } else if words[0] == "danger_glyphs" {
+            theme.danger_glyphs = words.get(1) == Some(&"1");
+        } else if words[0] == "recipient_letters" {
+            theme.recipient_letters = words.get(1) == Some(&"1");
+        }
+    }
+
+    // a theme without shirts keeps the default ones
+    for theme in themes.iter_mut() {
+        if theme.shirts.is_empty() {
+            theme.shirts = Theme::default().shirts;
+        }
+    }
+
+    if themes.is_empty() {
+        themes.push(Theme::default());
+    }
+    themes
+}
+
+fn parse_color(words: &[&str]) -> Option<RGB> {
+    if words.len() < 4 {
+        return None;
+    }
+    let r = words[1].parse::<u8>().ok()?;
+    let g = words[2].parse::<u8>().ok()?;
+    let b = words[3].parse::<u8>().ok()?;
+    Some(RGB::from_u8(r, g, b))
+}
@@ -1,57 +1,133 @@
-use controller::ai_controller::AIController;
-use controller::player_controller::PlayerController;
-
+use super::bindings_viewer::BindingsViewer;
+use super::canvas::Canvas;
+use super::frame::Frame;
 use super::gameover_viewer;
 use super::help_viewer;
+use super::high_scores_viewer;
+use super::history_viewer::HistoryViewer;
 use super::main_menu_viewer;
 use super::main_viewer::MainViewer;
+use super::sandbox_viewer::SandboxViewer;
+use super::shop_viewer::ShopViewer;
+use super::theme::{self, Theme};
 
-use model::goal_table::GoalTable;
-use model::obstacle_table::ObstacleTable;
-use model::player::Player;
 use model::state::ProcState;
 
 pub struct ViewManager {
     pub main_view: MainViewer,
+    pub history: HistoryViewer,
+    pub bindings: BindingsViewer,
+    pub sandbox: SandboxViewer,
+    pub shop: ShopViewer,
+
+    themes: Vec<Theme>,
+    theme_idx: usize,
 }
 
 impl ViewManager {
     pub fn new() -> Self {
-        ViewManager {
-            main_view: MainViewer::new(64), // set log length to 64
-        }
+        let mut vm = ViewManager {
+            main_view: MainViewer::new(512), // keep the last 512 messages for the history
+            history: HistoryViewer::new(),
+            bindings: BindingsViewer::new(),
+            sandbox: SandboxViewer::new(),
+            shop: ShopViewer::new(),
+            themes: theme::load_themes(),
+            theme_idx: 0,
+        };
+        vm.main_view.theme = Theme::clone(&vm.themes[0]);
+        vm.sandbox.table_view.theme = Theme::clone(&vm.themes[0]);
+
+        vm
+    }
+}
+
+impl ViewManager {
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.theme_idx]
+    }
+
+    // switch to the next theme from raws/themes.txt
+    pub fn next_theme(&mut self) -> &Theme {
+        self.theme_idx = (self.theme_idx + 1) % self.themes.len();
+        self.main_view.theme = Theme::clone(&self.themes[self.theme_idx]);
+        self.sandbox.table_view.theme = Theme::clone(&self.themes[self.theme_idx]);
+        &self.themes[self.theme_idx]
     }
 }
 
 impl ViewManager {
-    pub fn get_screen(
-        &mut self,
-        ctx: &mut rltk::Rltk,
-        state: &ProcState,
-        obs_table: &mut ObstacleTable,
-        goal_table: &GoalTable,
-        player: &Player,
-        ai: &Vec<AIController>,
-        controller: &PlayerController,
-        max_speed: f32,
-        fallover_threshold: f32,
-        window_width: u32,
-        window_height: u32,
-        score: i32,
-    ) {
-        match state {
+    pub fn get_screen(&mut self, ctx: &mut dyn Canvas, frame: &Frame) {
+        let (window_width, window_height) = (ctx.width(), ctx.height());
+        let scene = &frame.scene;
+        match frame.state {
             ProcState::MainMenu => {
-                main_menu_viewer::main_menu_screen(ctx, window_width, window_height);
+                main_menu_viewer::main_menu_screen(
+                    ctx,
+                    &self.themes[self.theme_idx].name,
+                    frame.mode,
+                    scene.controller.profile_name(frame.player),
+                    frame.couriers.len(),
+                    window_width,
+                    window_height,
+                );
             }
             ProcState::Help => {
-                help_viewer::help_screen(ctx, window_width, window_height);
+                help_viewer::help_screen(
+                    ctx,
+                    &self.main_view.theme,
+                    frame.bindings,
+                    window_width,
+                    window_height,
+                );
+            }
+            ProcState::Bindings => {
+                self.bindings
+                    .draw(ctx, frame.bindings, window_width, window_height);
+            }
+            ProcState::Sandbox => {
+                self.sandbox.draw(
+                    ctx,
+                    frame.sandbox,
+                    scene.controller,
+                    window_width,
+                    window_height,
+                );
+            }
+            ProcState::Shop => {
+                self.shop.draw(
+                    ctx,
+                    frame.shop_items,
+                    frame.progress,
+                    window_width,
+                    window_height,
+                );
+            }
+            ProcState::HighScores => {
+                high_scores_viewer::high_scores_screen(
+                    ctx,
+                    frame.high_scores,
+                    frame.new_high_score,
+                    window_width,
+                    window_height,
+                );
+            }
+            ProcState::MessageHistory => {
+                self.history.draw(
+                    ctx,
+                    &self.main_view.message_log,
+                    window_width,
+                    window_height,
+                );
             }
             ProcState::GameOver => {
                 self.main_view.clear_log();
                 gameover_viewer::game_over_screen(
                     ctx,
-                    obs_table,
-                    player,
+                    (frame.last_run, frame.standings),
+                    frame.mode,
+                    frame.run_end,
+                    (frame.high_scores, frame.new_high_score),
                     window_width,
                     window_height,
                 );
@@ -63,18 +139,22 @@ impl ViewManager {
             | ProcState::Chat
             | ProcState::Restart
             | ProcState::DeliveredPackage => {
+                if frame.couriers.len() > 1 {
+                    return self.main_view.draw_split_layout(
+                        ctx,
+                        scene,
+                        frame.couriers,
+                        frame.active,
+                        window_width,
+                        window_height,
+                    );
+                }
                 return self.main_view.draw_layout(
                     ctx,
-                    obs_table,
-                    goal_table,
-                    player,
-                    ai,
-                    controller,
-                    max_speed,
-                    fallover_threshold,
+                    scene,
+                    (frame.player, frame.score, frame.package),
                     window_width,
                     window_height,
-                    score,
                 );
             }
         }
@@ -0,0 +1,81 @@
+use view::camera::Camera;
+
+const VIEW: (u32, u32) = (80, 39);
+
+#[test]
+fn stays_put_inside_dead_zone() {
+    let mut camera = Camera::new();
+    camera.snap_to((100, 100));
+    let before = camera.top_left(VIEW, (200, 200));
+
+    camera.follow((103, 102), (0.0, 0.0));
+    assert_eq!(camera.top_left(VIEW, (200, 200)), before);
+
+    // one step past the edge of the dead zone moves the view one step
+    camera.follow((107, 100), (0.0, 0.0));
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (before.0 + 1, before.1));
+}
+
+#[test]
+fn looks_ahead_in_the_direction_of_speed() {
+    let mut still = Camera::new();
+    let mut moving = Camera::new();
+    still.snap_to((100, 100));
+    moving.snap_to((100, 100));
+
+    still.follow((110, 100), (0.0, 0.0));
+    moving.follow((110, 100), (2.0, 0.0));
+
+    let still_x = still.top_left(VIEW, (200, 200)).0;
+    let moving_x = moving.top_left(VIEW, (200, 200)).0;
+    assert_eq!(moving_x - still_x, 8);
+}
+
+#[test]
+fn clamps_to_map_edges() {
+    let mut camera = Camera::new();
+    camera.snap_to((0, 0));
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (0, 0));
+
+    camera.snap_to((199, 199));
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (120, 161));
+}
+
+#[test]
+fn centers_maps_smaller_than_the_view() {
+    let mut camera = Camera::new();
+    camera.snap_to((5, 5));
+    assert_eq!(camera.top_left(VIEW, (40, 20)), (-20, -9));
+
+    // exactly as big as the view
+    assert_eq!(camera.top_left(VIEW, VIEW), (0, 0));
+}
+
+#[test]
+fn panning_is_an_offset_until_reset() {
+    let mut camera = Camera::new();
+    camera.snap_to((100, 100));
+    let before = camera.top_left(VIEW, (200, 200));
+
+    camera.pan(-4, 8, (200, 200));
+    assert!(camera.is_panned());
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (before.0 - 4, before.1 + 8));
+
+    camera.reset_pan();
+    assert_eq!(camera.top_left(VIEW, (200, 200)), before);
+}
+
+#[test]
+fn panning_stops_at_the_map_edges() {
+    let mut camera = Camera::new();
+    camera.snap_to((190, 10));
+
+    for _ in 0..20 {
+        camera.pan(4, -4, (200, 200));
+    }
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (120, 0));
+
+    // coming back starts moving the view straight away
+    camera.pan(-60, 0, (200, 200));
+    assert_eq!(camera.top_left(VIEW, (200, 200)), (99, 0));
+}
@@ -0,0 +1,247 @@
+use controller::ai_controller::AIController;
+use controller::bindings::Bindings;
+use controller::player_controller::PlayerController;
+use model::goal_table::GoalTable;
+use model::high_scores::HighScores;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
+use model::visibility;
+use rltk::{VirtualKeyCode, RGB};
+
+use view::canvas::Canvas;
+use view::frame::Scene;
+use view::grid_canvas::GridCanvas;
+use view::main_viewer::MainViewer;
+use view::message_log::MessageKind;
+use view::theme::Theme;
+use view::{gameover_viewer, help_viewer, main_menu_viewer};
+
+const WIDTH: u32 = 80;
+const HEIGHT: u32 = 50;
+
+fn load_model_config() {
+    rltk::embedding::EMBED.lock().add_resource(
+        "raws/model.txt".to_string(),
+        include_bytes!("../../game/src/raws/raws/model.txt"),
+    );
+}
+
+#[test]
+fn grid_canvas_keeps_what_was_drawn() {
+    let mut canvas = GridCanvas::new(10, 3);
+    canvas.print(1, 1, "hello");
+    canvas.set(0, 2, RGB::named(rltk::RED), RGB::named(rltk::BLACK), rltk::to_cp437('#'));
+
+    // off the canvas, ignored
+    canvas.print(8, 0, "cut");
+    canvas.set(-1, 5, RGB::named(rltk::RED), RGB::named(rltk::BLACK), 35);
+
+    assert_eq!(canvas.text(), "        cu\n hello\n#\n");
+    assert_eq!(canvas.cell(0, 2).unwrap().fg, RGB::named(rltk::RED));
+
+    canvas.cls();
+    assert_eq!(canvas.text(), "\n\n\n");
+}
+
+#[test]
+fn grid_canvas_centers_like_rltk() {
+    let mut canvas = GridCanvas::new(10, 1);
+    canvas.print_centered(0, "abcd");
+    assert_eq!(canvas.row(0), "   abcd   ");
+}
+
+#[test]
+fn draw_box_outlines_and_clears() {
+    let mut canvas = GridCanvas::new(5, 4);
+    canvas.print(0, 1, "xxxxx");
+    canvas.draw_box(0, 0, 3, 2, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK));
+    assert_eq!(canvas.text(), "┌──┐\n│  │x\n└──┘\n\n");
+}
+
+#[test]
+fn main_menu_lists_entries() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    main_menu_viewer::main_menu_screen(
+        &mut canvas,
+        "Default",
+        &RunMode::TimedShift(300.0),
+        "Standard",
+        1,
+        WIDTH,
+        HEIGHT,
+    );
+    let text = canvas.text();
+    assert!(text.contains("Dangerous Deliveries"));
+    assert!(text.contains("How to Play"));
+    assert!(text.contains("Press Enter"));
+    assert!(text.contains("Mode: Timed Shift"));
+}
+
+#[test]
+fn main_menu_rows_can_be_clicked() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    main_menu_viewer::main_menu_screen(
+        &mut canvas,
+        "Default",
+        &RunMode::Quota(5),
+        "Heavy",
+        2,
+        WIDTH,
+        HEIGHT,
+    );
+
+    for (label, key) in [
+        ("Press Esc", VirtualKeyCode::Escape),
+        ("Press Enter", VirtualKeyCode::Return),
+        ("Press M", VirtualKeyCode::M),
+        ("Press C", VirtualKeyCode::C),
+        ("Press N", VirtualKeyCode::N),
+        ("Press S", VirtualKeyCode::S),
+        ("Press B", VirtualKeyCode::B),
+        ("Press P", VirtualKeyCode::P),
+        ("Press T", VirtualKeyCode::T),
+    ] {
+        let y = (0..HEIGHT as i32)
+            .find(|y| canvas.row(*y).contains(label))
+            .unwrap();
+        assert_eq!(main_menu_viewer::key_at(y, HEIGHT), Some(key), "{}", label);
+        assert_eq!(main_menu_viewer::key_at(y + 1, HEIGHT), None);
+    }
+}
+
+#[test]
+fn help_lists_controls() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    help_viewer::help_screen(&mut canvas, &Theme::default(), &Bindings::default(), WIDTH, HEIGHT);
+    let text = canvas.text();
+    assert!(text.contains("Movement"));
+    assert!(text.contains("W or K"));
+    assert!(text.contains("Esc to Return"));
+}
+
+#[test]
+fn game_over_shows_why_and_the_run() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    let run = RunStats {
+        delivered: 3,
+        ..RunStats::new(7, (40, 20))
+    };
+    gameover_viewer::game_over_screen(
+        &mut canvas,
+        (&run, &[]),
+        &RunMode::Quota(3),
+        Some(RunEnd::QuotaMet),
+        (&HighScores::new(10), None),
+        WIDTH,
+        HEIGHT,
+    );
+    let text = canvas.text();
+    assert!(text.contains("Quota Met"));
+    assert!(text.contains("deliver 3 packages"));
+    assert!(text.contains("40x20, seed 7"));
+}
+
+#[test]
+fn layout_draws_header_table_and_log() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(60, 40);
+    table.update_platforms();
+    let player = Player::new(30, 20);
+    visibility::update_visibility(&mut table, player.xy(), 16, 0.0);
+
+    let mut viewer = MainViewer::new(8);
+    viewer.add_message(MessageKind::Hint, "Find the package".to_string());
+
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    viewer.draw_layout(
+        &mut canvas,
+        &Scene {
+            table: &table,
+            goals: &GoalTable::new(),
+            opponents: &[],
+            controller: &controller,
+        },
+        (&player, 0, None),
+        WIDTH,
+        HEIGHT,
+    );
+
+    let text = canvas.text();
+    assert!(canvas.row(0).starts_with("$0, Push 100%, Help: press Esc"));
+    assert!(text.contains('☺'));
+    assert!(text.contains("Find the package"));
+
+    // out of sight and never seen
+    assert_eq!(canvas.cell(0, 1).unwrap().to_char(), ' ');
+}
+
+#[test]
+fn minimap_shows_the_recipient_of_the_package_carried() {
+    let mut table = ObstacleTable::new(40, 20);
+    table.update_platforms();
+    let player = Player::new(2, 2);
+    let mut viewer = MainViewer::new(8);
+    viewer.reveal_all = true;
+
+    // both picked up, the second by another courier
+    let mut goals = GoalTable::new();
+    goals.add_goal((4, 4), (0, RGB::named(rltk::CYAN)));
+    goals.add_goal((30, 4), (1, RGB::named(rltk::CYAN)));
+    goals.picked_up.insert((4, 4));
+    goals.picked_up.insert((30, 4));
+    let opponents = [AIController::new(10, 10), AIController::new(30, 16)];
+
+    let mut canvas = GridCanvas::new(30, 15);
+    let carried = (&player, Some((4, 4)));
+    viewer.draw_minimap(&mut canvas, (0, 0), &table, &goals, carried, &opponents);
+
+    // a cell for every 2x2 tiles, inside the border
+    assert_eq!(canvas.cell(6, 6).unwrap().to_char(), '☺');
+    assert_eq!(canvas.cell(16, 9).unwrap().to_char(), '.');
+    assert!(!canvas.text().contains('$'));
+}
+
+#[test]
+fn layout_finds_the_tile_under_the_mouse() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(60, 40);
+    table.update_platforms();
+    let player = Player::new(30, 20);
+    visibility::update_visibility(&mut table, player.xy(), 16, 0.0);
+
+    let mut viewer = MainViewer::new(8);
+    assert_eq!(viewer.tile_at((5, 5)), None);
+
+    viewer.hover = Some("Gravel".to_string());
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    viewer.draw_layout(
+        &mut canvas,
+        &Scene {
+            table: &table,
+            goals: &GoalTable::new(),
+            opponents: &[],
+            controller: &controller,
+        },
+        (&player, 0, None),
+        WIDTH,
+        HEIGHT,
+    );
+
+    let (x, y) = (0..HEIGHT as i32)
+        .flat_map(|y| (0..WIDTH as i32).map(move |x| (x, y)))
+        .find(|(x, y)| canvas.cell(*x, *y).unwrap().to_char() == '☺')
+        .unwrap();
+    assert_eq!(viewer.tile_at((x, y)), Some(player.xy()));
+    assert_eq!(viewer.tile_at((x + 1, y - 1)), Some((31, 19)));
+
+    // the header and the message log aren't part of the map
+    assert_eq!(viewer.tile_at((x, 0)), None);
+    assert_eq!(viewer.tile_at((x, HEIGHT as i32 - 1)), None);
+    assert!(canvas.text().contains("Gravel"));
+}
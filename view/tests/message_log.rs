@@ -0,0 +1,93 @@
+use rltk::RGB;
+
+use view::history_viewer::HistoryViewer;
+use view::message_log::{MessageKind, MessageLog};
+
+#[test]
+fn repeats_bump_the_counter() {
+    let mut log = MessageLog::new(8);
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+
+    assert_eq!(log.len(), 1);
+    let message = log.iter().next().unwrap();
+    assert_eq!(message.count, 3);
+    assert_eq!(message.display(), "Stick it to the man (x3)");
+}
+
+#[test]
+fn only_repeats_in_a_row_are_merged() {
+    let mut log = MessageLog::new(8);
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+    log.add(MessageKind::Fall, "You fell over".to_string());
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+
+    // same text in another kind or color is another message
+    log.add(MessageKind::Hint, "Stick it to the man".to_string());
+    log.add_colored(
+        MessageKind::Hint,
+        "Stick it to the man".to_string(),
+        RGB::named(rltk::MAGENTA),
+    );
+
+    assert_eq!(log.len(), 5);
+    assert!(log.iter().all(|m| m.count == 1));
+}
+
+#[test]
+fn oldest_messages_are_dropped() {
+    let mut log = MessageLog::new(3);
+    for i in 0..5 {
+        log.add(MessageKind::System, format!("message {}", i));
+    }
+
+    let texts: Vec<&str> = log.iter().map(|m| m.text.as_str()).collect();
+    assert_eq!(texts, vec!["message 2", "message 3", "message 4"]);
+}
+
+#[test]
+fn messages_take_their_kind_color_unless_given_one() {
+    let mut log = MessageLog::new(8);
+    log.add(MessageKind::Fall, "You bailed".to_string());
+    log.add_colored(
+        MessageKind::Delivery,
+        "Picked up package".to_string(),
+        RGB::named(rltk::TEAL),
+    );
+
+    let colors: Vec<RGB> = log.iter().map(|m| m.color).collect();
+    assert_eq!(
+        colors,
+        vec![MessageKind::Fall.color(), RGB::named(rltk::TEAL)]
+    );
+}
+
+#[test]
+fn filter_by_kind() {
+    let mut log = MessageLog::new(8);
+    log.add(MessageKind::Hint, "There's a job to the north".to_string());
+    log.add(MessageKind::Chat, "Stick it to the man".to_string());
+    log.add(MessageKind::Hint, "Last saw them to the east".to_string());
+
+    assert_eq!(log.filtered(None).count(), 3);
+    assert_eq!(log.filtered(Some(MessageKind::Hint)).count(), 2);
+    assert_eq!(log.filtered(Some(MessageKind::Delivery)).count(), 0);
+}
+
+#[test]
+fn history_filters_by_number_and_resets_scroll() {
+    let mut history = HistoryViewer::new();
+    history.scroll_up(5);
+
+    history.set_filter(3);
+    assert_eq!(history.filter, Some(MessageKind::Fall));
+    assert_eq!(history.scroll(), 0);
+
+    history.set_filter(0);
+    assert_eq!(history.filter, None);
+
+    // past the last kind shows everything
+    history.set_filter(9);
+    assert_eq!(history.filter, None);
+}
@@ -0,0 +1,477 @@
+// Golden file tests for what the viewers draw. Each test renders into a
+// GridCanvas and compares the text with tests/snapshots/<name>.txt.
+// After an intended layout change, rerun with UPDATE_SNAPSHOTS=1 to
+// rewrite the files and check the diff.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use controller::ai_controller::AIController;
+use controller::bindings::Bindings;
+use controller::player_controller::PlayerController;
+use controller::physics::Tunable;
+use controller::sandbox::Sandbox;
+use controller::shop::{self, Progress};
+use model::goal_table::GoalTable;
+use model::high_scores::HighScores;
+use model::obstacle::Obstacle;
+use model::obstacle_table::ObstacleTable;
+use model::player::Player;
+use model::run_mode::{RunEnd, RunMode};
+use model::run_stats::RunStats;
+use rltk::RGB;
+
+use view::bindings_viewer::{BindingsViewer, Capture};
+use view::frame::Scene;
+use view::grid_canvas::GridCanvas;
+use view::history_viewer::HistoryViewer;
+use view::main_viewer::MainViewer;
+use view::sandbox_viewer::SandboxViewer;
+use view::shop_viewer::ShopViewer;
+use view::message_log::{MessageKind, MessageLog};
+use view::theme::Theme;
+use view::{gameover_viewer, help_viewer, high_scores_viewer, main_menu_viewer};
+
+const WIDTH: u32 = 80;
+const HEIGHT: u32 = 50;
+
+const GAUGE_SIZE: u32 = 8;
+const GAUGE_MAX: f32 = 2.0;
+
+fn assert_snapshot(name: &str, canvas: &GridCanvas) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+    let actual = canvas.text();
+
+    if env::var("UPDATE_SNAPSHOTS").is_ok() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("no snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display())
+    });
+    assert!(
+        actual == expected,
+        "{} does not match {}\n--- expected\n{}--- actual\n{}",
+        name,
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+fn load_model_config() {
+    rltk::embedding::EMBED.lock().add_resource(
+        "raws/model.txt".to_string(),
+        include_bytes!("../../game/src/raws/raws/model.txt"),
+    );
+}
+
+fn gauge(v: (f32, f32)) -> GridCanvas {
+    let mut canvas = GridCanvas::new(GAUGE_SIZE + 1, GAUGE_SIZE + 1);
+    MainViewer::new(1).draw_vector(
+        &mut canvas,
+        (0, 0),
+        v,
+        GAUGE_MAX,
+        GAUGE_SIZE,
+        RGB::named(rltk::CYAN),
+    );
+    canvas
+}
+
+#[test]
+fn help_screen() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    help_viewer::help_screen(&mut canvas, &Theme::default(), &Bindings::default(), WIDTH, HEIGHT);
+    assert_snapshot("help_screen", &canvas);
+}
+
+#[test]
+fn bindings_screen() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    let mut viewer = BindingsViewer::new();
+    viewer.select_next();
+    viewer.message = Some(("W is already bound to Up".to_string(), RGB::named(rltk::ORANGE)));
+    viewer.draw(&mut canvas, &Bindings::default(), WIDTH, HEIGHT);
+    assert_snapshot("bindings_screen", &canvas);
+}
+
+#[test]
+fn sandbox_screen() {
+    load_model_config();
+    let mut controller = PlayerController::new();
+    let mut sandbox = Sandbox::new(30);
+    sandbox.select_next();
+    sandbox.adjust(&mut controller.physics, 2);
+    assert_eq!(sandbox.selected(), Tunable::TurnFactor);
+
+    // speed up to the right, then turn hard enough to fall
+    for direction in [(1, 0), (1, 0), (1, 0), (1, 0), (0, 1), (-1, 0), (-1, 0), (0, 0)] {
+        let moved = controller.move_player(&sandbox.table, &sandbox.player, direction);
+        sandbox.record(moved);
+    }
+
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    SandboxViewer::new().draw(&mut canvas, &sandbox, &controller, WIDTH, HEIGHT);
+    assert_snapshot("sandbox_screen", &canvas);
+}
+
+#[test]
+fn shop_screen() {
+    let items = shop::items_from_config(include_str!("../../game/src/raws/raws/shop.txt"));
+    let mut progress = Progress::new();
+    progress.money = 400;
+    for id in ["bearings", "bearings", "grip_tape", "wax"] {
+        let item = items.iter().find(|i| i.id == id).unwrap();
+        progress.buy(item).unwrap();
+    }
+
+    let mut viewer = ShopViewer::new();
+    viewer.select_next(items.len());
+    viewer.message = Some(("Bought Grip Tape".to_string(), RGB::named(rltk::GREEN)));
+
+    let mut canvas = GridCanvas::new(WIDTH, 30);
+    viewer.draw(&mut canvas, &items, &progress, WIDTH, 30);
+    assert_snapshot("shop_screen", &canvas);
+}
+
+#[test]
+fn bindings_screen_capturing() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    let mut viewer = BindingsViewer::new();
+    viewer.capture = Some(Capture::Add);
+    viewer.draw(&mut canvas, &Bindings::from_config("up W
+chat W
+"), WIDTH, HEIGHT);
+    assert_snapshot("bindings_screen_capturing", &canvas);
+}
+
+#[test]
+fn main_menu_screen() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    main_menu_viewer::main_menu_screen(
+        &mut canvas,
+        "Default",
+        &RunMode::Quota(5),
+        "Heavy",
+        2,
+        WIDTH,
+        HEIGHT,
+    );
+    assert_snapshot("main_menu_screen", &canvas);
+}
+
+fn some_high_scores() -> HighScores {
+    let mut scores = HighScores::new(10);
+    for (score, delivered, falls) in [(1200, 4, 2), (310, 1, 0), (2750, 7, 5)] {
+        scores.add(RunStats {
+            score,
+            delivered,
+            time: 45.5 * delivered as f32,
+            n_falls: falls,
+            distance: 60.25 * delivered as f32,
+            seed: 1234567 + score as u64,
+            map_size: (80, 50),
+        });
+    }
+    scores
+}
+
+#[test]
+fn high_scores_screen() {
+    let mut canvas = GridCanvas::new(WIDTH, 20);
+    high_scores_viewer::high_scores_screen(&mut canvas, &some_high_scores(), None, WIDTH, 20);
+    assert_snapshot("high_scores_screen", &canvas);
+}
+
+fn finished_run() -> RunStats {
+    RunStats {
+        score: 1200,
+        delivered: 4,
+        time: 182.0,
+        n_falls: 2,
+        distance: 241.0,
+        seed: 1235767,
+        map_size: (80, 50),
+    }
+}
+
+#[test]
+fn game_over_screen_new_high_score() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    gameover_viewer::game_over_screen(
+        &mut canvas,
+        (&finished_run(), &[]),
+        &RunMode::TimedShift(300.0),
+        Some(RunEnd::ShiftOver),
+        (&some_high_scores(), Some(1)),
+        WIDTH,
+        HEIGHT,
+    );
+    assert_snapshot("game_over_screen_new_high_score", &canvas);
+}
+
+#[test]
+fn game_over_screen_out_of_falls() {
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    gameover_viewer::game_over_screen(
+        &mut canvas,
+        (&finished_run(), &[]),
+        &RunMode::Survival(2),
+        Some(RunEnd::OutOfFalls),
+        (&HighScores::new(10), None),
+        WIDTH,
+        HEIGHT,
+    );
+    assert_snapshot("game_over_screen_out_of_falls", &canvas);
+}
+
+#[test]
+fn game_over_screen_standings() {
+    let second = RunStats {
+        score: 450,
+        delivered: 2,
+        n_falls: 4,
+        ..finished_run()
+    };
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    gameover_viewer::game_over_screen(
+        &mut canvas,
+        (&finished_run(), &[(1, finished_run()), (0, second)]),
+        &RunMode::Quota(4),
+        Some(RunEnd::QuotaMet),
+        (&some_high_scores(), None),
+        WIDTH,
+        HEIGHT,
+    );
+    assert_snapshot("game_over_screen_standings", &canvas);
+}
+
+#[test]
+fn msg_log_wraps() {
+    let mut viewer = MainViewer::new(8);
+    viewer.add_message(MessageKind::Hint, "Find the package".to_string());
+    viewer.add_colored_message(
+        MessageKind::Delivery,
+        "Picked up package, find the skater wearing this color shirt".to_string(),
+        RGB::named(rltk::CYAN),
+    );
+    viewer.add_message(MessageKind::Fall, "Aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaah!".to_string());
+
+    let mut canvas = GridCanvas::new(26, 9);
+    viewer.draw_msg_log(&mut canvas, 0, 0, 25, 8);
+    assert_snapshot("msg_log_wraps", &canvas);
+}
+
+#[test]
+fn msg_log_drops_what_does_not_fit() {
+    let mut viewer = MainViewer::new(16);
+    for i in 0..10 {
+        viewer.add_message(MessageKind::System, format!("message {}", i));
+    }
+
+    let mut canvas = GridCanvas::new(26, 5);
+    viewer.draw_msg_log(&mut canvas, 0, 0, 25, 4);
+    assert_snapshot("msg_log_drops_what_does_not_fit", &canvas);
+}
+
+#[test]
+fn msg_log_counts_repeats() {
+    let mut viewer = MainViewer::new(16);
+    viewer.add_message(MessageKind::Hint, "There's a job to the north".to_string());
+    for _ in 0..3 {
+        viewer.add_message(MessageKind::Chat, "Stick it to the man".to_string());
+    }
+
+    let mut canvas = GridCanvas::new(30, 4);
+    viewer.draw_msg_log(&mut canvas, 0, 0, 29, 3);
+    assert_snapshot("msg_log_counts_repeats", &canvas);
+}
+
+fn history_log() -> MessageLog {
+    let mut log = MessageLog::new(64);
+    for i in 0..12 {
+        log.add(MessageKind::Hint, format!("There's a job {} blocks away", i));
+        log.add(MessageKind::Chat, "Stick it to the man".to_string());
+        log.add(MessageKind::Chat, "Stick it to the man".to_string());
+    }
+    log.add(MessageKind::Fall, "You fell over".to_string());
+    log
+}
+
+#[test]
+fn history_screen() {
+    let log = history_log();
+    let mut history = HistoryViewer::new();
+    let mut canvas = GridCanvas::new(60, 12);
+    history.draw(&mut canvas, &log, 60, 12);
+    assert_snapshot("history_screen", &canvas);
+}
+
+#[test]
+fn history_screen_scrolled_and_filtered() {
+    let log = history_log();
+    let mut history = HistoryViewer::new();
+    history.set_filter(1);
+    history.scroll_up(3);
+    let mut canvas = GridCanvas::new(60, 12);
+    history.draw(&mut canvas, &log, 60, 12);
+    assert_snapshot("history_screen_scrolled_and_filtered", &canvas);
+}
+
+#[test]
+fn gauge_zero() {
+    assert_snapshot("gauge_zero", &gauge((0.0, 0.0)));
+}
+
+#[test]
+fn gauge_max() {
+    assert_snapshot("gauge_max", &gauge((GAUGE_MAX, 0.0)));
+}
+
+#[test]
+fn gauge_diagonal() {
+    let d = GAUGE_MAX / 2.0_f32.sqrt();
+    assert_snapshot("gauge_diagonal", &gauge((-d, d)));
+}
+
+#[test]
+fn gauge_beyond_threshold() {
+    assert_snapshot("gauge_beyond_threshold", &gauge((GAUGE_MAX * 3.0, -GAUGE_MAX * 3.0)));
+}
+
+#[test]
+fn layout_small_map_is_centered() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(20, 10);
+    table.update_platforms();
+    let player = Player::new(3, 2);
+
+    let mut viewer = MainViewer::new(8);
+    viewer.reveal_all = true;
+    viewer.add_message(MessageKind::Hint, "Find the package".to_string());
+
+    let mut goals = GoalTable::new();
+    goals.add_goal((15, 7), (0, RGB::named(rltk::CYAN)));
+
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    viewer.draw_layout(
+        &mut canvas,
+        &Scene {
+            table: &table,
+            goals: &goals,
+            opponents: &[],
+            controller: &controller,
+        },
+        (&player, 0, None),
+        WIDTH,
+        HEIGHT,
+    );
+    assert_snapshot("layout_small_map_is_centered", &canvas);
+}
+
+#[test]
+fn split_layout_two_couriers() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(90, 16);
+    table.update_platforms();
+    let first = Player::new(3, 2);
+    let second = Player::new(85, 13);
+
+    let mut viewer = MainViewer::new(8);
+    viewer.reveal_all = true;
+    viewer.add_message(MessageKind::Hint, "Courier 2: Find the package".to_string());
+
+    let mut goals = GoalTable::new();
+    goals.add_goal((15, 7), (0, RGB::named(rltk::CYAN)));
+    // the game moves the cameras once a turn is played
+    for (index, player) in [first, second].iter().enumerate() {
+        viewer.split_camera(index).follow(player.xy(), player.speed);
+    }
+
+    let mut canvas = GridCanvas::new(WIDTH, HEIGHT);
+    let scene = Scene {
+        table: &table,
+        goals: &goals,
+        opponents: &[],
+        controller: &controller,
+    };
+    viewer.draw_split_layout(&mut canvas, &scene, &[(first, 120), (second, 0)], 1, WIDTH, HEIGHT);
+    assert_snapshot("split_layout_two_couriers", &canvas);
+}
+
+#[test]
+fn trajectory_overlay() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(20, 10);
+    for y in 0..10 {
+        table.set_obstacle((6, y), Obstacle::Wall);
+    }
+    table.update_platforms();
+    let mut player = Player::new(5, 4);
+    player.speed = (1.5, 0.0);
+
+    let mut viewer = MainViewer::new(1);
+    viewer.reveal_all = true;
+    viewer.show_trajectory = true;
+
+    let mut canvas = GridCanvas::new(20, 10);
+    let scene = Scene {
+        table: &table,
+        goals: &GoalTable::new(),
+        opponents: &[],
+        controller: &controller,
+    };
+    viewer.draw_table(&mut canvas, (0, 0), &scene, &player, 20, 10);
+    viewer.draw_trajectory(&mut canvas, (0, 0), (20, 10), &scene, &player);
+    assert_snapshot("trajectory_overlay", &canvas);
+}
+
+#[test]
+fn danger_glyphs_and_recipient_letters() {
+    load_model_config();
+    let controller = PlayerController::new();
+
+    let mut table = ObstacleTable::new(12, 7);
+    for y in 0..7 {
+        table.set_obstacle((6, y), Obstacle::Wall);
+    }
+    table.update_platforms();
+    let mut player = Player::new(5, 3);
+    player.speed = (1.5, 0.0);
+
+    let theme = Theme {
+        danger_glyphs: true,
+        recipient_letters: true,
+        ..Theme::default()
+    };
+    let mut goals = GoalTable::new();
+    goals.add_goal((9, 5), (0, theme.shirts[2]));
+
+    let recipient = AIController::new(2, 1);
+    table.blocked.insert((2, 1), recipient.player);
+
+    let mut viewer = MainViewer::new(1);
+    viewer.reveal_all = true;
+    viewer.theme = theme;
+
+    let mut canvas = GridCanvas::new(12, 7);
+    let scene = Scene {
+        table: &table,
+        goals: &goals,
+        opponents: &[recipient],
+        controller: &controller,
+    };
+    viewer.draw_table(&mut canvas, (0, 0), &scene, &player, 12, 7);
+    assert_snapshot("danger_glyphs_and_recipient_letters", &canvas);
+}
@@ -0,0 +1,55 @@
+use rltk::RGB;
+
+use view::theme::{self, Theme};
+
+const THEMES: &str = include_str!("../../game/src/raws/raws/themes.txt");
+
+#[test]
+fn raws_themes_parse() {
+    let themes = theme::themes_from_config(THEMES);
+    let names: Vec<&str> = themes.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Default", "Red-Green Safe", "Blue-Yellow Safe", "High Contrast"]
+    );
+
+    for theme in themes.iter() {
+        assert_eq!(theme.shirts.len(), 8, "{}", theme.name);
+        for (i, shirt) in theme.shirts.iter().enumerate() {
+            assert!(
+                !theme.shirts[..i].contains(shirt),
+                "{} has the same shirt twice",
+                theme.name
+            );
+        }
+    }
+}
+
+#[test]
+fn default_theme_in_raws_matches_built_in() {
+    let from_raws = &theme::themes_from_config(THEMES)[0];
+    let built_in = Theme::default();
+    assert_eq!(from_raws.shirts, built_in.shirts);
+    assert_eq!(from_raws.balance_low, built_in.balance_low);
+    assert_eq!(from_raws.balance_high, built_in.balance_high);
+    assert_eq!(from_raws.fall, built_in.fall);
+}
+
+#[test]
+fn missing_settings_keep_defaults() {
+    let themes = theme::themes_from_config("theme Plain\nfall 1 2 3\ndanger_glyphs 1\n");
+    assert_eq!(themes.len(), 1);
+    assert_eq!(themes[0].name, "Plain");
+    assert_eq!(themes[0].fall, RGB::from_u8(1, 2, 3));
+    assert!(themes[0].danger_glyphs);
+    assert_eq!(themes[0].shirts, Theme::default().shirts);
+    assert_eq!(themes[0].balance_low, Theme::default().balance_low);
+}
+
+#[test]
+fn shirts_have_letters() {
+    let theme = Theme::default();
+    assert_eq!(theme.shirt_letter(theme.shirts[0]), Some('A'));
+    assert_eq!(theme.shirt_letter(theme.shirts[7]), Some('H'));
+    assert_eq!(theme.shirt_letter(RGB::from_u8(1, 2, 3)), None);
+}